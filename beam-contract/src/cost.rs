@@ -1,9 +1,21 @@
-use oracle::settings;
-use oracle::types::FeeConfig;
-use soroban_sdk::{contracttype, token, Address, Env, Vec};
+use core::borrow::Borrow;
+use oracle::types::{Asset, Error, FeeConfig};
+use oracle::{assets, events, fees, settings, timestamps};
+use soroban_sdk::{contracttype, panic_with_error, token::TokenClient, Address, Env, Vec};
 
 const COST_CONFIG_KEY: &str = "cost";
-const SCALE: i128 = 10_000_000;
+const TTL_SUBSIDY_KEY: &str = "ttl_subsidy";
+const COST_SCALE_KEY: &str = "cost_scale";
+const AUDIT_MODE_KEY: &str = "audit_mode";
+const CALLER_DISCOUNT_KEY: &str = "discount";
+const DEFAULT_COSTS_KEY: &str = "default_costs";
+const DEFAULT_SCALE: u64 = 10_000_000;
+//caller discount entries are extended once their TTL drops below ~30 days (in ledgers)
+const DISCOUNT_TTL_THRESHOLD: u32 = 518_400;
+const DEFAULT_COSTS_DECIMALS: u32 = 7;
+//default costs are defined for 7-decimal fee tokens
+// RecordsModifier, Price, Twap, CrossPrice, CrossTwap
+const DEFAULT_COSTS: [u64; 5] = [2_000_000, 10_000_000, 15_000_000, 20_000_000, 30_000_000];
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -27,44 +39,275 @@ pub fn set_costs_config(e: &Env, costs: &Vec<u64>) {
     e.storage().instance().set(&COST_CONFIG_KEY, &costs);
 }
 
+// Throw exception if invocation costs config doesn't cover all invocation categories
+pub fn panic_if_invalid_costs_config(e: &Env, costs: &Vec<u64>) {
+    if costs.len() != InvocationComplexity::CrossTwap as u32 + 1 {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+}
+
 // Load config containing invocation costs
 pub fn load_costs_config(e: &Env) -> Vec<u64> {
+    let instance = e.storage().instance();
+    instance
+        .get(&COST_CONFIG_KEY)
+        .or_else(|| instance.get(&DEFAULT_COSTS_KEY))
+        .unwrap_or_else(|| Vec::from_array(e, DEFAULT_COSTS))
+}
+
+// Scale default invocation costs to the fee token decimals, should be called whenever the fee config changes
+// to avoid querying the fee token decimals on every invocation
+pub fn update_default_costs(e: &Env) {
+    let decimals = resolve_fee_token_decimals(e);
+    let mut costs = Vec::new(e);
+    for cost in DEFAULT_COSTS {
+        match scale_default_cost(cost, decimals) {
+            Some(cost) => costs.push_back(cost),
+            //fee token precision is too high to express default costs
+            None => panic_with_error!(e, Error::InvalidAmount),
+        }
+    }
+    e.storage().instance().set(&DEFAULT_COSTS_KEY, &costs);
+}
+
+// Resolve decimals of the explicitly configured fee token (XRF token decimals are assumed otherwise)
+fn resolve_fee_token_decimals(e: &Env) -> u32 {
+    if settings::has_fee_config(e) {
+        if let FeeConfig::Some((fee_token, _)) = settings::get_fee_config(e) {
+            return TokenClient::new(e, &fee_token).decimals();
+        }
+    }
+    DEFAULT_COSTS_DECIMALS
+}
+
+// Scale default cost defined for 7-decimal fee tokens to the fee token decimals, returns None on overflow
+fn scale_default_cost(cost: u64, decimals: u32) -> Option<u64> {
+    if decimals >= DEFAULT_COSTS_DECIMALS {
+        10u64
+            .checked_pow(decimals - DEFAULT_COSTS_DECIMALS)
+            .and_then(|multiplier| cost.checked_mul(multiplier))
+    } else {
+        Some(
+            10u64
+                .checked_pow(DEFAULT_COSTS_DECIMALS - decimals)
+                .map_or(0, |divisor| cost / divisor),
+        )
+    }
+}
+
+// Load invocation costs paired with corresponding InvocationComplexity discriminants
+pub fn load_invocation_catalog(e: &Env) -> Vec<(u32, u64)> {
+    let mut catalog = Vec::new(e);
+    for (complexity, cost) in load_costs_config(e).iter().enumerate() {
+        catalog.push_back((complexity as u32, cost));
+    }
+    catalog
+}
+
+// Update share of the invocation fee (in basis points) allocated to the quoted asset TTL extension
+#[inline]
+pub fn set_ttl_subsidy(e: &Env, share: u32) {
+    if share > 10_000 {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+    e.storage().instance().set(&TTL_SUBSIDY_KEY, &share);
+}
+
+// Load share of the invocation fee (in basis points) allocated to the quoted asset TTL extension
+#[inline]
+pub fn get_ttl_subsidy(e: &Env) -> u32 {
     e.storage()
         .instance()
-        .get(&COST_CONFIG_KEY)
-        .unwrap_or_else(|| {
-            Vec::from_array(
-                e, // RecordsModifier, Price, Twap, CrossPrice, CrossTwap
-                [2_000_000, 10_000_000, 15_000_000, 20_000_000, 30_000_000],
-            )
-        })
+        .get(&TTL_SUBSIDY_KEY)
+        .unwrap_or_default()
+}
+
+// Update precision of the period modifier surcharge
+#[inline]
+pub fn set_cost_scale(e: &Env, scale: u64) {
+    if scale == 0 {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+    e.storage().instance().set(&COST_SCALE_KEY, &scale);
+}
+
+// Load precision of the period modifier surcharge
+#[inline]
+pub fn get_cost_scale(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&COST_SCALE_KEY)
+        .unwrap_or(DEFAULT_SCALE)
+}
+
+// Enable or disable read events publishing for paid reads
+#[inline]
+pub fn set_audit_mode(e: &Env, audit_mode: bool) {
+    e.storage().instance().set(&AUDIT_MODE_KEY, &audit_mode);
+}
+
+// Check whether paid reads publish audit events
+#[inline]
+pub fn get_audit_mode(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&AUDIT_MODE_KEY)
+        .unwrap_or_default()
+}
+
+// Update invocation fee discount (in basis points) granted to the caller, zero removes the discount
+pub fn set_caller_discount(e: &Env, caller: &Address, discount: u32) {
+    if discount > 10_000 {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+    //discounts are stored per caller, so the number of discounted callers doesn't affect instance size
+    let key = (CALLER_DISCOUNT_KEY, caller.clone());
+    let storage = e.storage().persistent();
+    if discount == 0 {
+        storage.remove(&key);
+    } else {
+        storage.set(&key, &discount);
+        storage.extend_ttl(&key, DISCOUNT_TTL_THRESHOLD, e.storage().max_ttl());
+    }
+}
+
+// Load invocation fee discount (in basis points) granted to the caller
+pub fn get_caller_discount(e: &Env, caller: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&(CALLER_DISCOUNT_KEY, caller.clone()))
+        .unwrap_or_default()
+}
+
+// Keep the discount entry of the paying caller alive
+fn extend_caller_discount_ttl(e: &Env, caller: &Address) {
+    let key = (CALLER_DISCOUNT_KEY, caller.clone());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, DISCOUNT_TTL_THRESHOLD, e.storage().max_ttl());
+}
+
+// Publish read event attributing the invocation to the payer if audit mode is enabled
+fn audit_read(e: &Env, payer: &Address, assets: &[&Asset], invocation: &InvocationComplexity) {
+    if get_audit_mode(e) {
+        let timestamp = timestamps::ledger_timestamp(e);
+        events::publish_read_event(e, payer, assets, invocation.clone() as u32, timestamp);
+    }
 }
 
-// Charge per-invocation fee
+// Charge per-invocation fee, returns charged amount
 pub fn charge_invocation_fee(
     e: &Env,
     caller: &Address,
+    assets: &[&Asset],
     invocation: InvocationComplexity,
     periods: u32,
-) {
+) -> i128 {
+    audit_read(e, caller, assets, &invocation);
     //load fee config
     let fee_config = settings::get_fee_config(e);
     if let FeeConfig::Some((fee_token, _)) = fee_config.clone() {
         //calculate amount to charge
-        let cost = estimate_invocation_cost(e, invocation, periods, fee_config);
+        let multiplier = resolve_fee_multiplier(e, assets.iter().copied());
+        let discount = get_caller_discount(e, caller);
+        let (_, _, cost) =
+            calculate_cost_breakdown(e, multiplier, discount, invocation, periods, fee_config);
+        //only discounted callers have an entry to keep alive
+        if discount > 0 {
+            extend_caller_discount_ttl(e, caller);
+        }
         if cost <= 0 {
-            return;
+            return 0;
+        }
+        //charge tokens
+        fees::charge(e, &fee_token, caller, cost);
+        return cost;
+    }
+    0
+}
+
+// Reject queries for unsupported assets before charging the invocation fee
+pub fn panic_if_asset_missing(e: &Env, asset: &Asset) {
+    if assets::resolve_asset_index(e, asset).is_none() {
+        panic_with_error!(e, Error::AssetMissing);
+    }
+}
+
+// Extend quoted asset TTL using the configured share of the charged invocation fee
+pub fn subsidize_asset_ttl(e: &Env, asset: &Asset, charged: i128) {
+    let share = get_ttl_subsidy(e);
+    if share == 0 || charged <= 0 {
+        return; //subsidy disabled or nothing has been charged
+    }
+    let fee = match settings::get_fee_config(e) {
+        FeeConfig::Some((_, fee)) if fee > 0 => fee,
+        _ => return,
+    };
+    let asset_index = match assets::resolve_asset_index(e, asset) {
+        Some(index) => index,
+        None => return,
+    };
+    //calculate extension period based on the allocated share
+    let bump = assets::calculate_extension(charged * share as i128 / 10_000, fee);
+    if bump > 0 {
+        assets::extend_expiration(e, asset_index, bump as u64, 0);
+    }
+}
+
+// Charge per-invocation fee split between multiple payers, returns charged amount
+// (caller discounts are not applied, as the shares must sum up to the full invocation cost)
+pub fn charge_split_invocation_fee(
+    e: &Env,
+    payers: &Vec<(Address, i128)>,
+    assets: &[&Asset],
+    invocation: InvocationComplexity,
+    periods: u32,
+) -> i128 {
+    let fee_config = settings::get_fee_config(e);
+    let cost = estimate_invocation_cost(e, assets, invocation.clone(), periods, fee_config.clone());
+    //validate shares before charging anyone
+    let mut total = 0i128;
+    for (_, share) in payers.iter() {
+        if share < 0 {
+            panic_with_error!(e, Error::InvalidAmount);
+        }
+        total = total.checked_add(share).unwrap();
+    }
+    if total != cost {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+    for (payer, _) in payers.iter() {
+        payer.require_auth();
+        audit_read(e, &payer, assets, &invocation);
+    }
+    if let FeeConfig::Some((fee_token, _)) = fee_config {
+        //charge each payer share
+        for (payer, share) in payers.iter() {
+            if share > 0 {
+                fees::charge(e, &fee_token, &payer, share);
+            }
         }
-        //init fee token client
-        let fee_client = token::Client::new(e, &fee_token);
-        //burn tokens
-        fee_client.burn(caller, &cost);
     }
+    total
+}
+
+// Resolve the highest fee multiplier (in basis points) among the quoted assets
+pub fn resolve_fee_multiplier<A: Borrow<Asset>>(
+    e: &Env,
+    assets: impl IntoIterator<Item = A>,
+) -> u32 {
+    assets
+        .into_iter()
+        .filter_map(|asset| assets::resolve_asset_index(e, asset.borrow()))
+        .map(|asset_index| assets::get_asset_fee_multiplier(e, asset_index))
+        .max()
+        .unwrap_or(assets::DEFAULT_FEE_MULTIPLIER)
 }
 
-// Estimate invocation cost based on its complexity and fee config
+// Estimate invocation cost based on its complexity, quoted assets and fee config
 pub fn estimate_invocation_cost(
     e: &Env,
+    assets: &[&Asset],
     invocation: InvocationComplexity,
     periods: u32,
     fee_config: FeeConfig,
@@ -86,10 +329,30 @@ pub fn estimate_invocation_cost(
                     .get(InvocationComplexity::NModifier as u32)
                     .unwrap_or_default() as i128;
                 if period_modifier > 0 {
-                    cost = cost * (SCALE + (periods - 1) as i128 * period_modifier) / SCALE;
+                    let scale = get_cost_scale(e) as i128;
+                    cost = cost * (scale + (periods - 1) as i128 * period_modifier) / scale;
                 }
             }
-            cost
+            //apply premium feed multiplier
+            cost * resolve_fee_multiplier(e, assets.iter().copied()) as i128
+                / assets::DEFAULT_FEE_MULTIPLIER as i128
         }
     }
 }
+
+// Calculate invocation cost components: base cost, adjustments applied by the fee multiplier
+// and caller discount (both in basis points), and the net amount to charge
+pub fn calculate_cost_breakdown(
+    e: &Env,
+    multiplier: u32,
+    discount: u32,
+    invocation: InvocationComplexity,
+    periods: u32,
+    fee_config: FeeConfig,
+) -> (i128, i128, i128) {
+    let base = estimate_invocation_cost(e, &[], invocation, periods, fee_config);
+    //same rounding as in the invocation cost estimate
+    let cost = base * multiplier as i128 / assets::DEFAULT_FEE_MULTIPLIER as i128;
+    let net = cost - cost * discount as i128 / 10_000;
+    (base, net - base, net)
+}
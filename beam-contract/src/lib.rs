@@ -2,11 +2,22 @@
 mod cost;
 mod tests;
 
-use cost::{charge_invocation_fee, load_costs_config, set_costs_config, InvocationComplexity};
+use cost::{
+    calculate_cost_breakdown, charge_invocation_fee, charge_split_invocation_fee, get_audit_mode,
+    get_caller_discount, get_cost_scale, get_ttl_subsidy, load_costs_config,
+    load_invocation_catalog, panic_if_asset_missing, panic_if_invalid_costs_config,
+    resolve_fee_multiplier, set_audit_mode, set_caller_discount, set_costs_config, set_ttl_subsidy,
+    subsidize_asset_ttl, update_default_costs, InvocationComplexity,
+};
 use oracle::price_oracle::PriceOracleContractBase;
-use oracle::settings;
-use oracle::types::{Asset, ConfigData, FeeConfig, PriceData, PriceUpdate};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use oracle::types::{
+    Asset, CacheEvictionPolicy, ConfigData, Error, FeeConfig, PriceData, PriceStatus, PriceUpdate,
+};
+use oracle::{assets, auth, prices, settings, timestamps};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token::TokenClient, Address, Bytes, BytesN, Env,
+    Symbol, Vec,
+};
 
 #[contract]
 pub struct BeamOracleContract;
@@ -22,6 +33,33 @@ impl BeamOracleContract {
         PriceOracleContractBase::base(e)
     }
 
+    // Return base asset contract address
+    //
+    // # Returns
+    //
+    // Base asset address or None if the base is not a Stellar asset
+    pub fn base_address(e: &Env) -> Option<Address> {
+        PriceOracleContractBase::base_address(e)
+    }
+
+    // Return base asset symbol
+    //
+    // # Returns
+    //
+    // Base asset symbol or None if the base is a Stellar asset
+    pub fn base_symbol(e: &Env) -> Option<Symbol> {
+        PriceOracleContractBase::base_symbol(e)
+    }
+
+    // Return unit price of the base asset, only available while the oracle is fresh
+    //
+    // # Returns
+    //
+    // Base asset price (10^decimals) at the most recent record timestamp or None if the oracle is stale
+    pub fn base_price(e: &Env) -> Option<PriceData> {
+        PriceOracleContractBase::base_price(e)
+    }
+
     // Return number of decimal places used to represent price for all quoted assets
     //
     // # Returns
@@ -40,6 +78,15 @@ impl BeamOracleContract {
         PriceOracleContractBase::resolution(e)
     }
 
+    // Return default tick period timeframe (in milliseconds)
+    //
+    // # Returns
+    //
+    // Price feed resolution (in milliseconds), as used for history record keys
+    pub fn resolution_ms(e: &Env) -> u32 {
+        PriceOracleContractBase::resolution_ms(e)
+    }
+
     // Return historical records retention period (in seconds)
     //
     // # Returns
@@ -49,6 +96,27 @@ impl BeamOracleContract {
         PriceOracleContractBase::history_retention_period(e)
     }
 
+    // Return historical records retention period (in milliseconds)
+    //
+    // # Returns
+    //
+    // History retention period (in milliseconds)
+    pub fn history_retention_period_ms(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::history_retention_period_ms(e)
+    }
+
+    // Return history retention period and whether it is enforced
+    //
+    // Retention period controls how long price records are kept in the storage,
+    // while asset expiration only reflects the price feed payment status
+    //
+    // # Returns
+    //
+    // History retention period (in seconds) and true if the retention is enforced (non-zero period)
+    pub fn retention_info(e: &Env) -> (u64, bool) {
+        PriceOracleContractBase::retention_info(e)
+    }
+
     // Return price records cache size
     //
     // # Returns
@@ -58,345 +126,2095 @@ impl BeamOracleContract {
         PriceOracleContractBase::cache_size(e)
     }
 
-    // Return all quoted assets
+    // Return policy applied when the price records cache exceeds its size
     //
     // # Returns
     //
-    // Quoted assets
-    pub fn assets(e: &Env) -> Vec<Asset> {
-        PriceOracleContractBase::assets(e)
+    // Cache eviction policy
+    pub fn cache_eviction_policy(e: &Env) -> CacheEvictionPolicy {
+        PriceOracleContractBase::cache_eviction_policy(e)
     }
 
-    // Return most recent price update timestamp in seconds
+    // Return timestamps of price records held in the instance cache
     //
     // # Returns
     //
-    // Timestamp of last recorded price update
-    pub fn last_timestamp(e: &Env) -> u64 {
-        PriceOracleContractBase::last_timestamp(e)
+    // Cached records timestamps (in seconds), most recent first
+    pub fn cache_records(e: &Env) -> Vec<u64> {
+        PriceOracleContractBase::cache_records(e)
     }
 
-    // Return current contract protocol version
+    // Return whether missing prices cause errors instead of None results
     //
     // # Returns
     //
-    // Contract protocol version
-    pub fn version(e: &Env) -> u32 {
-        PriceOracleContractBase::version(e)
+    // Strict mode flag
+    pub fn strict_mode(e: &Env) -> bool {
+        PriceOracleContractBase::strict_mode(e)
     }
 
-    // Return expiration date for a given asset
+    // Return whether history records are read from the temporary storage before the cache
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Quoted asset
+    // Storage-first read strategy flag
+    pub fn storage_first(e: &Env) -> bool {
+        PriceOracleContractBase::storage_first(e)
+    }
+
+    // Return feeder public keys allowed to sign price attestations
     //
     // # Returns
     //
-    // Asset expiration timestamp or None if asset is not supported
+    // Feeder ed25519 public keys with the feeder addresses they are linked to
+    pub fn feeder_keys(e: &Env) -> Vec<(BytesN<32>, Address)> {
+        PriceOracleContractBase::feeder_keys(e)
+    }
+
+    // Return allowed feeders with the time of their last price update
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if asset is not supported
-    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
-        PriceOracleContractBase::expires(e, asset)
+    // Feeder addresses with last activity timestamps (in seconds, zero if never submitted)
+    pub fn feeder_activity(e: &Env) -> Vec<(Address, u64)> {
+        PriceOracleContractBase::feeder_activity(e)
     }
 
-    // Extends asset expiration date by a given amount of tokens.
+    // Return timestamps of price records whose storage TTL is estimated to end within the given number of ledgers
     //
     // # Arguments
     //
-    // * `sponsor` - Address that sponsors price feed
-    // * `asset` - Quoted asset
-    // * `amount` - Amount of tokens to burn for extending the expiration date
+    // * `within_ledgers` - Number of ledgers to look ahead
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if asset is not supported or if retention config is malformed/missing
-    pub fn extend_asset_ttl(e: &Env, sponsor: Address, asset: Asset, amount: i128) {
-        PriceOracleContractBase::extend_asset_ttl(e, sponsor, asset, amount, 0);
+    // Timestamps (in seconds) of the records at risk of expiration
+    pub fn records_at_risk(e: &Env, within_ledgers: u32) -> Vec<u64> {
+        PriceOracleContractBase::records_at_risk(e, within_ledgers)
     }
 
-    // Return fee token address daily price feed retainer fee amount
+    // Return whether price updates are paused
     //
     // # Returns
     //
-    // Fee token address and daily price feed retainer fee amount
-    pub fn fee_config(e: &Env) -> FeeConfig {
-        PriceOracleContractBase::fee_config(e)
+    // Paused flag
+    pub fn paused(e: &Env) -> bool {
+        PriceOracleContractBase::paused(e)
     }
 
-    // Retrieve current invocation costs config
+    // Return price deviation limits applied to new price updates
     //
     // # Returns
     //
-    // Invocation costs categorized by complexity
-    pub fn invocation_costs(e: &Env) -> Vec<u64> {
-        load_costs_config(e)
+    // Reject and auto-pause thresholds (in basis points) or None if not set
+    pub fn deviation_limits(e: &Env) -> Option<(u32, u32)> {
+        PriceOracleContractBase::deviation_limits(e)
     }
 
-    // Estimate invocation cost based on its complexity
+    // Return minimum number of recent updates required to serve asset prices
     //
-    // # Arguments
+    // # Returns
     //
-    // * `invocation` - Invocation type (single price check, cross-price, TWAP, etc.)
-    // * `periods` - Number of requested history periods
+    // Required number of updates and the number of recent periods to check, or None if not set
+    pub fn min_coverage(e: &Env) -> Option<(u32, u32)> {
+        PriceOracleContractBase::min_coverage(e)
+    }
+
+    // Return minimum number of records TWAP requests should span
     //
     // # Returns
     //
-    // Amount of fee tokens required to pay for invocation
-    pub fn estimate_cost(e: &Env, invocation: InvocationComplexity, periods: u32) -> i128 {
-        let fee_config = settings::get_fee_config(e);
-        cost::estimate_invocation_cost(e, invocation, periods, fee_config)
+    // Minimum TWAP window (0 if not set)
+    pub fn min_twap_records(e: &Env) -> u32 {
+        PriceOracleContractBase::min_twap_records(e)
     }
 
-    // Return contract admin address
+    // Return maximum number of price records returned by a single query
     //
     // # Returns
     //
-    // Contract admin account address
-    pub fn admin(e: &Env) -> Option<Address> {
-        PriceOracleContractBase::admin(e)
+    // Maximum number of records
+    pub fn max_records(e: &Env) -> u32 {
+        PriceOracleContractBase::max_records(e)
     }
 
-    // Returns price  for an asset at specific timestamp
+    // Return maximum number of price snapshots accepted in a single batch
     //
-    // # Arguments
+    // # Returns
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `asset` - Asset to quote
-    // * `timestamp` - Timestamp in seconds
+    // Maximum batch size
+    pub fn max_batch_size(e: &Env) -> u32 {
+        PriceOracleContractBase::max_batch_size(e)
+    }
+
+    // Return whether price record TTLs are extended on reads
     //
     // # Returns
     //
-    // Price record for given asset at given timestamp or None if not found
-    pub fn price(e: &Env, caller: Address, asset: Asset, timestamp: u64) -> Option<PriceData> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::Price, 1);
-        PriceOracleContractBase::price(e, asset, timestamp)
+    // Read bump flag
+    pub fn read_bump(e: &Env) -> bool {
+        PriceOracleContractBase::read_bump(e)
     }
 
-    // Returns most recent price for an asset
+    // Return placeholder price served for newly added assets until their first price update
     //
-    // # Arguments
+    // # Returns
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `asset` - Asset to quote
+    // Placeholder price or None if placeholder prices are disabled
+    pub fn placeholder_price(e: &Env) -> Option<i128> {
+        PriceOracleContractBase::placeholder_price(e)
+    }
+
+    // Return maximum allowed price value
     //
     // # Returns
     //
-    // Most recent price for given asset or None if asset is not supported
-    pub fn lastprice(e: &Env, caller: Address, asset: Asset) -> Option<PriceData> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::Price, 1);
-        PriceOracleContractBase::lastprice(e, asset)
+    // Maximum price or 0 if not limited
+    pub fn max_price(e: &Env) -> i128 {
+        PriceOracleContractBase::max_price(e)
     }
 
-    // Return last N price records for given asset
+    // Return all quoted assets
     //
-    // # Arguments
+    // # Returns
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `asset` - Asset to quote
-    // * `records` - Number of records to return
+    // Quoted assets, purged assets are omitted even before their slots are compacted
+    pub fn assets(e: &Env) -> Vec<Asset> {
+        PriceOracleContractBase::assets(e)
+    }
+
+    // Return configuration snapshot for off-chain backup
     //
     // # Returns
     //
-    // Prices for given asset or None if asset is not supported
-    pub fn prices(e: &Env, caller: Address, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::Price, records);
-        PriceOracleContractBase::prices(e, asset, records)
+    // Quoted assets, asset expiration timestamps, most recent price update timestamp (in seconds) and fee config
+    pub fn export_snapshot(e: &Env) -> (Vec<Asset>, Vec<u64>, u64, FeeConfig) {
+        PriceOracleContractBase::export_snapshot(e)
     }
 
-    // Returns most recent cross price record for pair of assets
+    // Return assets updated in the price record at specific timestamp
     //
     // # Arguments
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp in seconds
     //
     // # Returns
     //
-    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
-    pub fn x_last_price(
-        e: &Env,
-        caller: Address,
-        base_asset: Asset,
-        quote_asset: Asset,
-    ) -> Option<PriceData> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::CrossPrice, 1);
-        PriceOracleContractBase::x_last_price(e, base_asset, quote_asset)
+    // Assets included in the update record or empty vector if record not found
+    pub fn updated_assets_at(e: &Env, timestamp: u64) -> Vec<Asset> {
+        PriceOracleContractBase::updated_assets_at(e, timestamp)
     }
 
-    // Return cross price for pair of assets at specific timestamp
-    //
-    // # Arguments
-    //
-    // * `caller` - Caller that covers invocation cost
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `timestamp` - Timestamp
+    // Return assets omitted from the most recent price update
     //
     // # Returns
     //
-    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
-    pub fn x_price(
-        e: &Env,
-        caller: Address,
-        base_asset: Asset,
-        quote_asset: Asset,
-        timestamp: u64,
-    ) -> Option<PriceData> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::CrossPrice, 1);
-        PriceOracleContractBase::x_price(e, base_asset, quote_asset, timestamp)
+    // Assets without a price in the most recent period or empty vector if all assets were updated
+    pub fn missing_in_latest(e: &Env) -> Vec<Asset> {
+        PriceOracleContractBase::missing_in_latest(e)
     }
 
-    // Returns last N cross price records of for pair of assets
+    // Return the most recent timestamps that have price records for given asset
     //
     // # Arguments
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to fetch
+    // * `asset` - Asset to quote
+    // * `limit` - Maximum number of timestamps to return (capped at 256)
     //
     // # Returns
     //
-    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
-    pub fn x_prices(
-        e: &Env,
-        caller: Address,
-        base_asset: Asset,
-        quote_asset: Asset,
-        records: u32,
-    ) -> Option<Vec<PriceData>> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::CrossPrice, records);
-        PriceOracleContractBase::x_prices(e, base_asset, quote_asset, records)
+    // Timestamps in seconds starting from the most recent one or empty vector if asset is not supported
+    pub fn asset_timestamps(e: &Env, asset: Asset, limit: u32) -> Vec<u64> {
+        PriceOracleContractBase::asset_timestamps(e, asset, limit)
     }
 
-    // Returns time-weighted average price for given asset over N recent records
+    // Return the oldest timestamp tracked in the history that has price record for given asset
     //
     // # Arguments
     //
-    // * `caller` - Caller that covers invocation cost
     // * `asset` - Asset to quote
-    // * `records` - Number of records to process
     //
     // # Returns
     //
-    // TWAP for the given asset over N recent records or None if asset is not supported
-    pub fn twap(e: &Env, caller: Address, asset: Asset, records: u32) -> Option<i128> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::Twap, 1);
-        PriceOracleContractBase::twap(e, asset, records)
+    // Oldest timestamp in seconds or None if there are no records or asset is not supported
+    pub fn oldest_timestamp(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::oldest_timestamp(e, asset)
     }
 
-    // Returns time-weighted average cross price for given asset pair over N recent records
+    // Return the number of periods with price records for given asset within the 256 periods tracked in the history
     //
     // # Arguments
     //
-    // * `caller` - Caller that covers invocation cost
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to process
+    // * `asset` - Asset to quote
     //
     // # Returns
     //
-    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported
-    pub fn x_twap(
-        e: &Env,
-        caller: Address,
-        base_asset: Asset,
-        quote_asset: Asset,
-        records: u32,
-    ) -> Option<i128> {
-        caller.require_auth();
-        charge_invocation_fee(e, &caller, InvocationComplexity::CrossTwap, records);
-        PriceOracleContractBase::x_twap(e, base_asset, quote_asset, records)
+    // Number of populated periods or 0 if asset is not supported
+    pub fn populated_depth(e: &Env, asset: Asset) -> u32 {
+        PriceOracleContractBase::populated_depth(e, asset)
     }
 
-    /* Admin section */
-
-    // Initializes contract configuration
-    // Requires admin authorization
+    // Run all checks performed on price update submission without storing the update
+    //
     // # Arguments
     //
-    // * `config` - Configuration parameters
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `backfill` - Validate the update as `backfill_price` submission
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized or if contract is already initialized
-    pub fn config(e: &Env, config: ConfigData) {
-        PriceOracleContractBase::config(e, config, 0);
+    // Error that would be raised by `set_price` (or `backfill_price` in backfill mode) for this update, if any,
+    // `Paused` is also returned for updates that would pause the contract due to severe price deviation
+    pub fn validate_update(
+        e: &Env,
+        update: PriceUpdate,
+        timestamp: u64,
+        backfill: bool,
+    ) -> Result<(), Error> {
+        PriceOracleContractBase::validate_update(e, update, timestamp, backfill)
     }
 
-    // Update contract cache size
-    // Requires admin authorization
+    // Preview the history mask after applying a hypothetical price update, without modifying the contract state
     //
     // # Arguments
     //
-    // * `cache_size` - New cache size (number of rounds stored in cache)
+    // * `current` - Current history mask
+    // * `prices` - Prices for all assets, zero for assets without updates
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized
-    pub fn set_cache_size(e: &Env, cache_size: u32) {
-        PriceOracleContractBase::set_cache_size(e, cache_size);
+    // Updated history mask
+    pub fn preview_history_mask(e: &Env, current: Bytes, prices: Vec<i128>) -> Bytes {
+        PriceOracleContractBase::preview_history_mask(e, current, prices)
     }
 
-    // Adds given assets to the contract quoted assets list
-    // Requires admin authorization
+    // Count assets marked as updated in the price update mask
     //
     // # Arguments
     //
-    // * `assets` - Assets to add
+    // * `mask` - Price update record mask
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
-    pub fn add_assets(e: &Env, assets: Vec<Asset>) {
-        PriceOracleContractBase::add_assets(e, assets, 0);
+    // Number of set bits, expected to match the number of prices in the update
+    pub fn mask_popcount(e: &Env, mask: Bytes) -> u32 {
+        PriceOracleContractBase::mask_popcount(e, mask)
     }
 
-    // Sets history retention period for the prices
-    // Requires admin authorization
+    // Return most recent price update timestamp in seconds
     //
-    // # Arguments
+    // # Returns
     //
-    // * `period` - History retention period (in seconds)
+    // Timestamp of last recorded price update
+    pub fn last_timestamp(e: &Env) -> u64 {
+        PriceOracleContractBase::last_timestamp(e)
+    }
+
+    // Return most recent price update timestamp in milliseconds
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized
-    pub fn set_history_retention_period(e: &Env, period: u64) {
-        PriceOracleContractBase::set_history_retention_period(e, period);
+    // Raw timestamp of last recorded price update, as used for history record keys
+    pub fn last_timestamp_ms(e: &Env) -> u64 {
+        PriceOracleContractBase::last_timestamp_ms(e)
     }
 
-    // Set fee token address and daily price feed retainer fee amount
-    // Requires admin authorization
+    // Return time remaining until the next expected price update
     //
-    // # Arguments
+    // # Returns
     //
-    // * `fee_config` - Fee token address and fee amount
+    // Seconds until the next update is due (0 if it is already overdue) or None if there is no fresh data
+    pub fn next_update_eta(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::next_update_eta(e)
+    }
+
+    // Return current contract protocol version
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized or not initialized yet
-    pub fn set_fee_config(e: &Env, config: FeeConfig) {
-        PriceOracleContractBase::set_fee_config(e, config, 0);
+    // Contract protocol version
+    pub fn version(e: &Env) -> u32 {
+        PriceOracleContractBase::version(e)
     }
 
-    // Update costs configuration per each invocation category
-    // Requires admin authorization
+    // Return expiration date for a given asset
     //
     // # Arguments
     //
-    // * `config` - Invocation costs for different invocation categories
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in milliseconds) or None if asset has no expiration record
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::expires(e, asset)
+    }
+
+    // Return expiration date for a given asset (in seconds)
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in seconds) or None if asset has no expiration record
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn expires_s(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::expires_s(e, asset)
+    }
+
+    // Return expiration timestamps for the given assets
+    //
+    // # Arguments
+    //
+    // * `assets` - Quoted assets
+    //
+    // # Returns
+    //
+    // Expiration timestamps (in seconds) aligned with the input, None for unknown assets or assets without expiration record
+    pub fn expirations_of(e: &Env, assets: Vec<Asset>) -> Vec<Option<u64>> {
+        PriceOracleContractBase::expirations_of(e, assets)
+    }
+
+    // Return time remaining until the asset price feed expiration
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Seconds until expiration (negative if already expired) or None if asset has no expiration record
+    pub fn ttl_remaining(e: &Env, asset: Asset) -> Option<i128> {
+        PriceOracleContractBase::ttl_remaining(e, asset)
+    }
+
+    // Return whether the asset price feed is not expired and has a price in the most recent record
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // True if the feed is active, false otherwise or if asset is not supported
+    pub fn is_feed_active(e: &Env, asset: Asset) -> bool {
+        PriceOracleContractBase::is_feed_active(e, asset)
+    }
+
+    // Return expiration, latest price, and active flag for the asset price feed
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Expiration timestamp (in seconds), most recent price, and active flag
+    pub fn feed_summary(e: &Env, asset: Asset) -> (Option<u64>, Option<PriceData>, bool) {
+        PriceOracleContractBase::feed_summary(e, asset)
+    }
+
+    // Extends asset expiration date by a given amount of tokens.
+    //
+    // # Arguments
+    //
+    // * `sponsor` - Address that sponsors price feed
+    // * `asset` - Quoted asset
+    // * `amount` - Amount of tokens to burn for extending the expiration date
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported or if retention config is malformed/missing
+    pub fn extend_asset_ttl(e: &Env, sponsor: Address, asset: Asset, amount: i128) {
+        PriceOracleContractBase::extend_asset_ttl(e, sponsor, asset, amount, 0);
+    }
+
+    // Return whether fee config has been explicitly set rather than defaulted
+    //
+    // # Returns
+    //
+    // True if fee config present in the contract storage
+    pub fn fee_config_explicitly_set(e: &Env) -> bool {
+        PriceOracleContractBase::fee_config_explicitly_set(e)
+    }
+
+    // Return human-readable label for a given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset label or None if not set or asset is not supported
+    pub fn asset_label(e: &Env, asset: Asset) -> Option<Symbol> {
+        PriceOracleContractBase::asset_label(e, asset)
+    }
+
+    // Return minimum price of the asset accepted as a cross-price denominator
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Price floor (zero if not set) or None if asset is not supported
+    pub fn asset_price_floor(e: &Env, asset: Asset) -> Option<i128> {
+        PriceOracleContractBase::asset_price_floor(e, asset)
+    }
+
+    // Return invocation fee multiplier for a given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Fee multiplier in basis points (10000 if not set) or None if asset is not supported
+    pub fn asset_fee_multiplier(e: &Env, asset: Asset) -> Option<u32> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        Some(assets::get_asset_fee_multiplier(e, asset_index))
+    }
+
+    // Return canonical asset the given asset is registered as an alias of
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to check
+    //
+    // # Returns
+    //
+    // Canonical asset or None if no alias is configured
+    pub fn asset_alias(e: &Env, asset: Asset) -> Option<Asset> {
+        PriceOracleContractBase::asset_alias(e, asset)
+    }
+
+    // Return price feed resolution for a given asset (in seconds)
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset resolution override or global resolution if not set, None if asset is not supported
+    pub fn asset_resolution(e: &Env, asset: Asset) -> Option<u32> {
+        PriceOracleContractBase::asset_resolution(e, asset)
+    }
+
+    // Return scheduled fee config change
+    //
+    // # Returns
+    //
+    // Pending fee config and the timestamp (in seconds) it can be applied at, or None if nothing is scheduled
+    pub fn pending_fee_config(e: &Env) -> Option<(FeeConfig, u64)> {
+        PriceOracleContractBase::pending_fee_config(e)
+    }
+
+    // Return timestamp of the last fee token migration
+    //
+    // # Returns
+    //
+    // Migration timestamp (in seconds) or None if fee token has never been migrated
+    pub fn fee_migration_timestamp(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::fee_migration_timestamp(e)
+    }
+
+    // Return fee split between burn and treasury transfer
+    //
+    // # Returns
+    //
+    // Burn share, treasury share (in basis points), and treasury address or None if all fees are burned
+    pub fn fee_split(e: &Env) -> Option<(u32, u32, Address)> {
+        PriceOracleContractBase::fee_split(e)
+    }
+
+    // Return the oracle contract balance of the configured fee token
+    //
+    // # Returns
+    //
+    // Fee token balance or 0 if fee config is not set
+    pub fn fee_balance(e: &Env) -> i128 {
+        PriceOracleContractBase::fee_balance(e)
+    }
+
+    // Check whether both assets of the pair are supported by the oracle
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // True if both assets are supported
+    pub fn pair_supported(e: &Env, base_asset: Asset, quote_asset: Asset) -> bool {
+        PriceOracleContractBase::pair_supported(e, base_asset, quote_asset)
+    }
+
+    // Check whether time-weighted average cross price for given asset pair over N recent records is available
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // True if both assets have fresh records for the entire window
+    // (false for windows shorter than the minimum TWAP window)
+    pub fn x_twap_available(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> bool {
+        PriceOracleContractBase::x_twap_available(e, base_asset, quote_asset, records)
+    }
+
+    // Return fee token address daily price feed retainer fee amount
+    //
+    // # Returns
+    //
+    // Fee token address and daily price feed retainer fee amount
+    pub fn fee_config(e: &Env) -> FeeConfig {
+        PriceOracleContractBase::fee_config(e)
+    }
+
+    // Retrieve current invocation costs config
+    //
+    // # Returns
+    //
+    // Invocation costs categorized by complexity
+    pub fn invocation_costs(e: &Env) -> Vec<u64> {
+        load_costs_config(e)
+    }
+
+    // Retrieve invocation costs paired with the InvocationComplexity discriminants
+    //
+    // # Returns
+    //
+    // Invocation complexity discriminants with corresponding configured costs
+    pub fn invocation_catalog(e: &Env) -> Vec<(u32, u64)> {
+        load_invocation_catalog(e)
+    }
+
+    // Retrieve precision of the per-period surcharge (NModifier) in invocation costs
+    //
+    // # Returns
+    //
+    // Surcharge scale, NModifier equal to the scale doubles the cost for each additional period
+    pub fn cost_scale(e: &Env) -> u64 {
+        get_cost_scale(e)
+    }
+
+    // Retrieve share of the invocation fee allocated to the quoted asset TTL extension
+    //
+    // # Returns
+    //
+    // Fee share in basis points (0 if disabled)
+    pub fn ttl_subsidy(e: &Env) -> u32 {
+        get_ttl_subsidy(e)
+    }
+
+    // Return whether paid reads publish audit events
+    //
+    // # Returns
+    //
+    // Audit mode flag
+    pub fn audit_mode(e: &Env) -> bool {
+        get_audit_mode(e)
+    }
+
+    // Estimate invocation cost based on its complexity
+    //
+    // # Arguments
+    //
+    // * `invocation` - Invocation type (single price check, cross-price, TWAP, etc.)
+    // * `periods` - Number of requested history periods
+    //
+    // # Returns
+    //
+    // Amount of fee tokens required to pay for invocation
+    pub fn estimate_cost(e: &Env, invocation: InvocationComplexity, periods: u32) -> i128 {
+        let fee_config = settings::get_fee_config(e);
+        cost::estimate_invocation_cost(e, &[], invocation, periods, fee_config)
+    }
+
+    // Itemize invocation cost for the caller, including the fee multipliers of the quoted assets and caller discount
+    // Quoted assets are required in addition to the caller and invocation type, as the fee multiplier depends on them
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `assets` - Quoted assets
+    // * `invocation` - Invocation type (single price check, cross-price, TWAP, etc.)
+    // * `periods` - Number of requested history periods
+    //
+    // # Returns
+    //
+    // Base cost, adjustments (negative for discounts), and net amount charged for the invocation
+    pub fn cost_breakdown(
+        e: &Env,
+        caller: Address,
+        assets: Vec<Asset>,
+        invocation: InvocationComplexity,
+        periods: u32,
+    ) -> (i128, i128, i128) {
+        let multiplier = resolve_fee_multiplier(e, assets);
+        let discount = get_caller_discount(e, &caller);
+        let fee_config = settings::get_fee_config(e);
+        calculate_cost_breakdown(e, multiplier, discount, invocation, periods, fee_config)
+    }
+
+    // Return invocation fee discount granted to the caller
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller address
+    //
+    // # Returns
+    //
+    // Discount in basis points (0 if not set)
+    pub fn caller_discount(e: &Env, caller: Address) -> u32 {
+        get_caller_discount(e, &caller)
+    }
+
+    // Return total amount of fee tokens burned by the contract
+    //
+    // # Returns
+    //
+    // Cumulative amount of burned fee tokens
+    pub fn total_fees_burned(e: &Env) -> i128 {
+        PriceOracleContractBase::total_fees_burned(e)
+    }
+
+    // Estimate invocation cost based on its complexity along with fee token decimals
+    //
+    // # Arguments
+    //
+    // * `invocation` - Invocation type (single price check, cross-price, TWAP, etc.)
+    // * `periods` - Number of requested history periods
+    //
+    // # Returns
+    //
+    // Amount of fee tokens required to pay for invocation and fee token decimals, or (0, 0) if fees are disabled
+    pub fn estimate_cost_formatted(
+        e: &Env,
+        invocation: InvocationComplexity,
+        periods: u32,
+    ) -> (i128, u32) {
+        let fee_config = settings::get_fee_config(e);
+        match fee_config.clone() {
+            FeeConfig::None => (0, 0),
+            FeeConfig::Some((fee_token, _)) => {
+                let cost = cost::estimate_invocation_cost(e, &[], invocation, periods, fee_config);
+                (cost, TokenClient::new(e, &fee_token).decimals())
+            }
+        }
+    }
+
+    // Return contract admin address
+    //
+    // # Returns
+    //
+    // Contract admin account address
+    pub fn admin(e: &Env) -> Option<Address> {
+        PriceOracleContractBase::admin(e)
+    }
+
+    // Returns price  for an asset at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn price(e: &Env, caller: Address, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::price(e, asset, timestamp)
+    }
+
+    // Returns price for an asset at specific timestamp, distinguishing future-dated requests from missing data
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found,
+    // InvalidTimestamp error if the timestamp is ahead of the current ledger time
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn price_checked(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        timestamp: u64,
+    ) -> Result<Option<PriceData>, Error> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::price_checked(e, asset, timestamp)
+    }
+
+    // Returns raw stored price for an asset at specific timestamp, without timestamp conversion
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `timestamp_ms` - Timestamp in milliseconds
+    //
+    // # Returns
+    //
+    // Price exactly as stored in the record for given asset at given timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn raw_asset_price(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        timestamp_ms: u64,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::raw_asset_price(e, asset, timestamp_ms)
+    }
+
+    // Returns price for an asset at specific timestamp along with the number of periods
+    // elapsed since the record until the last price update
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record with its period offset relative to the last update timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn price_with_age_periods(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        timestamp: u64,
+    ) -> Option<(PriceData, u32)> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::price_with_age_periods(e, asset, timestamp)
+    }
+
+    // Returns price for an asset at the time the ledger with given sequence has been closed
+    // Ledger close time is not stored, so it is approximated from the current ledger assuming
+    // 5-second ledger close time, which may shift the result to the adjacent period
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `ledger_seq` - Ledger sequence
+    //
+    // # Returns
+    //
+    // Price record for given asset at the approximated ledger timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn price_at_ledger(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        ledger_seq: u32,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        let timestamp = timestamps::ledger_sequence_to_timestamp(e, ledger_seq)?;
+        PriceOracleContractBase::price(e, asset, timestamps::to_seconds(timestamp))
+    }
+
+    // Returns most recent price for an asset
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn lastprice(e: &Env, caller: Address, asset: Asset) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::lastprice(e, asset)
+    }
+
+    // Returns most recent price for an asset, invocation cost is split between multiple payers
+    // Caller discounts don't apply to split invocations, shares must sum up to the full invocation cost
+    //
+    // # Arguments
+    //
+    // * `payers` - Payers with their shares of the invocation cost
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported, shares don't sum up to the invocation cost or any of the payers hasn't authorized the call
+    pub fn lastprice_split(
+        e: &Env,
+        payers: Vec<(Address, i128)>,
+        asset: Asset,
+    ) -> Option<PriceData> {
+        panic_if_asset_missing(e, &asset);
+        let charged =
+            charge_split_invocation_fee(e, &payers, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::lastprice(e, asset)
+    }
+
+    // Returns most recent price for an asset along with the record source, used for cache tuning
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price or None if not found, and whether the record has been served from the cache
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn lastprice_debug(e: &Env, caller: Address, asset: Asset) -> (Option<PriceData>, bool) {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::lastprice_debug(e, asset)
+    }
+
+    // Returns most recent price for an asset along with the reason if it is unavailable
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price, or the status explaining why the price is not available
+    // (unsupported assets are reported without charging the caller)
+    pub fn lastprice_status(e: &Env, caller: Address, asset: Asset) -> PriceStatus {
+        caller.require_auth();
+        if assets::resolve_asset_index(e, &asset).is_none() {
+            return PriceStatus::Unsupported;
+        }
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::lastprice_status(e, asset)
+    }
+
+    // Returns most recent price for an asset if it is within allowed deviation from the reference price
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `reference_price` - Expected price
+    // * `max_deviation_bps` - Maximum allowed deviation from the reference price (in basis points)
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if not found or the asset has no price updates yet
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported, reference price is not positive or the price deviates more than allowed
+    pub fn price_within(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        reference_price: i128,
+        max_deviation_bps: u32,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::price_within(e, asset, reference_price, max_deviation_bps)
+    }
+
+    // Estimates execution price for a hypothetical trade using a linear impact model:
+    // price * (depth + notional) / depth
+    //
+    // The oracle doesn't track liquidity, so the result is a rough model
+    // based on the caller-supplied depth rather than an observed market price
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `notional` - Trade size (positive for buys, negative for sells)
+    // * `depth_param` - Liquidity depth, notional equal to depth doubles the price
+    //
+    // # Returns
+    //
+    // Estimated execution price or None if price not found (placeholder prices are ignored) or calculation overflows
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported or depth is not positive
+    pub fn price_impact(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        notional: i128,
+        depth_param: i128,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::price_impact(e, asset, notional, depth_param)
+    }
+
+    // Return last N price records for given asset
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to return
+    //
+    // # Returns
+    //
+    // Prices for given asset or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn prices(e: &Env, caller: Address, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged =
+            charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, records);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::prices(e, asset, records)
+    }
+
+    // Return price records for given asset within the time range, capped by the max records setting,
+    // invocation is charged per returned record
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    //
+    // # Returns
+    //
+    // Most recent prices within the range
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn prices_range(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+    ) -> Option<Vec<PriceData>> {
+        Self::prices_range_paged(e, caller, asset, from_timestamp, to_timestamp, 0)
+            .map(|(prices, _)| prices)
+    }
+
+    // Return a page of price records for given asset within the time range,
+    // invocation is charged per returned record
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    // * `offset` - Number of most recent records within the range to skip
+    //
+    // # Returns
+    //
+    // Prices within the range (at most max records) along with the flag indicating whether
+    // the range contains more records
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn prices_range_paged(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+        offset: u32,
+    ) -> Option<(Vec<PriceData>, bool)> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let page = PriceOracleContractBase::prices_range_paged(
+            e,
+            asset.clone(),
+            from_timestamp,
+            to_timestamp,
+            offset,
+        );
+        let records = page.as_ref().map_or(0, |(prices, _)| prices.len()).max(1);
+        let charged =
+            charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Price, records);
+        subsidize_asset_ttl(e, &asset, charged);
+        page
+    }
+
+    // Returns most recent cross price record for pair of assets
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_last_price(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossPrice,
+            1,
+        );
+        PriceOracleContractBase::x_last_price(e, base_asset, quote_asset)
+    }
+
+    // Returns spread between the highest and the lowest recent cross price of the base asset
+    // against each of the quote assets, invocation is charged per quote asset
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quotes` - Quote assets
+    //
+    // # Returns
+    //
+    // Spread (in basis points of the lowest cross price) or None if less than two cross prices are available
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn basket_cross_spread(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quotes: Vec<Asset>,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        for quote_asset in quotes.iter() {
+            panic_if_asset_missing(e, &quote_asset);
+        }
+        for quote_asset in quotes.iter() {
+            charge_invocation_fee(
+                e,
+                &caller,
+                &[&base_asset, &quote_asset],
+                InvocationComplexity::CrossPrice,
+                1,
+            );
+        }
+        PriceOracleContractBase::basket_cross_spread(e, base_asset, quotes)
+    }
+
+    // Returns implied price of the oracle base asset in a weighted basket of quote assets,
+    // invocation is charged per quote asset
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `quotes` - Quote assets
+    // * `weights` - Weights of the quote assets, in the same order
+    //
+    // # Returns
+    //
+    // Weighted average of the base asset cross prices or None if any of the quotes is unavailable or weights mismatch
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn base_in_basket(
+        e: &Env,
+        caller: Address,
+        quotes: Vec<Asset>,
+        weights: Vec<i128>,
+    ) -> Option<i128> {
+        caller.require_auth();
+        for quote_asset in quotes.iter() {
+            panic_if_asset_missing(e, &quote_asset);
+        }
+        for quote_asset in quotes.iter() {
+            charge_invocation_fee(
+                e,
+                &caller,
+                &[&quote_asset],
+                InvocationComplexity::CrossPrice,
+                1,
+            );
+        }
+        PriceOracleContractBase::base_in_basket(e, quotes, weights)
+    }
+
+    // Return cross price for pair of assets at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp
+    //
+    // # Returns
+    //
+    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_price(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamp: u64,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossPrice,
+            1,
+        );
+        PriceOracleContractBase::x_price(e, base_asset, quote_asset, timestamp)
+    }
+
+    // Return cross prices for pair of assets at multiple timestamps, invocation is charged per timestamp
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamps` - Timestamps (in seconds)
+    //
+    // # Returns
+    //
+    // Cross prices (base_asset_price/quote_asset_price) aligned with the input timestamps, None for missing records
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_prices_at(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamps: Vec<u64>,
+    ) -> Vec<Option<PriceData>> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        for _ in timestamps.iter() {
+            charge_invocation_fee(
+                e,
+                &caller,
+                &[&base_asset, &quote_asset],
+                InvocationComplexity::CrossPrice,
+                1,
+            );
+        }
+        PriceOracleContractBase::x_prices_at(e, base_asset, quote_asset, timestamps)
+    }
+
+    // Returns price of the asset denominated in another asset at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `denom` - Asset the price is expressed in
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price of the asset in denom terms (asset_price/denom_price) or None if any of the prices not found
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn price_in(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        denom: Asset,
+        timestamp: u64,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        panic_if_asset_missing(e, &denom);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&asset, &denom],
+            InvocationComplexity::CrossPrice,
+            1,
+        );
+        PriceOracleContractBase::price_in(e, asset, denom, timestamp)
+    }
+
+    // Return cross price for pair of assets at specific timestamp, looking up each asset price
+    // independently within the tolerance window
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp
+    // * `tolerance_periods` - Maximum number of periods to look back for each asset price
+    //
+    // # Returns
+    //
+    // Cross price (base_asset_price/quote_asset_price) timestamped with the older of two records or None if any of the prices not found within tolerance
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_price_tolerant(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamp: u64,
+        tolerance_periods: u32,
+    ) -> Option<PriceData> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossPrice,
+            1,
+        );
+        PriceOracleContractBase::x_price_tolerant(
+            e,
+            base_asset,
+            quote_asset,
+            timestamp,
+            tolerance_periods,
+        )
+    }
+
+    // Returns last N cross price records of for pair of assets
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to fetch
+    //
+    // # Returns
+    //
+    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_prices(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<Vec<PriceData>> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossPrice,
+            records,
+        );
+        PriceOracleContractBase::x_prices(e, base_asset, quote_asset, records)
+    }
+
+    // Returns time-weighted average price for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP for the given asset over N recent records or None if not found
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn twap(e: &Env, caller: Address, asset: Asset, records: u32) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Twap, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::twap(e, asset, records)
+    }
+
+    // Returns weighted composite of time-weighted average prices for given assets over N recent records,
+    // invocation is charged per constituent asset
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `assets` - Index constituents
+    // * `weights` - Weights of the constituents, normalized to their sum
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // Index price or None if any of the constituents is unavailable, weights don't match assets or any of them is not positive
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn index_twap(
+        e: &Env,
+        caller: Address,
+        assets: Vec<Asset>,
+        weights: Vec<i128>,
+        records: u32,
+    ) -> Option<i128> {
+        caller.require_auth();
+        for asset in assets.iter() {
+            panic_if_asset_missing(e, &asset);
+        }
+        for asset in assets.iter() {
+            charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Twap, 1);
+        }
+        PriceOracleContractBase::index_twap(e, assets, weights, records)
+    }
+
+    // Returns weighted median price for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    // * `weights` - Weights of the records, starting from the most recent one
+    //
+    // # Returns
+    //
+    // Weighted median for the given asset over N recent records or None if prices are stale or the total weight overflows
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported, weights length doesn't match the number of records, any of the weights is not positive,
+    // or the window is shorter than the minimum TWAP window
+    pub fn weighted_median(
+        e: &Env,
+        caller: Address,
+        asset: Asset,
+        records: u32,
+        weights: Vec<i128>,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Twap, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::weighted_median(e, asset, records, weights)
+    }
+
+    // Returns deviation of the most recent price from TWAP for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // Spot price deviation from TWAP (in basis points) or None if any of them is unavailable
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported or the window is shorter than the minimum TWAP window
+    pub fn spot_vs_twap_bps(e: &Env, caller: Address, asset: Asset, records: u32) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &asset);
+        let charged = charge_invocation_fee(e, &caller, &[&asset], InvocationComplexity::Twap, 1);
+        subsidize_asset_ttl(e, &asset, charged);
+        PriceOracleContractBase::spot_vs_twap_bps(e, asset, records)
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if not found
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_twap(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossTwap,
+            records,
+        );
+        PriceOracleContractBase::x_twap(e, base_asset, quote_asset, records)
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records,
+    // ensuring that both assets have fresh records over the entire window
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller that covers invocation cost
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if any of prices is stale
+    //
+    // # Panics
+    //
+    // Panics if any of the assets is not supported
+    pub fn x_twap_checked(
+        e: &Env,
+        caller: Address,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<i128> {
+        caller.require_auth();
+        panic_if_asset_missing(e, &base_asset);
+        panic_if_asset_missing(e, &quote_asset);
+        charge_invocation_fee(
+            e,
+            &caller,
+            &[&base_asset, &quote_asset],
+            InvocationComplexity::CrossTwap,
+            records,
+        );
+        PriceOracleContractBase::x_twap_checked(e, base_asset, quote_asset, records)
+    }
+
+    /* Admin section */
+
+    // Initializes contract configuration
+    // Requires admin authorization
+    // # Arguments
+    //
+    // * `config` - Configuration parameters
+    //
+    // # Panics
+    //
+    // Panics if not authorized, if contract is already initialized, assets list is empty, resolution is not a whole number of seconds, or retention period is too short
+    pub fn config(e: &Env, config: ConfigData) {
+        PriceOracleContractBase::config(e, config, 0);
+        update_default_costs(e);
+    }
+
+    // Initializes contract configuration together with invocation costs in a single call
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `config` - Configuration parameters
+    // * `costs` - Invocation costs for different invocation categories
+    //
+    // # Panics
+    //
+    // Panics if not authorized, if contract is already initialized, or costs config is invalid
+    pub fn bootstrap(e: &Env, config: ConfigData, costs: Vec<u64>) {
+        //validate costs before applying any settings
+        panic_if_invalid_costs_config(e, &costs);
+        PriceOracleContractBase::config(e, config, 0);
+        update_default_costs(e);
+        set_costs_config(e, &costs);
+    }
+
+    // Update contract cache size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `cache_size` - New cache size (number of rounds stored in cache)
+    //
+    // Growing the cache doesn't populate it with older records, use `warm_cache` for that
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_cache_size(e: &Env, cache_size: u32) {
+        PriceOracleContractBase::set_cache_size(e, cache_size);
+    }
+
+    // Sets policy applied when the price records cache exceeds its size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `policy` - Cache eviction policy, `Strided` keeps records aligned to the given number of rounds
+    //
+    // # Panics
+    //
+    // Panics if not authorized or stride is less than 2 rounds
+    pub fn set_cache_eviction_policy(e: &Env, policy: CacheEvictionPolicy) {
+        PriceOracleContractBase::set_cache_eviction_policy(e, policy);
+    }
+
+    // Rewrite legacy (protocol 1) price records in the current format, so they are served without the legacy read path
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the legacy records to migrate (in seconds)
+    //
+    // # Returns
+    //
+    // Number of migrated records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the number of timestamps exceeds the max batch size
+    pub fn migrate_v1_records(e: &Env, timestamps: Vec<u64>) -> u32 {
+        PriceOracleContractBase::migrate_v1_records(e, timestamps)
+    }
+
+    // Load price records for given timestamps from the temporary storage into the instance cache
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the records to cache (in seconds)
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn warm_cache(e: &Env, timestamps: Vec<u64>) {
+        PriceOracleContractBase::warm_cache(e, timestamps);
+    }
+
+    // Remove all cached price records, subsequent reads fall back to the temporary storage
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn clear_cache(e: &Env) {
+        PriceOracleContractBase::clear_cache(e);
+    }
+
+    // Toggle strict mode, when enabled `price`, `lastprice`, and `x_price` panic instead of returning None
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `strict` - Whether strict mode is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_strict_mode(e: &Env, strict: bool) {
+        PriceOracleContractBase::set_strict_mode(e, strict);
+    }
+
+    // Toggle TTL extension on reads, when enabled every read extends TTL of the price record
+    // up to the history retention period (reads become more expensive)
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `read_bump` - Whether TTL extension on reads is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_read_bump(e: &Env, read_bump: bool) {
+        PriceOracleContractBase::set_read_bump(e, read_bump);
+    }
+
+    // Toggle read strategy, when enabled history records are loaded from the temporary storage first,
+    // and the cache is consulted only for records missing there
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `storage_first` - Whether storage-first read strategy is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_storage_first(e: &Env, storage_first: bool) {
+        PriceOracleContractBase::set_storage_first(e, storage_first);
+    }
+
+    // Adds given assets to the contract quoted assets list
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `assets` - Assets to add
+    //
+    // # Panics
+    //
+    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
+    pub fn add_assets(e: &Env, assets: Vec<Asset>) {
+        PriceOracleContractBase::add_assets(e, assets, 0);
+    }
+
+    // Removes assets expired more than the grace period ago
+    // Removed asset slots are kept as tombstones, so positions of other assets don't change
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `grace_days` - Grace period after expiration (in days)
+    //
+    // # Returns
+    //
+    // Number of removed assets
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn purge_expired(e: &Env, grace_days: u32) -> u32 {
+        PriceOracleContractBase::purge_expired(e, grace_days)
+    }
+
+    // Compacts live assets to contiguous indexes, removing tombstones of purged assets
+    // Asset indexes, expiration records, per-asset settings, history mask and recent price records are rewritten together
+    // Requires admin authorization
+    //
+    // # Returns
+    //
+    // Number of removed asset slots
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn reindex_assets(e: &Env) -> u32 {
+        PriceOracleContractBase::reindex_assets(e)
+    }
+
+    // Sets human-readable label for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `label` - Asset label
+    //
+    // # Panics
+    //
+    // Panics if not authorized or asset is not supported
+    pub fn set_asset_label(e: &Env, asset: Asset, label: Symbol) {
+        PriceOracleContractBase::set_asset_label(e, asset, label);
+    }
+
+    // Sets human-readable labels for multiple assets at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `entries` - Quoted assets with their labels
+    //
+    // # Panics
+    //
+    // Panics if not authorized or any of the assets is not supported
+    pub fn set_asset_labels(e: &Env, entries: Vec<(Asset, Symbol)>) {
+        PriceOracleContractBase::set_asset_labels(e, entries);
+    }
+
+    // Sets price feed resolution override for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `resolution` - Asset resolution (in seconds), multiple of the global resolution or zero to reset
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or resolution is not aligned with the global resolution
+    pub fn set_asset_resolution(e: &Env, asset: Asset, resolution: u32) {
+        PriceOracleContractBase::set_asset_resolution(e, asset, resolution);
+    }
+
+    // Sets minimum price of the asset accepted as a cross-price denominator
+    // Cross prices quoted in the asset with a price below the floor are not returned
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `floor` - Minimum denominator price, zero to remove the floor
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or floor is negative
+    pub fn set_asset_price_floor(e: &Env, asset: Asset, floor: i128) {
+        PriceOracleContractBase::set_asset_price_floor(e, asset, floor);
+    }
+
+    // Registers an alias for the canonical asset, preventing both forms from being listed at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `alias` - Alternative form of the asset
+    // * `canonical` - Canonical form of the asset, passing the alias itself removes the mapping
+    //
+    // # Panics
+    //
+    // Panics if not authorized, canonical asset is an alias itself, or alias is a canonical asset of another alias
+    pub fn set_asset_alias(e: &Env, alias: Asset, canonical: Asset) {
+        PriceOracleContractBase::set_asset_alias(e, alias, canonical);
+    }
+
+    // Sets sanity bounds for the asset prices, updates outside of bounds are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `min` - Minimum allowed price
+    // * `max` - Maximum allowed price
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported, or bounds are invalid
+    pub fn set_price_bounds(e: &Env, asset: Asset, min: i128, max: i128) {
+        PriceOracleContractBase::set_price_bounds(e, asset, min, max);
+    }
+
+    // Adds feeder account allowed to submit price updates
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn add_feeder(e: &Env, feeder: Address) {
+        PriceOracleContractBase::add_feeder(e, feeder);
+    }
+
+    // Removes feeder account from the allowed feeders
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn remove_feeder(e: &Env, feeder: Address) {
+        PriceOracleContractBase::remove_feeder(e, feeder);
+    }
+
+    // Adds feeder public key allowed to sign price attestations on behalf of the feeder
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Allowed feeder account the key belongs to
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the feeder is not allowed
+    pub fn add_feeder_key(e: &Env, feeder: Address, public_key: BytesN<32>) {
+        PriceOracleContractBase::add_feeder_key(e, feeder, public_key);
+    }
+
+    // Removes feeder public key from the allowed attestation signers
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn remove_feeder_key(e: &Env, public_key: BytesN<32>) {
+        PriceOracleContractBase::remove_feeder_key(e, public_key);
+    }
+
+    // Pauses price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn pause(e: &Env) {
+        PriceOracleContractBase::pause(e);
+    }
+
+    // Resumes price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn unpause(e: &Env) {
+        PriceOracleContractBase::unpause(e);
+    }
+
+    // Sets price deviation limits applied to new price updates
+    // Updates deviating from the last prices more than the reject threshold are rejected,
+    // while updates exceeding the severe threshold pause the contract
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `reject_bps` - Reject threshold (in basis points)
+    // * `pause_bps` - Auto-pause threshold (in basis points), greater than the reject threshold
+    //
+    // # Panics
+    //
+    // Panics if not authorized or thresholds are invalid
+    pub fn set_deviation_limits(e: &Env, reject_bps: u32, pause_bps: u32) {
+        PriceOracleContractBase::set_deviation_limits(e, reject_bps, pause_bps);
+    }
+
+    // Sets minimum number of updates within recent periods required for the asset before
+    // `lastprice` and `twap` return its prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `min_updates` - Required number of updates, zero along with zero periods removes the guard
+    // * `periods` - Number of recent periods to check (up to 256)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or coverage parameters are invalid
+    pub fn set_min_coverage(e: &Env, min_updates: u32, periods: u32) {
+        PriceOracleContractBase::set_min_coverage(e, min_updates, periods);
+    }
+
+    // Sets minimum number of records TWAP requests should span, shorter windows are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `min_records` - Minimum TWAP window, zero disables the check
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_min_twap_records(e: &Env, min_records: u32) {
+        PriceOracleContractBase::set_min_twap_records(e, min_records);
+    }
+
+    // Sets maximum number of price snapshots accepted in a single batch
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_batch_size` - Maximum batch size
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max batch size is zero
+    pub fn set_max_batch_size(e: &Env, max_batch_size: u32) {
+        PriceOracleContractBase::set_max_batch_size(e, max_batch_size);
+    }
+
+    // Sets maximum number of price records returned by a single query
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_records` - Maximum number of records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max records is zero
+    pub fn set_max_records(e: &Env, max_records: u32) {
+        PriceOracleContractBase::set_max_records(e, max_records);
+    }
+
+    // Sets placeholder price served by `lastprice` for newly added assets until their first price update,
+    // placeholder records are flagged with zero timestamp
+    // Only assets added while placeholder prices are enabled are affected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `placeholder_price` - Placeholder price (0 disables placeholder prices)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or placeholder price is negative
+    pub fn set_placeholder_price(e: &Env, placeholder_price: i128) {
+        PriceOracleContractBase::set_placeholder_price(e, placeholder_price);
+    }
+
+    // Sets maximum allowed price value, updates containing higher prices are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_price` - Maximum price (0 removes the limit)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max price is negative
+    pub fn set_max_price(e: &Env, max_price: i128) {
+        PriceOracleContractBase::set_max_price(e, max_price);
+    }
+
+    // Sets history retention period for the prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `period` - History retention period (in milliseconds), zero disables the retention
+    //
+    // # Panics
+    //
+    // Panics if not authorized or if the period is too short to extend records TTL beyond the minimum
+    pub fn set_history_retention_period(e: &Env, period: u64) {
+        PriceOracleContractBase::set_history_retention_period(e, period);
+    }
+
+    // Set fee token address and daily price feed retainer fee amount
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    //
+    // # Panics
+    //
+    // Panics if not authorized or not initialized yet
+    pub fn set_fee_config(e: &Env, config: FeeConfig) {
+        PriceOracleContractBase::set_fee_config(e, config, 0);
+        update_default_costs(e);
+    }
+
+    // Update costs configuration per each invocation category
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `config` - Invocation costs for different invocation categories
     //
     // # Panics
     //
@@ -405,6 +2223,203 @@ impl BeamOracleContract {
         set_costs_config(e, &config);
     }
 
+    // Update fee config, invocation costs, and history retention period in a single call
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    // * `costs` - Invocation costs for different invocation categories
+    // * `retention_period` - History retention period (in milliseconds), zero disables the retention
+    //
+    // # Panics
+    //
+    // Panics if not authorized or any of the settings is invalid, in which case none of them is applied
+    pub fn set_economics(e: &Env, fee_config: FeeConfig, costs: Vec<u64>, retention_period: u64) {
+        auth::panic_if_not_admin(e);
+        //validate all settings before applying any of them
+        panic_if_invalid_costs_config(e, &costs);
+        if let FeeConfig::Some((_, fee)) = &fee_config {
+            if *fee <= 0 {
+                panic_with_error!(e, Error::InvalidAmount);
+            }
+        }
+        if !prices::is_valid_retention_period(retention_period) {
+            panic_with_error!(e, Error::RetentionPeriodTooShort);
+        }
+        settings::set_fee_config(e, &fee_config);
+        assets::init_expiration_config(e, 0);
+        update_default_costs(e);
+        set_costs_config(e, &costs);
+        settings::set_history_retention_period(e, retention_period);
+    }
+
+    // Update costs configuration using named invocation categories
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `price` - Single asset price record request cost
+    // * `twap` - TWAP approximation cost
+    // * `cross_price` - Cross-price calculation cost
+    // * `cross_twap` - Cross-price TWAP approximation cost
+    // * `n_modifier` - Surcharge for each additional requested period
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_invocation_costs_named(
+        e: &Env,
+        price: u64,
+        twap: u64,
+        cross_price: u64,
+        cross_twap: u64,
+        n_modifier: u64,
+    ) {
+        auth::panic_if_not_admin(e);
+        //costs vector indexes correspond to InvocationComplexity
+        let costs = Vec::from_array(e, [n_modifier, price, twap, cross_price, cross_twap]);
+        set_costs_config(e, &costs);
+    }
+
+    // Set precision of the per-period surcharge (NModifier) in invocation costs
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `scale` - Surcharge scale
+    //
+    // # Panics
+    //
+    // Panics if not authorized or scale is zero
+    pub fn set_cost_scale(e: &Env, scale: u64) {
+        auth::panic_if_not_admin(e);
+        cost::set_cost_scale(e, scale);
+    }
+
+    // Set share of the paid single-asset read fee allocated to extending the quoted asset TTL
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `share` - Fee share in basis points (0 disables the subsidy)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or share exceeds 10000
+    pub fn set_ttl_subsidy(e: &Env, share: u32) {
+        auth::panic_if_not_admin(e);
+        set_ttl_subsidy(e, share);
+    }
+
+    // Toggle audit mode, when enabled every paid read publishes an event attributing the query
+    // to the caller (reads become more expensive)
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `audit_mode` - Whether read events are published
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_audit_mode(e: &Env, audit_mode: bool) {
+        auth::panic_if_not_admin(e);
+        set_audit_mode(e, audit_mode);
+    }
+
+    // Set invocation fee discount granted to the caller on paid reads
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `caller` - Caller address
+    // * `discount` - Discount in basis points (0 removes the discount)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or discount exceeds 10000
+    pub fn set_caller_discount(e: &Env, caller: Address, discount: u32) {
+        auth::panic_if_not_admin(e);
+        set_caller_discount(e, &caller, discount);
+    }
+
+    // Set invocation fee multiplier applied to paid reads of the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `multiplier` - Fee multiplier in basis points (10000 resets to the base cost)
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or multiplier is zero
+    pub fn set_asset_fee_multiplier(e: &Env, asset: Asset, multiplier: u32) {
+        auth::panic_if_not_admin(e);
+        assets::set_asset_fee_multiplier(e, asset, multiplier);
+    }
+
+    // Schedule fee token address and daily price feed retainer fee amount change
+    // The change takes effect only after it is applied once the timelock passes
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    // * `apply_at` - Timestamp (in seconds) after which the change can be applied
+    //
+    // # Panics
+    //
+    // Panics if not authorized or apply timestamp is not in the future
+    pub fn schedule_fee_config(e: &Env, fee_config: FeeConfig, apply_at: u64) {
+        PriceOracleContractBase::schedule_fee_config(e, fee_config, apply_at);
+    }
+
+    // Apply scheduled fee config change if its timelock has passed
+    //
+    // # Returns
+    //
+    // True if the scheduled change has been applied
+    pub fn apply_pending_fee_config(e: &Env) -> bool {
+        let applied = PriceOracleContractBase::apply_pending_fee_config(e, 0);
+        if applied {
+            update_default_costs(e);
+        }
+        applied
+    }
+
+    // Switch fee token and daily price feed retainer fee amount, keeping existing expirations intact
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `new_token` - New fee token address
+    // * `new_rate` - Daily price feed retainer fee amount in new tokens
+    //
+    // # Panics
+    //
+    // Panics if not authorized or rate is not positive
+    pub fn migrate_fee_token(e: &Env, new_token: Address, new_rate: i128) {
+        PriceOracleContractBase::migrate_fee_token(e, new_token, new_rate);
+    }
+
+    // Sets fee split between burn and treasury transfer applied to all charged fees
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `burn_bps` - Share of the fee to burn (in basis points)
+    // * `treasury_bps` - Share of the fee to transfer to the treasury (in basis points)
+    // * `treasury` - Treasury address
+    //
+    // # Panics
+    //
+    // Panics if not authorized or shares don't sum up to 10000
+    pub fn set_fee_split(e: &Env, burn_bps: u32, treasury_bps: u32, treasury: Address) {
+        PriceOracleContractBase::set_fee_split(e, burn_bps, treasury_bps, treasury);
+    }
+
     // Record new price feed history snapshot
     // Requires admin authorization
     //
@@ -417,7 +2432,114 @@ impl BeamOracleContract {
     //
     // Panics if not authorized or price snapshot record is invalid
     pub fn set_price(e: &Env, updates: PriceUpdate, timestamp: u64) {
-        PriceOracleContractBase::set_price(e, updates, timestamp);
+        PriceOracleContractBase::set_price(e, updates, timestamp, false);
+    }
+
+    // Record new price feed history snapshot on behalf of an allowed feeder
+    // Requires feeder authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    //
+    // # Panics
+    //
+    // Panics if not authorized, feeder is not allowed, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_by_feeder(e: &Env, feeder: Address, updates: PriceUpdate, timestamp: u64) {
+        PriceOracleContractBase::set_price_by_feeder(e, feeder, updates, timestamp);
+    }
+
+    // Record new price feed history snapshot signed off-chain by an allowed feeder key
+    // Can be submitted by any account (e.g. a relayer), the activity is recorded for the linked feeder
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `signature` - Ed25519 signature of the XDR-encoded (contract address, updates, timestamp) tuple
+    // * `signer` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if signer is not allowed, signature is invalid, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_attested(
+        e: &Env,
+        updates: PriceUpdate,
+        timestamp: u64,
+        signature: BytesN<64>,
+        signer: BytesN<32>,
+    ) {
+        PriceOracleContractBase::set_price_attested(e, updates, timestamp, signature, signer);
+    }
+
+    // Record new price feed history snapshot only if the last update timestamp matches the expected one
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `expected_last_timestamp` - Expected timestamp of the most recent update (in seconds), as returned by `last_timestamp`
+    //
+    // # Panics
+    //
+    // Panics if not authorized, last update timestamp doesn't match the expected value, or price snapshot record is invalid
+    pub fn set_price_cas(
+        e: &Env,
+        updates: PriceUpdate,
+        timestamp: u64,
+        expected_last_timestamp: u64,
+    ) {
+        PriceOracleContractBase::set_price_cas(e, updates, timestamp, expected_last_timestamp);
+    }
+
+    // Overwrites prices of multiple assets in the existing price record
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamp` - Timestamp of the record to correct (in seconds)
+    // * `corrections` - Assets with corrected prices
+    //
+    // # Panics
+    //
+    // Panics if not authorized, record not found, any of the assets is not supported or has no price in the record,
+    // or corrected price is invalid
+    pub fn correct_prices(e: &Env, timestamp: u64, corrections: Vec<(Asset, i128)>) {
+        PriceOracleContractBase::correct_prices(e, timestamp, corrections);
+    }
+
+    // Record multiple price feed history snapshots in a single invocation
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshots with corresponding history snapshot timestamps
+    //
+    // # Panics
+    //
+    // Panics if not authorized, batch size exceeds the limit, or any price snapshot record is invalid
+    pub fn set_prices_batch(e: &Env, updates: Vec<(PriceUpdate, u64)>) {
+        PriceOracleContractBase::set_prices_batch(e, updates);
+    }
+
+    // Record price feed history snapshot older than the most recent update
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    //
+    // # Panics
+    //
+    // Panics if not authorized, price snapshot record is invalid, or timestamp is out of history range
+    pub fn backfill_price(e: &Env, updates: PriceUpdate, timestamp: u64) {
+        PriceOracleContractBase::set_price(e, updates, timestamp, true);
     }
 
     // Update contract source code
@@ -3,10 +3,12 @@ extern crate std;
 
 use crate::cost::InvocationComplexity;
 use crate::{BeamOracleContract, BeamOracleContractClient};
-use oracle::types::{Asset, ConfigData, FeeConfig};
-use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+use oracle::types::{Asset, ConfigData, Error, FeeConfig, PriceData, PriceStatus, PriceUpdate};
+use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
 use soroban_sdk::token::{StellarAssetClient, TokenClient};
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, Address, Bytes, Env, IntoVal, Map, String, Symbol, TryIntoVal, Val, Vec,
+};
 use test_case::test_case;
 
 pub fn init_contract_with_admin<'a>() -> (Env, BeamOracleContractClient<'a>, ConfigData) {
@@ -47,7 +49,7 @@ fn prepare_contract_config(env: &Env) -> ConfigData {
         admin: admin.clone(),
         history_retention_period: (100 * resolution).into(),
         assets,
-        base_asset: Asset::Stellar(Address::generate(&env)),
+        base_asset: Asset::Stellar(Address::generate(env)),
         decimals: 14,
         resolution,
         cache_size: 0,
@@ -55,6 +57,23 @@ fn prepare_contract_config(env: &Env) -> ConfigData {
     }
 }
 
+fn generate_updates(env: &Env, prices: &[i128]) -> PriceUpdate {
+    let mut mask = [0u8; 32];
+    let mut updates = Vec::new(env);
+    for (asset_index, price) in prices.iter().enumerate() {
+        if *price > 0 {
+            let (byte, bitmask) =
+                oracle::mapping::resolve_period_update_mask_position(asset_index as u32);
+            mask[byte as usize] |= bitmask;
+            updates.push_back(*price);
+        }
+    }
+    PriceUpdate {
+        prices: updates,
+        mask: Bytes::from_array(env, &mask),
+    }
+}
+
 #[test]
 fn set_invocation_config_test() {
     let (env, client, _) = init_contract_with_admin();
@@ -66,6 +85,115 @@ fn set_invocation_config_test() {
     assert_eq!(result, costs);
 }
 
+#[test]
+fn invocation_catalog_test() {
+    let (env, client, _) = init_contract_with_admin();
+
+    let costs = Vec::from_array(&env, [10, 20, 30, 40, 50]);
+    client.set_invocation_costs_config(&costs);
+
+    let catalog = client.invocation_catalog();
+    assert_eq!(catalog.len(), costs.len());
+    for (index, cost) in costs.iter().enumerate() {
+        assert_eq!(catalog.get_unchecked(index as u32), (index as u32, cost));
+    }
+    assert_eq!(
+        catalog.get_unchecked(InvocationComplexity::CrossTwap as u32),
+        (InvocationComplexity::CrossTwap as u32, 50)
+    );
+}
+
+#[contract]
+struct SixDecimalsToken;
+
+#[contractimpl]
+impl SixDecimalsToken {
+    pub fn decimals(_e: Env) -> u32 {
+        6
+    }
+}
+
+#[contract]
+struct HighDecimalsToken;
+
+#[contractimpl]
+impl HighDecimalsToken {
+    pub fn decimals(_e: Env) -> u32 {
+        20
+    }
+}
+
+#[test]
+fn default_costs_fee_token_decimals_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    //7-decimal fee token uses unscaled defaults
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset, 1_000_000)));
+    assert_eq!(
+        client.invocation_costs(),
+        Vec::from_array(
+            &env,
+            [2_000_000, 10_000_000, 15_000_000, 20_000_000, 30_000_000]
+        )
+    );
+
+    let fee_token = env.register(SixDecimalsToken, ());
+    client.set_fee_config(&FeeConfig::Some((fee_token, 100_000)));
+    assert_eq!(
+        client.invocation_costs(),
+        Vec::from_array(&env, [200_000, 1_000_000, 1_500_000, 2_000_000, 3_000_000])
+    );
+    assert_eq!(
+        client.estimate_cost(&InvocationComplexity::Price, &1),
+        1_000_000
+    );
+
+    //fee token precision that can't express the default costs is rejected
+    let fee_token = env.register(HighDecimalsToken, ());
+    assert_eq!(
+        client.try_set_fee_config(&FeeConfig::Some((fee_token, 100_000))),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.invocation_costs(),
+        Vec::from_array(&env, [200_000, 1_000_000, 1_500_000, 2_000_000, 3_000_000])
+    );
+
+    //explicitly configured costs are not scaled
+    let costs = Vec::from_array(&env, [10, 20, 30, 40, 50]);
+    client.set_invocation_costs_config(&costs);
+    assert_eq!(client.invocation_costs(), costs);
+}
+
+#[test]
+fn set_invocation_costs_named_test() {
+    let (env, client, _) = init_contract_with_admin();
+
+    client.set_invocation_costs_named(&10, &20, &30, &40, &5);
+    let costs = client.invocation_costs();
+
+    //same config as correctly ordered raw vector
+    client.set_invocation_costs_config(&Vec::from_array(&env, [5, 10, 20, 30, 40]));
+    assert_eq!(client.invocation_costs(), costs);
+    assert_eq!(costs.get_unchecked(InvocationComplexity::Price as u32), 10);
+    assert_eq!(costs.get_unchecked(InvocationComplexity::Twap as u32), 20);
+    assert_eq!(
+        costs.get_unchecked(InvocationComplexity::CrossPrice as u32),
+        30
+    );
+    assert_eq!(
+        costs.get_unchecked(InvocationComplexity::CrossTwap as u32),
+        40
+    );
+    assert_eq!(
+        costs.get_unchecked(InvocationComplexity::NModifier as u32),
+        5
+    );
+}
+
 #[test]
 fn invocation_charge_test() {
     let (env, client, init_data) = init_contract_with_admin();
@@ -85,13 +213,14 @@ fn invocation_charge_test() {
     //get cross price
     client.x_twap(
         &caller,
-        &init_data.base_asset,
+        &init_data.assets.get_unchecked(1),
         &init_data.assets.first_unchecked(),
         &5,
     );
     //check that fee token was deducted
     let fee_token_balance = TokenClient::new(&env, &fee_asset).balance(&caller);
     assert_eq!(fee_token_balance, 36_000_000);
+    assert_eq!(client.total_fees_burned(), 64_000_000);
 }
 
 #[test_case(InvocationComplexity::Price, 1, 10_000_000 ; "price")]
@@ -123,3 +252,983 @@ fn invocation_charge_estimate_test(
     let fee = client.estimate_cost(&invocation, &periods);
     assert_eq!(fee, expected_fee);
 }
+
+#[test]
+fn invocation_charge_fine_scale_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset, 1_000_000)));
+    assert_eq!(client.cost_scale(), 10_000_000);
+    //basis point granularity, 0.25% surcharge per each additional period
+    client.set_cost_scale(&10_000);
+    let costs = Vec::from_array(&env, [25, 10_000_000, 15_000_000, 20_000_000, 30_000_000]);
+    client.set_invocation_costs_config(&costs);
+
+    assert_eq!(
+        client.estimate_cost(&InvocationComplexity::Price, &1),
+        10_000_000
+    );
+    assert_eq!(
+        client.estimate_cost(&InvocationComplexity::Price, &2),
+        10_025_000
+    );
+    assert_eq!(
+        client.estimate_cost(&InvocationComplexity::Twap, &5),
+        15_150_000
+    );
+    assert_eq!(
+        client.try_set_cost_scale(&0),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn base_in_basket_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &200_000_000);
+
+    let decimals = 10i128.pow(init_data.decimals);
+    client.set_price(
+        &generate_updates(&env, &[decimals / 2, decimals / 4]),
+        &600_000,
+    );
+    let assets = init_data.assets;
+    //base is worth 2 units of the first quote and 4 units of the second one
+    let quotes = Vec::from_array(&env, [assets.get_unchecked(0), assets.get_unchecked(1)]);
+    let weights = Vec::from_array(&env, [3, 1]);
+    //(2 * 3 + 4 * 1) / 4 = 2.5
+    assert_eq!(
+        client.base_in_basket(&caller, &quotes, &weights),
+        Some(25 * decimals / 10)
+    );
+    //charged per quote asset
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 200_000_000 - 2 * 20_000_000);
+
+    //weights mismatch
+    let weights = Vec::from_array(&env, [1]);
+    assert_eq!(client.base_in_basket(&caller, &quotes, &weights), None);
+
+    //constituent without prices
+    let quotes = Vec::from_array(&env, [assets.get_unchecked(0), assets.get_unchecked(4)]);
+    let weights = Vec::from_array(&env, [1, 1]);
+    assert_eq!(client.base_in_basket(&caller, &quotes, &weights), None);
+}
+
+#[test]
+fn x_prices_at_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+
+    let decimals = 10i128.pow(init_data.decimals);
+    client.set_price(
+        &generate_updates(&env, &[10 * decimals, 5 * decimals]),
+        &300_000,
+    );
+    client.set_price(
+        &generate_updates(&env, &[12 * decimals, 4 * decimals]),
+        &600_000,
+    );
+    let assets = init_data.assets;
+    let base = assets.get_unchecked(0);
+    let quote = assets.get_unchecked(1);
+    let timestamps = Vec::from_array(&env, [300, 450, 600, 900]);
+    let cross = |price: i128, timestamp: u64| Some(PriceData { price, timestamp });
+    assert_eq!(
+        client.x_prices_at(&caller, &base, &quote, &timestamps),
+        Vec::from_array(
+            &env,
+            [
+                cross(2 * decimals, 300),
+                cross(2 * decimals, 300),
+                cross(3 * decimals, 600),
+                None
+            ]
+        )
+    );
+    //charged per timestamp
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 100_000_000 - 4 * 20_000_000);
+}
+
+#[test]
+fn basket_cross_spread_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+
+    let decimals = 10i128.pow(init_data.decimals);
+    client.set_price(
+        &generate_updates(
+            &env,
+            &[100 * decimals, 50 * decimals, 40 * decimals, 20 * decimals],
+        ),
+        &600_000,
+    );
+    let assets = init_data.assets;
+    let base = assets.get_unchecked(0);
+    //cross rates 2, 2.5 and 5
+    let quotes = Vec::from_array(
+        &env,
+        [
+            assets.get_unchecked(1),
+            assets.get_unchecked(2),
+            assets.get_unchecked(3),
+        ],
+    );
+    assert_eq!(
+        client.basket_cross_spread(&caller, &base, &quotes),
+        Some(15_000)
+    );
+    //charged per quote asset
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 100_000_000 - 3 * 20_000_000);
+
+    //quote without prices is skipped, single valid cross price is not enough
+    let quotes = Vec::from_array(&env, [assets.get_unchecked(1), assets.get_unchecked(4)]);
+    assert_eq!(client.basket_cross_spread(&caller, &base, &quotes), None);
+}
+
+#[test]
+fn price_within_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let price = 100 * 10i128.pow(init_data.decimals);
+    client.set_price(&generate_updates(&env, &[price]), &600_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    //1% off the reference price with 1% allowed deviation
+    let reference = 101 * 10i128.pow(init_data.decimals);
+    let result = client.price_within(&caller, &asset, &reference, &100);
+    assert_eq!(result.unwrap().price, price);
+    //exact match
+    let result = client.price_within(&caller, &asset, &price, &0);
+    assert_eq!(result.unwrap().price, price);
+
+    //placeholder price is not checked against the reference
+    client.set_placeholder_price(&price);
+    let new_asset = Asset::Stellar(Address::generate(&env));
+    client.add_assets(&Vec::from_array(&env, [new_asset.clone()]));
+    assert_eq!(client.lastprice(&caller, &new_asset).unwrap().timestamp, 0);
+    assert_eq!(client.price_within(&caller, &new_asset, &price, &0), None);
+}
+
+#[test]
+fn price_impact_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let price = 100 * 10i128.pow(init_data.decimals);
+    client.set_price(&generate_updates(&env, &[price]), &600_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let depth = 1_000_000;
+    assert_eq!(
+        client.price_impact(&caller, &asset, &0, &depth),
+        Some(price)
+    );
+    //impact grows with the trade size
+    let mut last = price;
+    for notional in [10_000, 100_000, 500_000, 1_000_000] {
+        let impact = client
+            .price_impact(&caller, &asset, &notional, &depth)
+            .unwrap();
+        assert!(impact > last);
+        last = impact;
+    }
+    assert_eq!(last, 2 * price);
+    //sells push the price down
+    assert_eq!(
+        client.price_impact(&caller, &asset, &-100_000, &depth),
+        Some(90 * 10i128.pow(init_data.decimals))
+    );
+    assert_eq!(
+        client.try_price_impact(&caller, &asset, &100_000, &0),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+
+    //no impact estimate for assets served with the placeholder price
+    client.set_placeholder_price(&price);
+    let new_asset = Asset::Stellar(Address::generate(&env));
+    client.add_assets(&Vec::from_array(&env, [new_asset.clone()]));
+    assert_eq!(client.lastprice(&caller, &new_asset).unwrap().timestamp, 0);
+    assert_eq!(client.price_impact(&caller, &new_asset, &0, &depth), None);
+}
+
+#[test]
+fn price_with_age_periods_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    client.set_price(&generate_updates(&env, &[100]), &600_000);
+    client.set_price(&generate_updates(&env, &[200]), &900_000);
+    client.set_price(&generate_updates(&env, &[300]), &1_500_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let (price, period) = client
+        .price_with_age_periods(&caller, &asset, &600)
+        .unwrap();
+    assert_eq!(Some(price), client.price(&caller, &asset, &600));
+    assert_eq!(period, 3);
+    let (price, period) = client
+        .price_with_age_periods(&caller, &asset, &1_500)
+        .unwrap();
+    assert_eq!(price.price, 300);
+    assert_eq!(period, 0);
+    //gap in updates
+    assert!(client
+        .price_with_age_periods(&caller, &asset, &1_200)
+        .is_none());
+}
+
+#[test]
+fn price_at_ledger_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        sequence_number: 1_000,
+        ..ledger_info
+    });
+    client.set_price(&generate_updates(&env, &[100]), &600_000);
+    client.set_price(&generate_updates(&env, &[200]), &900_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    //60 ledgers ago is 300 seconds before the current ledger
+    let result = client.price_at_ledger(&caller, &asset, &940);
+    assert_eq!(result, client.price(&caller, &asset, &900));
+    assert_eq!(result.unwrap().price, 200);
+    //timestamp is trimmed to the resolution
+    let result = client.price_at_ledger(&caller, &asset, &900);
+    assert_eq!(result, client.price(&caller, &asset, &600));
+    assert_eq!(result.unwrap().price, 100);
+    //future ledger
+    assert!(client.price_at_ledger(&caller, &asset, &1_001).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn price_within_deviation_exceeded_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let price = 100 * 10i128.pow(init_data.decimals);
+    client.set_price(&generate_updates(&env, &[price]), &600_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let reference = 110 * 10i128.pow(init_data.decimals);
+    client.price_within(&caller, &asset, &reference, &500);
+}
+
+#[test]
+fn ttl_subsidy_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    client.set_price(&generate_updates(&env, &[100]), &600_000);
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+
+    //no fee config - nothing to allocate
+    client.set_ttl_subsidy(&5_000);
+    client.lastprice(&caller, &asset);
+    assert_eq!(client.expires(&asset), None);
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    assert_eq!(client.ttl_subsidy(), 5_000);
+
+    //each read costs 10_000_000, half of it buys 5 days
+    let now = env.ledger().timestamp() * 1000;
+    let day = 86_400_000;
+    client.lastprice(&caller, &asset);
+    assert_eq!(client.expires(&asset).unwrap(), now + 5 * day);
+    client.lastprice(&caller, &asset);
+    assert_eq!(client.expires(&asset).unwrap(), now + 10 * day);
+    //other assets are not affected
+    assert_eq!(client.expires(&init_data.assets.get_unchecked(1)), None);
+}
+
+#[test]
+fn bootstrap_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(BeamOracleContract, ());
+    let client = BeamOracleContractClient::new(&env, &contract_id);
+    env.ledger().set(LedgerInfo {
+        timestamp: 900,
+        ..env.ledger().get()
+    });
+
+    let mut config = prepare_contract_config(&env);
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(config.admin.clone())
+        .address();
+    config.fee_config = FeeConfig::Some((fee_asset.clone(), 1_000_000));
+    let costs = Vec::from_array(
+        &env,
+        [1_000_000, 5_000_000, 6_000_000, 7_000_000, 8_000_000],
+    );
+    client.bootstrap(&config, &costs);
+
+    assert_eq!(client.admin().unwrap(), config.admin);
+    assert_eq!(client.assets(), config.assets);
+    assert_eq!(client.fee_config(), config.fee_config);
+    assert_eq!(client.invocation_costs(), costs);
+
+    //paid read is charged right away
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &10_000_000);
+    client.lastprice(&caller, &config.assets.first_unchecked());
+    assert_eq!(
+        TokenClient::new(&env, &fee_asset).balance(&caller),
+        5_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn bootstrap_invalid_costs_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(BeamOracleContract, ());
+    let client = BeamOracleContractClient::new(&env, &contract_id);
+
+    let config = prepare_contract_config(&env);
+    client.bootstrap(&config, &Vec::from_array(&env, [1, 2, 3]));
+}
+
+#[test]
+fn spot_vs_twap_bps_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    assert!(client.spot_vs_twap_bps(&caller, &asset, &3).is_none());
+
+    client.set_price(&generate_updates(&env, &[100]), &300_000);
+    client.set_price(&generate_updates(&env, &[100]), &600_000);
+    //not enough records yet
+    assert!(client.spot_vs_twap_bps(&caller, &asset, &3).is_none());
+    //price spike
+    client.set_price(&generate_updates(&env, &[130]), &900_000);
+    //twap = 110, spot = 130
+    assert_eq!(client.spot_vs_twap_bps(&caller, &asset, &3), Some(1818));
+    assert_eq!(client.spot_vs_twap_bps(&caller, &asset, &1), Some(0));
+
+    //windows shorter than the minimum are rejected
+    client.set_min_twap_records(&3);
+    assert_eq!(
+        client.try_spot_vs_twap_bps(&caller, &asset, &1),
+        Err(Ok(Error::TwapWindowTooShort.into()))
+    );
+    let weights = Vec::from_array(&env, [1, 1]);
+    assert_eq!(
+        client.try_weighted_median(&caller, &asset, &2, &weights),
+        Err(Ok(Error::TwapWindowTooShort.into()))
+    );
+}
+
+#[test]
+fn estimate_cost_formatted_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    assert_eq!(
+        client.estimate_cost_formatted(&InvocationComplexity::Price, &1),
+        (0, 0)
+    );
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+
+    let (cost, decimals) = client.estimate_cost_formatted(&InvocationComplexity::Twap, &5);
+    assert_eq!(cost, client.estimate_cost(&InvocationComplexity::Twap, &5));
+    assert_eq!(decimals, TokenClient::new(&env, &fee_asset).decimals());
+}
+
+#[test]
+fn x_price_tolerant_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    //base and quote assets are updated in turns
+    client.set_price(&generate_updates(&env, &[400, 0]), &300_000);
+    client.set_price(&generate_updates(&env, &[0, 200]), &600_000);
+    client.set_price(&generate_updates(&env, &[600, 0]), &900_000);
+
+    let caller = Address::generate(&env);
+    let base = init_data.assets.get_unchecked(0);
+    let quote = init_data.assets.get_unchecked(1);
+    let price = client
+        .x_price_tolerant(&caller, &base, &quote, &900, &1)
+        .unwrap();
+    assert_eq!(price.price, 3 * 10i128.pow(init_data.decimals));
+    assert_eq!(price.timestamp, 600);
+    let price = client
+        .x_price_tolerant(&caller, &base, &quote, &600, &1)
+        .unwrap();
+    assert_eq!(price.price, 2 * 10i128.pow(init_data.decimals));
+    assert_eq!(price.timestamp, 300);
+    //no tolerance behaves like x_price
+    assert!(client
+        .x_price_tolerant(&caller, &base, &quote, &900, &0)
+        .is_none());
+    assert!(client.x_price(&caller, &base, &quote, &900).is_none());
+    //quote asset has no records before 600
+    assert!(client
+        .x_price_tolerant(&caller, &base, &quote, &300, &5)
+        .is_none());
+}
+
+#[test]
+fn weighted_median_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    client.set_price(&generate_updates(&env, &[100]), &300_000);
+    client.set_price(&generate_updates(&env, &[200]), &600_000);
+    client.set_price(&generate_updates(&env, &[300]), &900_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let equal = Vec::from_array(&env, [1, 1, 1]);
+    assert_eq!(
+        client.weighted_median(&caller, &asset, &3, &equal),
+        Some(200)
+    );
+    //the oldest record dominates
+    let weights = Vec::from_array(&env, [1, 1, 10]);
+    assert_eq!(
+        client.weighted_median(&caller, &asset, &3, &weights),
+        Some(100)
+    );
+    //the most recent record dominates
+    let weights = Vec::from_array(&env, [5, 1, 1]);
+    assert_eq!(
+        client.weighted_median(&caller, &asset, &3, &weights),
+        Some(300)
+    );
+    //not enough records
+    let weights = Vec::from_array(&env, [1, 1, 1, 1]);
+    assert_eq!(client.weighted_median(&caller, &asset, &4, &weights), None);
+    //large weights don't overflow the half-total comparison
+    let weights = Vec::from_array(&env, [i128::MAX / 2, i128::MAX / 2, 1]);
+    assert_eq!(
+        client.weighted_median(&caller, &asset, &3, &weights),
+        Some(200)
+    );
+    //total weight overflow
+    let weights = Vec::from_array(&env, [i128::MAX, i128::MAX, 1]);
+    assert_eq!(client.weighted_median(&caller, &asset, &3, &weights), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn weighted_median_invalid_weights_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let weights = Vec::from_array(&env, [1, 0]);
+    client.weighted_median(&caller, &asset, &2, &weights);
+}
+
+#[test]
+fn lastprice_status_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.get_unchecked(0);
+    let unknown = Asset::Stellar(Address::generate(&env));
+    //no updates yet
+    assert_eq!(
+        client.lastprice_status(&caller, &asset),
+        PriceStatus::NoData
+    );
+    assert_eq!(
+        client.lastprice_status(&caller, &unknown),
+        PriceStatus::Unsupported
+    );
+
+    //only the second asset gets updated
+    client.set_price(&generate_updates(&env, &[0, 200]), &900_000);
+    assert_eq!(
+        client.lastprice_status(&caller, &asset),
+        PriceStatus::NoData
+    );
+    assert_eq!(
+        client.lastprice_status(&caller, &init_data.assets.get_unchecked(1)),
+        PriceStatus::Ok(PriceData {
+            price: 200,
+            timestamp: 900
+        })
+    );
+
+    //oracle stops receiving updates
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    assert_eq!(client.lastprice_status(&caller, &asset), PriceStatus::Stale);
+    assert!(client.lastprice(&caller, &asset).is_none());
+}
+
+#[test]
+fn lastprice_status_unsupported_not_charged_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    let token = TokenClient::new(&env, &fee_asset);
+
+    let unknown = Asset::Stellar(Address::generate(&env));
+    assert_eq!(
+        client.lastprice_status(&caller, &unknown),
+        PriceStatus::Unsupported
+    );
+    assert_eq!(token.balance(&caller), 100_000_000);
+    //supported assets are still charged
+    client.lastprice_status(&caller, &init_data.assets.get_unchecked(0));
+    assert!(token.balance(&caller) < 100_000_000);
+}
+
+#[test]
+fn set_economics_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    let fee_config = FeeConfig::Some((fee_asset, 1_000_000));
+    let costs = Vec::from_array(&env, [1, 2, 3, 4, 5]);
+    client.set_economics(&fee_config, &costs, &600_000);
+
+    assert_eq!(client.fee_config(), fee_config);
+    assert_eq!(client.invocation_costs(), costs);
+    assert_eq!(client.history_retention_period(), Some(600));
+
+    //zero period disables the retention
+    client.set_economics(&fee_config, &costs, &0);
+    assert_eq!(client.history_retention_period(), None);
+}
+
+#[test]
+fn set_economics_invalid_settings_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    let fee_config = client.fee_config();
+    let costs = client.invocation_costs();
+    let retention_period = client.history_retention_period();
+    let valid_costs = Vec::from_array(&env, [1, 2, 3, 4, 5]);
+
+    //invalid costs
+    assert_eq!(
+        client.try_set_economics(
+            &FeeConfig::Some((fee_asset.clone(), 1_000_000)),
+            &Vec::from_array(&env, [1, 2, 3]),
+            &600_000
+        ),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    //invalid fee
+    assert_eq!(
+        client.try_set_economics(
+            &FeeConfig::Some((fee_asset.clone(), 0)),
+            &valid_costs,
+            &600_000
+        ),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    //too short retention period
+    assert_eq!(
+        client.try_set_economics(
+            &FeeConfig::Some((fee_asset, 1_000_000)),
+            &valid_costs,
+            &35_000
+        ),
+        Err(Ok(Error::RetentionPeriodTooShort.into()))
+    );
+
+    //prior state is unchanged
+    assert_eq!(client.fee_config(), fee_config);
+    assert_eq!(client.invocation_costs(), costs);
+    assert_eq!(client.history_retention_period(), retention_period);
+}
+
+#[test]
+fn price_in_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    client.set_price(&generate_updates(&env, &[400, 200]), &600_000);
+    client.set_price(&generate_updates(&env, &[600, 0]), &900_000);
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.get_unchecked(0);
+    let denom = init_data.assets.get_unchecked(1);
+    let price = client.price_in(&caller, &asset, &denom, &600).unwrap();
+    assert_eq!(
+        Some(price.clone()),
+        client.x_price(&caller, &asset, &denom, &600)
+    );
+    assert_eq!(price.price, 2 * 10i128.pow(14));
+    //inverse pair
+    assert_eq!(
+        client.price_in(&caller, &denom, &asset, &600),
+        client.x_price(&caller, &denom, &asset, &600)
+    );
+    //denom price is missing
+    assert!(client.price_in(&caller, &asset, &denom, &900).is_none());
+    //unknown asset
+    let unknown = Asset::Stellar(Address::generate(&env));
+    assert_eq!(
+        client.try_price_in(&caller, &asset, &unknown, &600),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+}
+
+#[test]
+fn lastprice_split_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    client.set_price(&generate_updates(&env, &[100]), &900_000);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let fee_token = StellarAssetClient::new(&env, &fee_asset);
+    fee_token.mint(&first, &10_000_000);
+    fee_token.mint(&second, &10_000_000);
+
+    let asset = init_data.assets.first_unchecked();
+    let cost = client.estimate_cost(&InvocationComplexity::Price, &1);
+    let payers = Vec::from_array(
+        &env,
+        [
+            (first.clone(), 4_000_000),
+            (second.clone(), cost - 4_000_000),
+        ],
+    );
+    let price = client.lastprice_split(&payers, &asset).unwrap();
+    assert_eq!(price.price, 100);
+    //each payer authorized the invocation
+    let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(a, _)| a).collect();
+    assert!(authorized.contains(&first) && authorized.contains(&second));
+
+    let balance = TokenClient::new(&env, &fee_asset);
+    assert_eq!(balance.balance(&first), 6_000_000);
+    assert_eq!(balance.balance(&second), 10_000_000 - (cost - 4_000_000));
+    assert_eq!(client.total_fees_burned(), cost);
+
+    //shares don't cover the invocation cost
+    let payers = Vec::from_array(&env, [(first.clone(), 1), (second.clone(), 1)]);
+    assert_eq!(
+        client.try_lastprice_split(&payers, &asset),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    assert_eq!(balance.balance(&first), 6_000_000);
+}
+
+#[test]
+fn invocation_fee_split_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let treasury = Address::generate(&env);
+    client.set_fee_split(&2_500, &7_500, &treasury);
+
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    client.lastprice(&caller, &init_data.assets.first_unchecked());
+
+    //price invocation costs 10_000_000
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 90_000_000);
+    assert_eq!(token.balance(&treasury), 7_500_000);
+    assert_eq!(client.total_fees_burned(), 2_500_000);
+}
+
+#[test]
+fn fee_balance_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    client.set_fee_config(&FeeConfig::None);
+    assert_eq!(client.fee_balance(), 0);
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    //route the treasury share to the oracle contract itself
+    client.set_fee_split(&2_500, &7_500, &client.address);
+    assert_eq!(client.fee_balance(), 0);
+
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    client.lastprice(&caller, &init_data.assets.first_unchecked());
+    assert_eq!(client.fee_balance(), 7_500_000);
+    client.lastprice(&caller, &init_data.assets.first_unchecked());
+    assert_eq!(client.fee_balance(), 15_000_000);
+}
+
+#[test]
+fn unknown_asset_query_not_charged_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+
+    let unknown = Asset::Stellar(Address::generate(&env));
+    let asset = init_data.assets.first_unchecked();
+    assert_eq!(
+        client.try_price(&caller, &unknown, &600),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert_eq!(
+        client.try_twap(&caller, &unknown, &3),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert_eq!(
+        client.try_x_last_price(&caller, &asset, &unknown),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 100_000_000);
+    assert_eq!(client.total_fees_burned(), 0);
+}
+
+#[test]
+fn asset_fee_multiplier_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    let token = TokenClient::new(&env, &fee_asset);
+
+    let baseline = init_data.assets.get_unchecked(0);
+    let premium = init_data.assets.get_unchecked(1);
+    assert_eq!(client.asset_fee_multiplier(&premium), Some(10_000));
+    client.set_asset_fee_multiplier(&premium, &20_000);
+    assert_eq!(client.asset_fee_multiplier(&premium), Some(20_000));
+    assert_eq!(client.asset_fee_multiplier(&baseline), Some(10_000));
+
+    client.lastprice(&caller, &baseline);
+    let baseline_cost = 100_000_000 - token.balance(&caller);
+    assert_eq!(baseline_cost, 10_000_000);
+    client.lastprice(&caller, &premium);
+    let premium_cost = 100_000_000 - baseline_cost - token.balance(&caller);
+    assert_eq!(premium_cost, 2 * baseline_cost);
+
+    //reset to the default multiplier
+    client.set_asset_fee_multiplier(&premium, &10_000);
+    assert_eq!(client.asset_fee_multiplier(&premium), Some(10_000));
+    assert_eq!(
+        client.try_set_asset_fee_multiplier(&premium, &0),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    let unknown = Asset::Stellar(Address::generate(&env));
+    assert_eq!(client.asset_fee_multiplier(&unknown), None);
+}
+
+#[test]
+fn cost_breakdown_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+    let token = TokenClient::new(&env, &fee_asset);
+
+    let premium = init_data.assets.get_unchecked(1);
+    let quoted = Vec::from_array(&env, [premium.clone()]);
+    //no modifiers
+    assert_eq!(
+        client.cost_breakdown(&caller, &quoted, &InvocationComplexity::Price, &1),
+        (10_000_000, 0, 10_000_000)
+    );
+
+    //x1.5 premium feed and 20% caller discount
+    client.set_asset_fee_multiplier(&premium, &15_000);
+    assert_eq!(
+        client.try_set_caller_discount(&caller, &10_001),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    client.set_caller_discount(&caller, &2_000);
+    assert_eq!(client.caller_discount(&caller), 2_000);
+    let (base, adjustments, net) =
+        client.cost_breakdown(&caller, &quoted, &InvocationComplexity::Price, &1);
+    assert_eq!(base, 10_000_000);
+    assert_eq!(net, 12_000_000);
+    assert_eq!(base + adjustments, net);
+
+    //net amount matches the charged fee
+    client.lastprice(&caller, &premium);
+    assert_eq!(token.balance(&caller), 100_000_000 - net);
+
+    //discount is caller-specific
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.cost_breakdown(&other, &quoted, &InvocationComplexity::Price, &1),
+        (10_000_000, 5_000_000, 15_000_000)
+    );
+    //discounts are kept in per-caller persistent entries
+    let discount_key = ("discount", caller.clone());
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&discount_key));
+    });
+    client.set_caller_discount(&caller, &0);
+    assert_eq!(client.caller_discount(&caller), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&discount_key));
+    });
+}
+
+#[test]
+fn audit_mode_read_event_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let caller = Address::generate(&env);
+    let asset = init_data.assets.first_unchecked();
+    let read_topics = (
+        Symbol::new(&env, "REFLECTOR"),
+        Symbol::new(&env, "read"),
+        caller.clone(),
+    )
+        .into_val(&env);
+
+    //no read events by default
+    assert!(!client.audit_mode());
+    client.lastprice(&caller, &asset);
+    assert!(env
+        .events()
+        .all()
+        .iter()
+        .all(|(_, topics, _)| topics != read_topics));
+
+    client.set_audit_mode(&true);
+    assert!(client.audit_mode());
+    client.x_last_price(&caller, &asset, &init_data.assets.last_unchecked());
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, read_topics);
+    let data: Map<Symbol, Val> = data.try_into_val(&env).unwrap();
+    let invocation: u32 = data
+        .get(Symbol::new(&env, "invocation"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(invocation, InvocationComplexity::CrossPrice as u32);
+    let assets: Vec<Val> = data
+        .get(Symbol::new(&env, "assets"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(assets.len(), 2);
+    let timestamp: u64 = data
+        .get(Symbol::new(&env, "timestamp"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(timestamp, env.ledger().timestamp());
+}
+
+#[test]
+fn index_twap_test() {
+    let (env, client, init_data) = init_contract_with_admin();
+
+    let fee_asset = env
+        .register_stellar_asset_contract_v2(init_data.admin.clone())
+        .address();
+    client.set_fee_config(&FeeConfig::Some((fee_asset.clone(), 1_000_000)));
+    let caller = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset).mint(&caller, &100_000_000);
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    client.set_price(&generate_updates(&env, &[100, 300]), &900_000);
+    client.set_price(&generate_updates(&env, &[200, 500]), &1_200_000);
+
+    let assets = Vec::from_array(
+        &env,
+        [
+            init_data.assets.get_unchecked(0),
+            init_data.assets.get_unchecked(1),
+        ],
+    );
+    //TWAPs are 150 and 400
+    let weights = Vec::from_array(&env, [1, 1]);
+    assert_eq!(client.index_twap(&caller, &assets, &weights, &2), Some(275));
+    //charged per constituent
+    let token = TokenClient::new(&env, &fee_asset);
+    assert_eq!(token.balance(&caller), 100_000_000 - 2 * 15_000_000);
+
+    //mismatched weights
+    let weights = Vec::from_array(&env, [1]);
+    assert_eq!(client.index_twap(&caller, &assets, &weights, &2), None);
+    //constituent without prices
+    let assets = Vec::from_array(
+        &env,
+        [
+            init_data.assets.get_unchecked(0),
+            init_data.assets.get_unchecked(2),
+        ],
+    );
+    let weights = Vec::from_array(&env, [1, 1]);
+    assert_eq!(client.index_twap(&caller, &assets, &weights, &2), None);
+}
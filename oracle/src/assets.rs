@@ -1,16 +1,26 @@
 use crate::types::{Asset, Error, FeeConfig};
-use crate::{settings, timestamps};
-use soroban_sdk::{panic_with_error, token::TokenClient, Address, Env, Vec};
+use crate::{fees, settings, timestamps};
+use soroban_sdk::{panic_with_error, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
 
 const ASSET_LIMIT: u32 = 1000; //current limit
 
 //storage keys
 const ASSETS_KEY: &str = "assets";
 const EXPIRATION_KEY: &str = "expiration";
+const PRICE_BOUNDS_KEY: &str = "price_bounds";
+const LABELS_KEY: &str = "labels";
+const RESOLUTIONS_KEY: &str = "resolutions";
+const TOMBSTONES_KEY: &str = "tombstones";
+const PRICE_FLOORS_KEY: &str = "price_floors";
+const FEE_MULTIPLIERS_KEY: &str = "fee_multipliers";
+const ALIASES_KEY: &str = "aliases";
+const UNPRICED_KEY: &str = "unpriced";
+
+pub const DEFAULT_FEE_MULTIPLIER: u32 = 10_000; //1x, in basis points
 
 fn get_expiration_timestamp(e: &Env, initial_expiration_period: u32) -> u64 {
     if initial_expiration_period > 0 {
-        return timestamps::ledger_timestamp(&e)
+        return timestamps::ledger_timestamp(e)
             .checked_add(timestamps::days_to_milliseconds(initial_expiration_period))
             .unwrap();
     }
@@ -27,16 +37,10 @@ pub fn load_all_assets(e: &Env) -> Vec<Asset> {
 
 // Load asset index
 pub fn resolve_asset_index(e: &Env, asset: &Asset) -> Option<u32> {
-    let index: Option<u32>;
     match asset {
-        Asset::Stellar(address) => {
-            index = e.storage().instance().get(&address);
-        }
-        Asset::Other(symbol) => {
-            index = e.storage().instance().get(&symbol);
-        }
+        Asset::Stellar(address) => e.storage().instance().get(&address),
+        Asset::Other(symbol) => e.storage().instance().get(&symbol),
     }
-    index
 }
 
 // Resolve indexes for a pair of assets
@@ -57,14 +61,21 @@ pub fn add_assets(e: &Env, assets: Vec<Asset>, initial_expiration_period: u32) {
     //load current state
     let mut asset_list = load_all_assets(e);
     let mut expiration = load_expiration_records(e);
-    let is_fee_config_set = settings::get_fee_config(e) != FeeConfig::None;
+    let is_fee_config_set =
+        settings::has_fee_config(e) && settings::get_fee_config(e) != FeeConfig::None;
+    //new assets are tracked until the first price update only if placeholder prices are enabled
+    let is_placeholder_set = settings::get_placeholder_price(e).is_some();
+    let mut unpriced = load_unpriced_assets(e);
     //for each new asset
     for asset in assets.iter() {
-        //check if the asset has been already added
-        if resolve_asset_index(e, &asset).is_some() {
+        //check if the asset (or its canonical counterpart) has been already added
+        if resolve_asset_index(e, &asset).is_some() || is_alias_listed(e, &asset) {
             panic_with_error!(&e, Error::AssetAlreadyExists);
         }
         set_asset_index(e, &asset, asset_list.len());
+        if is_placeholder_set {
+            unpriced.set(asset_list.len(), true);
+        }
         asset_list.push_back(asset);
         //if the fee is not set, we don't need to set the expiration
         if is_fee_config_set && expiration_timestamp > 0 {
@@ -77,22 +88,104 @@ pub fn add_assets(e: &Env, assets: Vec<Asset>, initial_expiration_period: u32) {
     //update assets list and expirations vector
     e.storage().instance().set(&ASSETS_KEY, &asset_list);
     set_expirations_records(e, &expiration);
+    if is_placeholder_set {
+        e.storage().instance().set(&UNPRICED_KEY, &unpriced);
+    }
+    //new records use the general layout once the oracle tracks more than one asset
+    if asset_list.len() > 1 && settings::get_single_asset_layout(e) {
+        settings::set_single_asset_layout(e, false);
+    }
+}
+
+// Check whether the asset has been added while placeholder prices were enabled and hasn't been priced yet
+pub fn is_asset_unpriced(e: &Env, asset_index: u32) -> bool {
+    load_unpriced_assets(e).contains_key(asset_index)
 }
 
-// Retrieve expiration time for given asset
+// Stop tracking assets that received a price in the update
+pub fn clear_unpriced_assets(e: &Env, asset_prices: &Vec<i128>) {
+    let mut unpriced = load_unpriced_assets(e);
+    if unpriced.is_empty() {
+        return;
+    }
+    for asset_index in unpriced.keys() {
+        if asset_prices.get(asset_index).unwrap_or_default() > 0 {
+            unpriced.remove(asset_index);
+        }
+    }
+    if unpriced.is_empty() {
+        e.storage().instance().remove(&UNPRICED_KEY);
+    } else {
+        e.storage().instance().set(&UNPRICED_KEY, &unpriced);
+    }
+}
+
+// Load assets awaiting the first price update
+fn load_unpriced_assets(e: &Env) -> Map<u32, bool> {
+    e.storage()
+        .instance()
+        .get(&UNPRICED_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Register alias pointing to the canonical asset representing the same economic asset
+// Setting an alias to itself removes the mapping
+pub fn set_asset_alias(e: &Env, alias: Asset, canonical: Asset) {
+    let mut aliases = load_asset_aliases(e);
+    if alias == canonical {
+        aliases.remove(alias);
+    } else {
+        //chained aliases are not supported in either direction
+        if aliases.contains_key(canonical.clone()) || aliases.values().contains(&alias) {
+            e.panic_with_error(Error::ChainedAlias);
+        }
+        aliases.set(alias, canonical);
+    }
+    e.storage().instance().set(&ALIASES_KEY, &aliases);
+}
+
+// Resolve canonical asset for the alias, returns the asset itself if no alias is configured
+pub fn resolve_canonical_asset(e: &Env, asset: &Asset) -> Asset {
+    load_asset_aliases(e)
+        .get(asset.clone())
+        .unwrap_or_else(|| asset.clone())
+}
+
+// Check whether any other form of the same economic asset is already listed
+fn is_alias_listed(e: &Env, asset: &Asset) -> bool {
+    let canonical = resolve_canonical_asset(e, asset);
+    if canonical != *asset && resolve_asset_index(e, &canonical).is_some() {
+        return true;
+    }
+    load_asset_aliases(e).iter().any(|(alias, target)| {
+        target == canonical && alias != *asset && resolve_asset_index(e, &alias).is_some()
+    })
+}
+
+// Load configured asset aliases (alias -> canonical asset)
+fn load_asset_aliases(e: &Env) -> Map<Asset, Asset> {
+    e.storage()
+        .instance()
+        .get(&ALIASES_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Retrieve expiration time for given asset, zero (padded) expiration records are treated as missing
 pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
     let asset_index = resolve_asset_index(e, &asset);
     if asset_index.is_none() {
         e.panic_with_error(Error::AssetMissing);
     }
     let expirations = load_expiration_records(e);
-    expirations.get(asset_index.unwrap())
+    expirations
+        .get(asset_index.unwrap())
+        .filter(|expiration| *expiration > 0)
 }
 
 // Initialize expiration records for all existing assets
 pub fn init_expiration_config(e: &Env, initial_expiration_period: u32) {
     let mut expiration_records = load_expiration_records(e);
-    if expiration_records.len() > 0 {
+    if !expiration_records.is_empty() {
         return; // expiration values for existing price feeds already initialized
     }
     //init expiration, set INITIAL_EXPIRATION_PERIOD for all symbols by default
@@ -135,16 +228,26 @@ pub fn extend_ttl(
             e.panic_with_error(Error::InvalidConfigVersion);
         }
     };
-    //burn corresponding amount of fee tokens
-    TokenClient::new(&e, &xrf).burn(&sponsor, &amount);
+    //charge corresponding amount of fee tokens
+    fees::charge(e, &xrf, &sponsor, amount);
     //calculate extension period
-    let bump = amount * 86400000 / fee; // in milliseconds
+    let bump = calculate_extension(amount, fee); // in milliseconds
     if bump <= 0 {
         e.panic_with_error(Error::InvalidAmount);
     }
+    extend_expiration(e, asset_index, bump as u64, initial_expiration_period);
+}
+
+// Calculate expiration extension period (in milliseconds) for given amount of fee tokens
+pub fn calculate_extension(amount: i128, fee: i128) -> i128 {
+    amount * 86400000 / fee
+}
+
+// Bump expiration for given asset price feed by the extension period (in milliseconds)
+pub fn extend_expiration(e: &Env, asset_index: u32, bump: u64, initial_expiration_period: u32) {
     //load expiration info
     let mut expiration = load_expiration_records(e);
-    let now = timestamps::ledger_timestamp(&e);
+    let now = timestamps::ledger_timestamp(e);
     let mut asset_expiration = expiration
         .get(asset_index)
         .unwrap_or_else(|| now + timestamps::days_to_milliseconds(initial_expiration_period));
@@ -153,15 +256,298 @@ pub fn extend_ttl(
         asset_expiration = now;
     }
     //bump expiration
-    asset_expiration = asset_expiration.checked_add(bump as u64).unwrap();
+    asset_expiration = asset_expiration.checked_add(bump).unwrap();
+    //fill missing records for assets added without expiration
+    while expiration.len() <= asset_index {
+        expiration.push_back(0);
+    }
     //write into the vector that holds expiration dates for all symbols
     expiration.set(asset_index, asset_expiration);
     //update expiration records in instance storage
     set_expirations_records(e, &expiration)
 }
 
+// Set sanity bounds for asset prices
+pub fn set_price_bounds(e: &Env, asset: Asset, min: i128, max: i128) {
+    let asset_index = resolve_asset_index(e, &asset);
+    if asset_index.is_none() {
+        e.panic_with_error(Error::AssetMissing);
+    }
+    if min <= 0 || max < min {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    let mut bounds = load_price_bounds(e);
+    bounds.set(asset_index.unwrap(), (min, max));
+    e.storage().instance().set(&PRICE_BOUNDS_KEY, &bounds);
+}
+
+// Load configured price bounds for all assets
+pub fn load_price_bounds(e: &Env) -> Map<u32, (i128, i128)> {
+    e.storage()
+        .instance()
+        .get(&PRICE_BOUNDS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Check whether all prices are within configured bounds
+pub fn check_price_bounds(e: &Env, prices: &Vec<i128>) -> Result<(), Error> {
+    //global cap keeps headroom for the fixed point shift in cross price calculation
+    let max_price = settings::get_max_price(e);
+    if max_price > 0 && prices.iter().any(|price| price > max_price) {
+        return Err(Error::PriceOutOfBounds);
+    }
+    let bounds = load_price_bounds(e);
+    for (asset_index, (min, max)) in bounds.iter() {
+        let price = prices.get(asset_index).unwrap_or_default();
+        if price != 0 && (price < min || price > max) {
+            return Err(Error::PriceOutOfBounds);
+        }
+    }
+    Ok(())
+}
+
+// Set human-readable label for the asset
+pub fn set_asset_label(e: &Env, asset: Asset, label: Symbol) {
+    let asset_index = resolve_asset_index(e, &asset);
+    if asset_index.is_none() {
+        e.panic_with_error(Error::AssetMissing);
+    }
+    let mut labels = load_asset_labels(e);
+    labels.set(asset_index.unwrap(), label);
+    e.storage().instance().set(&LABELS_KEY, &labels);
+}
+
+// Set human-readable labels for multiple assets, all assets are validated before writing any label
+pub fn set_asset_labels(e: &Env, entries: Vec<(Asset, Symbol)>) {
+    let mut resolved = Vec::new(e);
+    for (asset, label) in entries.iter() {
+        match resolve_asset_index(e, &asset) {
+            Some(asset_index) => resolved.push_back((asset_index, label)),
+            None => e.panic_with_error(Error::AssetMissing),
+        }
+    }
+    let mut labels = load_asset_labels(e);
+    for (asset_index, label) in resolved.iter() {
+        labels.set(asset_index, label);
+    }
+    e.storage().instance().set(&LABELS_KEY, &labels);
+}
+
+// Get human-readable label for the asset
+pub fn get_asset_label(e: &Env, asset: &Asset) -> Option<Symbol> {
+    let asset_index = resolve_asset_index(e, asset)?;
+    load_asset_labels(e).get(asset_index)
+}
+
+// Load labels for all assets
+fn load_asset_labels(e: &Env) -> Map<u32, Symbol> {
+    e.storage()
+        .instance()
+        .get(&LABELS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Set resolution override (in milliseconds) for the asset, zero resets it to the global resolution
+pub fn set_asset_resolution(e: &Env, asset: Asset, resolution: u32) {
+    let asset_index = resolve_asset_index(e, &asset);
+    if asset_index.is_none() {
+        e.panic_with_error(Error::AssetMissing);
+    }
+    //asset resolution should be aligned with the global history timeframe
+    let global_resolution = settings::get_resolution(e);
+    if global_resolution == 0 || !resolution.is_multiple_of(global_resolution) {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    let mut resolutions = load_asset_resolutions(e);
+    if resolution == 0 || resolution == global_resolution {
+        resolutions.remove(asset_index.unwrap());
+    } else {
+        resolutions.set(asset_index.unwrap(), resolution);
+    }
+    e.storage().instance().set(&RESOLUTIONS_KEY, &resolutions);
+}
+
+// Get effective resolution (in milliseconds) for the asset, falls back to the global resolution
+pub fn get_asset_resolution(e: &Env, asset_index: u32) -> u32 {
+    load_asset_resolutions(e)
+        .get(asset_index)
+        .unwrap_or_else(|| settings::get_resolution(e))
+}
+
+// Set minimum price of the asset used as a cross-price denominator, zero removes the floor
+pub fn set_asset_price_floor(e: &Env, asset: Asset, floor: i128) {
+    let asset_index = resolve_asset_index(e, &asset);
+    if asset_index.is_none() {
+        e.panic_with_error(Error::AssetMissing);
+    }
+    if floor < 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    let mut floors = load_asset_price_floors(e);
+    if floor == 0 {
+        floors.remove(asset_index.unwrap());
+    } else {
+        floors.set(asset_index.unwrap(), floor);
+    }
+    e.storage().instance().set(&PRICE_FLOORS_KEY, &floors);
+}
+
+// Get minimum denominator price for the asset, zero if not set
+pub fn get_asset_price_floor(e: &Env, asset_index: u32) -> i128 {
+    load_asset_price_floors(e).get(asset_index).unwrap_or(0)
+}
+
+// Set invocation fee multiplier (in basis points) for the asset, default multiplier removes the override
+pub fn set_asset_fee_multiplier(e: &Env, asset: Asset, multiplier: u32) {
+    let asset_index = resolve_asset_index(e, &asset);
+    if asset_index.is_none() {
+        e.panic_with_error(Error::AssetMissing);
+    }
+    if multiplier == 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    let mut multipliers = load_asset_fee_multipliers(e);
+    if multiplier == DEFAULT_FEE_MULTIPLIER {
+        multipliers.remove(asset_index.unwrap());
+    } else {
+        multipliers.set(asset_index.unwrap(), multiplier);
+    }
+    e.storage()
+        .instance()
+        .set(&FEE_MULTIPLIERS_KEY, &multipliers);
+}
+
+// Get invocation fee multiplier (in basis points) for the asset, falls back to the default multiplier
+pub fn get_asset_fee_multiplier(e: &Env, asset_index: u32) -> u32 {
+    load_asset_fee_multipliers(e)
+        .get(asset_index)
+        .unwrap_or(DEFAULT_FEE_MULTIPLIER)
+}
+
+// Load invocation fee multipliers for all assets
+fn load_asset_fee_multipliers(e: &Env) -> Map<u32, u32> {
+    e.storage()
+        .instance()
+        .get(&FEE_MULTIPLIERS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Load denominator price floors for all assets
+fn load_asset_price_floors(e: &Env) -> Map<u32, i128> {
+    e.storage()
+        .instance()
+        .get(&PRICE_FLOORS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Load resolution overrides for all assets
+fn load_asset_resolutions(e: &Env) -> Map<u32, u32> {
+    e.storage()
+        .instance()
+        .get(&RESOLUTIONS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Remove assets expired more than the grace period ago, returns the number of removed assets
+pub fn purge_expired(e: &Env, grace_days: u32) -> u32 {
+    let now = timestamps::ledger_timestamp(e);
+    let grace = timestamps::days_to_milliseconds(grace_days);
+    let expiration = load_expiration_records(e);
+    let asset_list = load_all_assets(e);
+    let mut purged = 0;
+    for (asset_index, asset_expiration) in expiration.iter().enumerate() {
+        //zero expiration means that the feed expiration is not tracked
+        if asset_expiration == 0 || asset_expiration.saturating_add(grace) >= now {
+            continue;
+        }
+        if let Some(asset) = asset_list.get(asset_index as u32) {
+            if remove_asset(e, &asset, asset_index as u32) {
+                purged += 1;
+            }
+        }
+    }
+    purged
+}
+
+// Remove asset leaving a tombstone in its slot to keep positions of other assets intact, returns false if already removed
+pub fn remove_asset(e: &Env, asset: &Asset, asset_index: u32) -> bool {
+    if resolve_asset_index(e, asset) != Some(asset_index) {
+        return false;
+    }
+    match asset {
+        Asset::Stellar(address) => e.storage().instance().remove(address),
+        Asset::Other(symbol) => e.storage().instance().remove(symbol),
+    }
+    let mut tombstones = load_tombstones(e);
+    tombstones.push_back(asset_index);
+    e.storage().instance().set(&TOMBSTONES_KEY, &tombstones);
+    true
+}
+
+// Compact live assets to contiguous indexes, returns previous indexes of all live assets
+pub fn reindex_assets(e: &Env) -> Vec<u32> {
+    let tombstones = load_tombstones(e);
+    let asset_list = load_all_assets(e);
+    let mut live = Vec::new(e);
+    let mut compacted = Vec::new(e);
+    for (asset_index, asset) in asset_list.iter().enumerate() {
+        let asset_index = asset_index as u32;
+        if tombstones.contains(asset_index) {
+            continue;
+        }
+        set_asset_index(e, &asset, compacted.len());
+        compacted.push_back(asset);
+        live.push_back(asset_index);
+    }
+    //expiration records may be missing for the most recently added assets
+    let expiration = load_expiration_records(e);
+    let mut compacted_expiration = Vec::new(e);
+    for asset_index in live.iter() {
+        match expiration.get(asset_index) {
+            Some(asset_expiration) => compacted_expiration.push_back(asset_expiration),
+            None => break,
+        }
+    }
+    set_expirations_records(e, &compacted_expiration);
+    //per-asset settings
+    reindex_asset_settings::<(i128, i128)>(e, PRICE_BOUNDS_KEY, &live);
+    reindex_asset_settings::<Symbol>(e, LABELS_KEY, &live);
+    reindex_asset_settings::<u32>(e, RESOLUTIONS_KEY, &live);
+    reindex_asset_settings::<i128>(e, PRICE_FLOORS_KEY, &live);
+    reindex_asset_settings::<u32>(e, FEE_MULTIPLIERS_KEY, &live);
+    reindex_asset_settings::<bool>(e, UNPRICED_KEY, &live);
+    e.storage().instance().set(&ASSETS_KEY, &compacted);
+    e.storage().instance().remove(&TOMBSTONES_KEY);
+    live
+}
+
+// Move per-asset settings stored under the key to the compacted asset indexes
+fn reindex_asset_settings<V>(e: &Env, key: &str, live: &Vec<u32>)
+where
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let settings: Option<Map<u32, V>> = e.storage().instance().get(&key);
+    if let Some(settings) = settings {
+        let mut compacted = Map::new(e);
+        for (asset_index, previous_index) in live.iter().enumerate() {
+            if let Some(value) = settings.get(previous_index) {
+                compacted.set(asset_index as u32, value);
+            }
+        }
+        e.storage().instance().set(&key, &compacted);
+    }
+}
+
+// Load positions of removed assets
+pub fn load_tombstones(e: &Env) -> Vec<u32> {
+    e.storage()
+        .instance()
+        .get(&TOMBSTONES_KEY)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
 // Load expiration data for all assets
-fn load_expiration_records(e: &Env) -> Vec<u64> {
+pub fn load_expiration_records(e: &Env) -> Vec<u64> {
     e.storage()
         .instance()
         .get(&EXPIRATION_KEY)
@@ -1,8 +1,14 @@
+use crate::timestamps;
 use crate::types::Error;
-use soroban_sdk::{panic_with_error, Address, Env};
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env, Map, Vec};
 
 //storage keys
 const ADMIN_KEY: &str = "admin";
+const FEEDERS_KEY: &str = "feeders";
+const FEEDER_ACTIVITY_KEY: &str = "feeder_activity";
+const FEEDER_KEYS_KEY: &str = "feeder_keys";
+//feeder activity entries are extended once their TTL drops below ~30 days (in ledgers)
+const FEEDER_ACTIVITY_TTL_THRESHOLD: u32 = 518_400;
 
 // Get current admin account address
 #[inline]
@@ -25,3 +31,111 @@ pub fn panic_if_not_admin(e: &Env) {
     }
     admin.unwrap().require_auth()
 }
+
+// Load allowed feeders
+pub fn load_feeders(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get(&FEEDERS_KEY)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+// Load last activity timestamp of the feeder (in milliseconds, zero if never submitted)
+pub fn get_feeder_activity(e: &Env, feeder: &Address) -> u64 {
+    e.storage()
+        .persistent()
+        .get(&(FEEDER_ACTIVITY_KEY, feeder.clone()))
+        .unwrap_or_default()
+}
+
+// Add feeder account to the allowlist
+pub fn add_feeder(e: &Env, feeder: &Address) {
+    let mut feeders = load_feeders(e);
+    if !feeders.contains(feeder) {
+        feeders.push_back(feeder.clone());
+        e.storage().instance().set(&FEEDERS_KEY, &feeders);
+    }
+}
+
+// Remove feeder account from the allowlist along with its activity record and attestation keys
+pub fn remove_feeder(e: &Env, feeder: &Address) {
+    let mut feeders = load_feeders(e);
+    if let Some(index) = feeders.first_index_of(feeder) {
+        feeders.remove(index);
+        e.storage().instance().set(&FEEDERS_KEY, &feeders);
+    }
+    e.storage()
+        .persistent()
+        .remove(&(FEEDER_ACTIVITY_KEY, feeder.clone()));
+    let keys = load_feeder_keys(e);
+    let mut remaining = keys.clone();
+    for (public_key, key_feeder) in keys.iter() {
+        if key_feeder == *feeder {
+            remaining.remove(public_key);
+        }
+    }
+    if remaining.len() != keys.len() {
+        e.storage().instance().set(&FEEDER_KEYS_KEY, &remaining);
+    }
+}
+
+// Throw exception if the account is not an allowed feeder, record feeder activity otherwise
+fn record_feeder_activity(e: &Env, feeder: &Address) {
+    if !load_feeders(e).contains(feeder) {
+        panic_with_error!(e, Error::Unauthorized);
+    }
+    //activity is stored per feeder, so updates don't rewrite instance storage
+    let key = (FEEDER_ACTIVITY_KEY, feeder.clone());
+    let storage = e.storage().persistent();
+    storage.set(&key, &timestamps::ledger_timestamp(e));
+    storage.extend_ttl(&key, FEEDER_ACTIVITY_TTL_THRESHOLD, e.storage().max_ttl());
+}
+
+// Throw exception if call hasn't been authorized by an allowed feeder, record feeder activity otherwise
+pub fn panic_if_not_feeder(e: &Env, feeder: &Address) {
+    feeder.require_auth();
+    record_feeder_activity(e, feeder);
+}
+
+// Load public keys allowed to submit signed price attestations with the feeder accounts they belong to
+pub fn load_feeder_keys(e: &Env) -> Map<BytesN<32>, Address> {
+    e.storage()
+        .instance()
+        .get(&FEEDER_KEYS_KEY)
+        .unwrap_or_else(|| Map::new(e))
+}
+
+// Link feeder public key to an allowed feeder account
+pub fn add_feeder_key(e: &Env, feeder: &Address, public_key: &BytesN<32>) {
+    if !load_feeders(e).contains(feeder) {
+        panic_with_error!(e, Error::Unauthorized);
+    }
+    let mut keys = load_feeder_keys(e);
+    keys.set(public_key.clone(), feeder.clone());
+    e.storage().instance().set(&FEEDER_KEYS_KEY, &keys);
+}
+
+// Remove feeder public key from the attestation allowlist
+pub fn remove_feeder_key(e: &Env, public_key: &BytesN<32>) {
+    let mut keys = load_feeder_keys(e);
+    if keys.remove(public_key.clone()).is_some() {
+        e.storage().instance().set(&FEEDER_KEYS_KEY, &keys);
+    }
+}
+
+// Throw exception if the signer is not linked to an allowed feeder or the signature doesn't cover the message,
+// record activity of the linked feeder otherwise
+pub fn panic_if_not_attested(
+    e: &Env,
+    signer: &BytesN<32>,
+    message: &Bytes,
+    signature: &BytesN<64>,
+) {
+    let feeder = load_feeder_keys(e).get(signer.clone());
+    if feeder.is_none() {
+        panic_with_error!(e, Error::Unauthorized);
+    }
+    //traps on invalid signature
+    e.crypto().ed25519_verify(signer, message, signature);
+    record_feeder_activity(e, &feeder.unwrap());
+}
@@ -1,42 +1,175 @@
-use crate::types::{Asset, Error};
-use soroban_sdk::{contractevent, panic_with_error, Env, Val, Vec};
+use crate::timestamps;
+use crate::types::{Asset, Error, FeeConfig};
+use soroban_sdk::{contractevent, panic_with_error, Address, Bytes, Env, Val, Vec};
 
 #[contractevent(topics = ["REFLECTOR", "update"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UpdateEvent {
+    // Update timestamp (in milliseconds, kept for backward compatibility)
     #[topic]
     pub timestamp: u64,
     pub update_data: Vec<(Val, i128)>,
+    // Raw update mask as submitted in the price update
+    pub mask: Bytes,
+}
+
+#[contractevent(topics = ["REFLECTOR", "fee_token"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTokenMigratedEvent {
+    // Migration timestamp (in seconds)
+    #[topic]
+    pub timestamp: u64,
+    pub previous_token: Option<Address>,
+    pub token: Address,
+    pub rate: i128,
+}
+
+#[contractevent(topics = ["REFLECTOR", "fee_scheduled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigScheduledEvent {
+    // Timestamp (in seconds) after which the change can be applied
+    #[topic]
+    pub apply_at: u64,
+    pub fee_config: FeeConfig,
+}
+
+#[contractevent(topics = ["REFLECTOR", "fee_applied"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigAppliedEvent {
+    // Timestamp (in seconds) the change has been applied at
+    #[topic]
+    pub timestamp: u64,
+    pub fee_config: FeeConfig,
+}
+
+// Publish event announcing the scheduled fee config change (apply timestamp in milliseconds)
+#[inline]
+pub fn publish_fee_config_scheduled_event(e: &Env, fee_config: &FeeConfig, apply_at: u64) {
+    let event = FeeConfigScheduledEvent {
+        apply_at: timestamps::to_seconds(apply_at),
+        fee_config: fee_config.clone(),
+    };
+    e.events().publish_event(&event);
+}
+
+// Publish event signaling that the scheduled fee config change has been applied (timestamp in milliseconds)
+#[inline]
+pub fn publish_fee_config_applied_event(e: &Env, fee_config: &FeeConfig, timestamp: u64) {
+    let event = FeeConfigAppliedEvent {
+        timestamp: timestamps::to_seconds(timestamp),
+        fee_config: fee_config.clone(),
+    };
+    e.events().publish_event(&event);
+}
+
+#[contractevent(topics = ["REFLECTOR", "paused"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoPauseEvent {
+    // Timestamp (in seconds) of the rejected price update
+    #[topic]
+    pub timestamp: u64,
+    pub deviation_bps: i128,
+}
+
+#[contractevent(topics = ["REFLECTOR", "read"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadEvent {
+    #[topic]
+    pub caller: Address,
+    pub assets: Vec<Val>,
+    pub invocation: u32,
+    // Read timestamp (in seconds)
+    pub timestamp: u64,
+}
+
+// Publish audit event attributing a paid read to the caller (timestamp in milliseconds)
+#[inline]
+pub fn publish_read_event(
+    e: &Env,
+    caller: &Address,
+    assets: &[&Asset],
+    invocation: u32,
+    timestamp: u64,
+) {
+    let mut symbols = Vec::new(e);
+    for asset in assets {
+        symbols.push_back(resolve_asset_symbol(asset));
+    }
+    let event = ReadEvent {
+        caller: caller.clone(),
+        assets: symbols,
+        invocation,
+        timestamp: timestamps::to_seconds(timestamp),
+    };
+    e.events().publish_event(&event);
+}
+
+// Publish event signaling that price updates were paused due to severe price deviation (timestamp in milliseconds)
+#[inline]
+pub fn publish_auto_pause_event(e: &Env, deviation_bps: i128, timestamp: u64) {
+    let event = AutoPauseEvent {
+        timestamp: timestamps::to_seconds(timestamp),
+        deviation_bps,
+    };
+    e.events().publish_event(&event);
+}
+
+// Publish fee token migration event (timestamp in milliseconds)
+#[inline]
+pub fn publish_fee_token_migrated_event(
+    e: &Env,
+    previous_token: Option<Address>,
+    token: &Address,
+    rate: i128,
+    timestamp: u64,
+) {
+    let event = FeeTokenMigratedEvent {
+        timestamp: timestamps::to_seconds(timestamp),
+        previous_token,
+        token: token.clone(),
+        rate,
+    };
+    e.events().publish_event(&event);
 }
 
 // Compose and publish price update event
 #[inline]
-pub fn publish_update_event(e: &Env, updates: &Vec<i128>, all_assets: &Vec<Asset>, timestamp: u64) {
+pub fn publish_update_event(
+    e: &Env,
+    updates: &Vec<i128>,
+    mask: &Bytes,
+    all_assets: &Vec<Asset>,
+    timestamp: u64,
+) {
     //validate length
     if all_assets.len() < updates.len() {
         panic_with_error!(&e, Error::AssetLimitExceeded);
     }
     //prepare update event
-    let mut event_updates = Vec::new(&e);
+    let mut event_updates = Vec::new(e);
     for (index, asset) in all_assets.iter().enumerate() {
         //retrieve individual price
         let price = updates.get(index as u32).unwrap_or_default();
         if price == 0 {
             continue; //skip zero prices
         }
-        //resolve asset symbol
-        let symbol = match asset {
-            Asset::Stellar(address) => address.to_val(),
-            Asset::Other(symbol) => symbol.to_val(),
-        };
         //add to updates vector
-        event_updates.push_back((symbol, price));
+        event_updates.push_back((resolve_asset_symbol(&asset), price));
     }
 
     //compose and publish price update event
     let event = UpdateEvent {
         timestamp,
         update_data: event_updates,
+        mask: mask.clone(),
     };
     e.events().publish_event(&event);
 }
+
+// Resolve asset symbol used in events
+fn resolve_asset_symbol(asset: &Asset) -> Val {
+    match asset {
+        Asset::Stellar(address) => address.to_val(),
+        Asset::Other(symbol) => symbol.to_val(),
+    }
+}
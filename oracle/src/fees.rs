@@ -0,0 +1,102 @@
+use crate::types::{Error, FeeConfig};
+use crate::{events, settings, timestamps};
+use soroban_sdk::{panic_with_error, token::TokenClient, Address, Env};
+
+//storage keys
+const TOTAL_BURNED_KEY: &str = "burned";
+const FEE_MIGRATION_KEY: &str = "fee_migration";
+const PENDING_FEE_CONFIG_KEY: &str = "pending_fee";
+
+// Charge fee tokens, splitting the amount between burn and treasury transfer if configured
+pub fn charge(e: &Env, fee_token: &Address, from: &Address, amount: i128) {
+    let (burn_bps, _, treasury) = match settings::get_fee_split(e) {
+        Some(split) => split,
+        None => return burn(e, fee_token, from, amount),
+    };
+    let burn_amount = amount * burn_bps as i128 / 10_000;
+    let treasury_amount = amount - burn_amount;
+    if burn_amount > 0 {
+        burn(e, fee_token, from, burn_amount);
+    }
+    if treasury_amount > 0 {
+        TokenClient::new(e, fee_token).transfer(from, &treasury, &treasury_amount);
+    }
+}
+
+// Burn fee tokens and account them in the total burned amount
+pub fn burn(e: &Env, fee_token: &Address, from: &Address, amount: i128) {
+    TokenClient::new(e, fee_token).burn(from, &amount);
+    let total = get_total_burned(e).checked_add(amount).unwrap();
+    e.storage().instance().set(&TOTAL_BURNED_KEY, &total);
+}
+
+// Get total amount of fee tokens burned by the oracle
+#[inline]
+pub fn get_total_burned(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&TOTAL_BURNED_KEY)
+        .unwrap_or_default()
+}
+
+// Switch fee token and daily retention rate, existing expirations are kept as is
+pub fn migrate_fee_token(e: &Env, token: &Address, rate: i128) {
+    if rate <= 0 {
+        panic_with_error!(e, Error::InvalidAmount);
+    }
+    let previous_token = match settings::get_fee_config(e) {
+        FeeConfig::Some((previous_token, _)) => Some(previous_token),
+        FeeConfig::None => None,
+    };
+    //new rate applies only to subsequent extensions
+    settings::set_fee_config(e, &FeeConfig::Some((token.clone(), rate)));
+    let timestamp = timestamps::ledger_timestamp(e);
+    e.storage().instance().set(&FEE_MIGRATION_KEY, &timestamp);
+    events::publish_fee_token_migrated_event(e, previous_token, token, rate, timestamp);
+}
+
+// Schedule fee config change taking effect only after the given timestamp (in milliseconds)
+pub fn schedule_fee_config(e: &Env, fee_config: &FeeConfig, apply_at: u64) {
+    if apply_at <= timestamps::ledger_timestamp(e) {
+        panic_with_error!(e, Error::InvalidTimestamp);
+    }
+    e.storage()
+        .instance()
+        .set(&PENDING_FEE_CONFIG_KEY, &(fee_config.clone(), apply_at));
+    events::publish_fee_config_scheduled_event(e, fee_config, apply_at);
+}
+
+// Get scheduled fee config change along with the timestamp (in milliseconds) it can be applied at
+#[inline]
+pub fn get_pending_fee_config(e: &Env) -> Option<(FeeConfig, u64)> {
+    e.storage().instance().get(&PENDING_FEE_CONFIG_KEY)
+}
+
+// Promote scheduled fee config if the timelock has passed, returns promoted config
+pub fn apply_pending_fee_config(e: &Env) -> Option<FeeConfig> {
+    let (fee_config, apply_at) = get_pending_fee_config(e)?;
+    let timestamp = timestamps::ledger_timestamp(e);
+    if timestamp < apply_at {
+        return None; //timelock is still active
+    }
+    settings::set_fee_config(e, &fee_config);
+    e.storage().instance().remove(&PENDING_FEE_CONFIG_KEY);
+    events::publish_fee_config_applied_event(e, &fee_config, timestamp);
+    Some(fee_config)
+}
+
+// Get balance of the configured fee token held by the oracle contract
+pub fn get_fee_balance(e: &Env) -> i128 {
+    match settings::get_fee_config(e) {
+        FeeConfig::Some((fee_token, _)) => {
+            TokenClient::new(e, &fee_token).balance(&e.current_contract_address())
+        }
+        FeeConfig::None => 0,
+    }
+}
+
+// Get timestamp of the last fee token migration
+#[inline]
+pub fn get_fee_migration_timestamp(e: &Env) -> Option<u64> {
+    e.storage().instance().get(&FEE_MIGRATION_KEY)
+}
@@ -2,6 +2,7 @@
 pub mod assets;
 pub mod auth;
 pub mod events;
+pub mod fees;
 pub mod mapping;
 pub mod price_oracle;
 pub mod prices;
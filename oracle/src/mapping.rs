@@ -25,7 +25,7 @@ pub fn update_history_mask(e: &Env, mut history_mask: Bytes, updates: &Vec<i128>
             bitmask = bitmask.add(&one);
         }
         //encode into bytes again
-        let encoded = bitmask.to_be_bytes();
+        let encoded = encode_record(e, bitmask.to_be_bytes());
         //write to the history
         if history_mask.len() <= from {
             //that's new asset, add to the mask
@@ -40,6 +40,78 @@ pub fn update_history_mask(e: &Env, mut history_mask: Bytes, updates: &Vec<i128>
     history_mask //return updated history
 }
 
+// Ensure that encoded asset mask occupies exactly RECORD_SIZE bytes to keep subsequent slices aligned
+fn encode_record(e: &Env, encoded: Bytes) -> Bytes {
+    let len = encoded.len();
+    if len == RECORD_SIZE {
+        return encoded;
+    }
+    if len > RECORD_SIZE {
+        //keep least significant bytes
+        return encoded.slice(len - RECORD_SIZE..len);
+    }
+    //left-pad with zeros
+    let mut padded = Bytes::from_array(e, &[0u8; RECORD_SIZE as usize]);
+    for i in 0..len {
+        padded.set(RECORD_SIZE - len + i, encoded.get_unchecked(i));
+    }
+    padded
+}
+
+// Overwrite history records bits for a certain period in the past without shifting the mask
+pub fn update_history_period(
+    e: &Env,
+    mut history_mask: Bytes,
+    updates: &Vec<i128>,
+    period: u32,
+) -> Bytes {
+    for (asset_index, price) in updates.iter().enumerate() {
+        //locate particular asset mask slice position within entire history record
+        let from = asset_index as u32 * RECORD_SIZE;
+        if history_mask.len() <= from {
+            //that's new asset, add empty mask slice
+            history_mask.append(&Bytes::from_array(e, &[0u8; RECORD_SIZE as usize]));
+        }
+        //locate byte and bit corresponding to the period
+        let position = from + (RECORD_SIZE - 1 - period / 8);
+        let bit = 1 << (period % 8);
+        let bytemask = history_mask.get(position).unwrap_or_default();
+        //set corresponding bit if price found, clear otherwise
+        if price > 0 {
+            history_mask.set(position, bytemask | bit);
+        } else {
+            history_mask.set(position, bytemask & !bit);
+        }
+    }
+    history_mask //return updated history
+}
+
+// Copy history records of given assets into a new history mask, preserving the order of indexes
+pub fn compact_history_mask(e: &Env, history_mask: &Bytes, asset_indexes: &Vec<u32>) -> Bytes {
+    let mut res = Bytes::new(e);
+    for asset_index in asset_indexes.iter() {
+        let from = asset_index * RECORD_SIZE;
+        let to = from + RECORD_SIZE;
+        if history_mask.len() < to {
+            break; //no records for this and subsequent assets
+        }
+        res.append(&history_mask.slice(from..to));
+    }
+    res
+}
+
+// Build update record mask for given prices, zero prices are not included
+pub fn build_update_record_mask(e: &Env, prices: &Vec<i128>) -> Bytes {
+    let mut mask = Bytes::from_array(e, &[0u8; RECORD_SIZE as usize]);
+    for (asset_index, price) in prices.iter().enumerate() {
+        if price > 0 {
+            let (byte, bitmask) = resolve_period_update_mask_position(asset_index as u32);
+            mask.set(byte, mask.get_unchecked(byte) | bitmask);
+        }
+    }
+    mask
+}
+
 // Check whether asset price has been quoted for a certain period based on history records bitmask
 pub fn check_history_updated(history_mask: &Bytes, asset_index: u32, period: u32) -> bool {
     //locate particular asset mask slice position within entire history record
@@ -52,6 +124,24 @@ pub fn check_history_updated(history_mask: &Bytes, asset_index: u32, period: u32
     bytemask & bit == bit
 }
 
+// Check whether any asset price has been quoted for a certain period based on history records bitmask
+pub fn check_period_recorded(history_mask: &Bytes, period: u32) -> bool {
+    (0..history_mask.len() / RECORD_SIZE)
+        .any(|asset_index| check_history_updated(history_mask, asset_index, period))
+}
+
+// Count periods with asset price quotes among the given number of the most recent periods
+pub fn count_history_updates(history_mask: &Bytes, asset_index: u32, periods: u32) -> u32 {
+    (0..periods.min(256))
+        .filter(|period| check_history_updated(history_mask, asset_index, *period))
+        .count() as u32
+}
+
+// Count assets marked as updated in the price update record mask
+pub fn count_updated_assets(period_mask: &Bytes) -> u32 {
+    period_mask.iter().map(|byte| byte.count_ones()).sum()
+}
+
 // Check whether price update record contains update for given asset by its index
 pub fn check_period_updated(period_mask: &Bytes, asset_index: u32) -> bool {
     //calculate byte position and bit index to check
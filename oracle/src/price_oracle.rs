@@ -1,10 +1,217 @@
 use crate::types::ConfigData;
-use crate::types::{Asset, Error, FeeConfig, PriceData, PriceUpdate};
-use crate::{assets, auth, events, prices, protocol, settings, timestamps};
-use soroban_sdk::{panic_with_error, Address, BytesN, Env, Vec};
+use crate::types::{
+    Asset, CacheEvictionPolicy, Error, FeeConfig, PriceData, PriceStatus, PriceUpdate,
+};
+use crate::{assets, auth, events, fees, mapping, prices, protocol, settings, timestamps};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env, Symbol, Vec};
 
 pub struct PriceOracleContractBase;
 
+// Throw exception instead of returning None if strict mode is enabled
+fn require_in_strict_mode<T>(e: &Env, value: Option<T>, error: Error) -> Option<T> {
+    if value.is_none() && settings::get_strict_mode(e) {
+        panic_with_error!(e, error);
+    }
+    value
+}
+
+// Reject TWAP windows shorter than the configured minimum
+fn panic_if_twap_window_too_short(e: &Env, records: u32) {
+    if records < settings::get_min_twap_records(e) {
+        panic_with_error!(e, Error::TwapWindowTooShort);
+    }
+}
+
+// Calculate TWAP of the cross price for the given asset pair over N recent records
+fn calculate_cross_twap(e: &Env, asset_pair_indexes: (u32, u32), records: u32) -> Option<i128> {
+    let decimals = settings::get_decimals(e);
+    prices::calculate_twap(
+        e,
+        |timestamp| prices::load_cross_price(e, asset_pair_indexes, timestamp, decimals),
+        records,
+        settings::get_resolution(e) as u64,
+    )
+}
+
+// Reject updates that don't advance the last update timestamp (resubmissions and replays)
+fn panic_if_not_newer(e: &Env, timestamp: u64) {
+    if timestamp <= prices::get_last_timestamp(e) {
+        panic_with_error!(e, Error::StaleUpdate);
+    }
+}
+
+// Trim timestamp (in milliseconds) to the resolution of the given asset
+fn normalize_asset_timestamp(e: &Env, asset_index: u32, timestamp: u64) -> u64 {
+    timestamps::normalize_to(
+        timestamp,
+        assets::get_asset_resolution(e, asset_index) as u64,
+    )
+}
+
+// Run all checks for the price update, returns prices for all assets extracted from the update record
+fn validate_price_update(
+    e: &Env,
+    update: &PriceUpdate,
+    timestamp: u64,
+    backfill: bool,
+) -> Result<Vec<i128>, Error> {
+    let total = assets::load_all_assets(e).len();
+    if update.prices.len() > total {
+        return Err(Error::InvalidPricesUpdate);
+    }
+    //each updated position in the mask should have a corresponding price and vice versa,
+    //extra prices are rejected rather than silently dropped
+    let updated = (0..total)
+        .filter(|asset_index| mapping::check_period_updated(&update.mask, *asset_index))
+        .count();
+    if updated != update.prices.len() as usize {
+        return Err(Error::InvalidPricesUpdate);
+    }
+    //validate record timestamp
+    let ledger_timestamp = timestamps::ledger_timestamp(e);
+    if timestamp == 0 || !timestamps::is_valid(e, timestamp) || timestamp > ledger_timestamp {
+        return Err(Error::InvalidTimestamp);
+    }
+    //out-of-order records are accepted only in backfill mode and within the history mask range
+    let last_timestamp = prices::get_last_timestamp(e);
+    if timestamp < last_timestamp {
+        let resolution = settings::get_resolution(e) as u64;
+        if !backfill || (last_timestamp - timestamp) / resolution > 255 {
+            return Err(Error::InvalidTimestamp);
+        }
+    }
+    //extract prices for all assets from update record
+    let asset_prices = prices::extract_update_record_prices(e, update, total);
+    //a record without any price would still advance the last timestamp and skew history gaps
+    if asset_prices.iter().all(|price| price == 0) {
+        return Err(Error::ZeroPrices);
+    }
+    //purged assets keep their slots until compaction, but can't receive new prices
+    let tombstones = assets::load_tombstones(e);
+    if tombstones
+        .iter()
+        .any(|asset_index| asset_prices.get(asset_index).unwrap_or_default() > 0)
+    {
+        return Err(Error::AssetMissing);
+    }
+    //validate prices against configured bounds
+    assets::check_price_bounds(e, &asset_prices)?;
+    Ok(asset_prices)
+}
+
+// Load price for the asset from the most recent record
+fn load_last_asset_price(e: &Env, asset_index: u32) -> Option<PriceData> {
+    load_last_asset_price_with_source(e, asset_index).map(|(price, _)| price)
+}
+
+// Load price for the asset from the most recent record along with whether it has been served from the cache
+fn load_last_asset_price_with_source(e: &Env, asset_index: u32) -> Option<(PriceData, bool)> {
+    let ts = normalize_asset_timestamp(e, asset_index, prices::obtain_last_record_timestamp(e));
+    if ts == 0 {
+        return None;
+    }
+    prices::retrieve_asset_price_data_with_source(e, asset_index, ts)
+}
+
+// Load the most recent price served to the clients (falling back to the placeholder price)
+// along with whether it has been served from the cache
+fn load_served_last_price(e: &Env, asset_index: u32) -> Option<(PriceData, bool)> {
+    //thin feeds are not served
+    let price = if prices::has_min_coverage(e, asset_index) {
+        load_last_asset_price_with_source(e, asset_index)
+    } else {
+        None
+    };
+    price.or_else(|| load_placeholder_price(e, asset_index).map(|price| (price, false)))
+}
+
+// Load placeholder price for the asset that hasn't received any price updates yet
+// (zero timestamp flags the placeholder value)
+fn load_placeholder_price(e: &Env, asset_index: u32) -> Option<PriceData> {
+    let price = settings::get_placeholder_price(e)?;
+    if !assets::is_asset_unpriced(e, asset_index) {
+        return None;
+    }
+    Some(PriceData {
+        price,
+        timestamp: 0,
+    })
+}
+
+// Find the largest deviation (in basis points) of updated prices from the last known prices
+fn find_max_price_deviation(e: &Env, asset_prices: &Vec<i128>) -> i128 {
+    let mut max_deviation = 0;
+    //the last record is used as a reference even if it's no longer fresh
+    let last_timestamp = prices::get_last_timestamp(e);
+    for (asset_index, price) in asset_prices.iter().enumerate() {
+        if price == 0 {
+            continue;
+        }
+        let ts = normalize_asset_timestamp(e, asset_index as u32, last_timestamp);
+        if let Some(last_price) = prices::retrieve_asset_price_data(e, asset_index as u32, ts) {
+            max_deviation = max_deviation.max(prices::deviation_bps(price, last_price.price));
+        }
+    }
+    max_deviation
+}
+
+// Compare new prices with the most recent ones, backfilled records are not checked
+// Returns the deviation (in basis points) if it is severe enough to pause the contract
+fn check_price_deviation(
+    e: &Env,
+    asset_prices: &Vec<i128>,
+    is_backfill: bool,
+) -> Result<Option<i128>, Error> {
+    if is_backfill {
+        return Ok(None);
+    }
+    if let Some((reject_bps, pause_bps)) = settings::get_deviation_limits(e) {
+        let deviation = find_max_price_deviation(e, asset_prices);
+        if deviation > pause_bps as i128 {
+            return Ok(Some(deviation));
+        }
+        if deviation > reject_bps as i128 {
+            return Err(Error::DeviationExceeded);
+        }
+    }
+    Ok(None)
+}
+
+// Validate and store price feed history snapshot
+fn record_price_update(e: &Env, update: PriceUpdate, timestamp: u64, backfill: bool) {
+    if settings::get_paused(e) {
+        panic_with_error!(e, Error::Paused);
+    }
+    if update.prices.is_empty() {
+        return; //skip empty updates
+    }
+    let asset_prices = validate_price_update(e, &update, timestamp, backfill)
+        .unwrap_or_else(|err| panic_with_error!(&e, err));
+    let all = assets::load_all_assets(e);
+    let is_backfill = timestamp < prices::get_last_timestamp(e);
+    let severe_deviation = check_price_deviation(e, &asset_prices, is_backfill)
+        .unwrap_or_else(|err| panic_with_error!(&e, err));
+    if let Some(deviation) = severe_deviation {
+        //severe deviation - stop accepting updates until the admin intervenes
+        settings::set_paused(e, true);
+        events::publish_auto_pause_event(e, deviation, timestamp);
+        return;
+    }
+    //store history timestamps for all assets
+    if is_backfill {
+        prices::backfill_history_mask(e, &asset_prices, timestamp);
+    } else {
+        prices::update_history_mask(e, &asset_prices, timestamp);
+    }
+    //prepare and publish update event
+    events::publish_update_event(e, &asset_prices, &update.mask, &all, timestamp);
+    //store new prices
+    prices::store_prices(e, &update, timestamp, &asset_prices);
+    //assets priced for the first time no longer need placeholders
+    assets::clear_unpriced_assets(e, &asset_prices);
+}
+
 impl PriceOracleContractBase {
     // Return base asset price is reported in
     //
@@ -15,6 +222,46 @@ impl PriceOracleContractBase {
         settings::get_base_asset(e)
     }
 
+    // Return base asset contract address
+    //
+    // # Returns
+    //
+    // Base asset address or None if the base is not a Stellar asset
+    pub fn base_address(e: &Env) -> Option<Address> {
+        match settings::get_base_asset(e) {
+            Asset::Stellar(address) => Some(address),
+            Asset::Other(_) => None,
+        }
+    }
+
+    // Return base asset symbol
+    //
+    // # Returns
+    //
+    // Base asset symbol or None if the base is a Stellar asset
+    pub fn base_symbol(e: &Env) -> Option<Symbol> {
+        match settings::get_base_asset(e) {
+            Asset::Stellar(_) => None,
+            Asset::Other(symbol) => Some(symbol),
+        }
+    }
+
+    // Return unit price of the base asset, only available while the oracle is fresh
+    //
+    // # Returns
+    //
+    // Base asset price (10^decimals) at the most recent record timestamp or None if the oracle is stale
+    pub fn base_price(e: &Env) -> Option<PriceData> {
+        let timestamp = prices::obtain_last_record_timestamp(e);
+        if timestamp == 0 {
+            return None;
+        }
+        Some(PriceData {
+            price: 10i128.pow(settings::get_decimals(e)),
+            timestamp: timestamps::to_seconds(timestamp),
+        })
+    }
+
     // Return number of decimal places used to represent price for all quoted assets
     //
     // # Returns
@@ -30,7 +277,16 @@ impl PriceOracleContractBase {
     //
     // Price feed resolution (in seconds)
     pub fn resolution(e: &Env) -> u32 {
-        settings::get_resolution(e) / 1000
+        timestamps::to_seconds(settings::get_resolution(e) as u64) as u32
+    }
+
+    // Return default tick period timeframe (in milliseconds)
+    //
+    // # Returns
+    //
+    // Price feed resolution (in milliseconds), as used for history record keys
+    pub fn resolution_ms(e: &Env) -> u32 {
+        settings::get_resolution(e)
     }
 
     // Return historical records retention period (in seconds)
@@ -39,14 +295,36 @@ impl PriceOracleContractBase {
     //
     // History retention period (in seconds)
     pub fn history_retention_period(e: &Env) -> Option<u64> {
-        let period: u64 = settings::get_history_retention_period(e);
+        Self::history_retention_period_ms(e).map(timestamps::to_seconds)
+    }
+
+    // Return historical records retention period (in milliseconds)
+    //
+    // # Returns
+    //
+    // History retention period (in milliseconds)
+    pub fn history_retention_period_ms(e: &Env) -> Option<u64> {
+        let period = settings::get_history_retention_period(e);
         if period == 0 {
             None
         } else {
-            Some(period / 1000) //convert to seconds
+            Some(period)
         }
     }
 
+    // Return history retention period and whether it is enforced
+    //
+    // Retention period controls how long price records are kept in the storage,
+    // while asset expiration only reflects the price feed payment status
+    //
+    // # Returns
+    //
+    // History retention period (in seconds) and true if the retention is enforced (non-zero period)
+    pub fn retention_info(e: &Env) -> (u64, bool) {
+        let period = settings::get_history_retention_period(e);
+        (timestamps::to_seconds(period), period > 0)
+    }
+
     // Return price records cache size
     //
     // # Returns
@@ -56,347 +334,1961 @@ impl PriceOracleContractBase {
         settings::get_cache_size(e)
     }
 
-    // Return all quoted assets
+    // Return policy applied when the price records cache exceeds its size
     //
     // # Returns
     //
-    // Quoted assets
-    pub fn assets(e: &Env) -> Vec<Asset> {
-        assets::load_all_assets(e)
+    // Cache eviction policy
+    pub fn cache_eviction_policy(e: &Env) -> CacheEvictionPolicy {
+        settings::get_cache_eviction_policy(e)
     }
 
-    // Return most recent price update timestamp in seconds
+    // Return timestamps of price records held in the instance cache
     //
     // # Returns
     //
-    // Timestamp of last recorded price update
-    pub fn last_timestamp(e: &Env) -> u64 {
-        prices::get_last_timestamp(e) / 1000 //convert to seconds
+    // Cached records timestamps (in seconds), most recent first
+    pub fn cache_records(e: &Env) -> Vec<u64> {
+        let mut res = Vec::new(e);
+        for timestamp in prices::load_cached_timestamps(e) {
+            res.push_back(timestamps::to_seconds(timestamp));
+        }
+        res
     }
 
-    // Return current contract protocol version
+    // Return whether missing prices cause errors instead of None results
     //
     // # Returns
     //
-    // Contract protocol version
-    pub fn version(_e: &Env) -> u32 {
-        env!("CARGO_PKG_VERSION")
-            .split(".")
-            .next()
-            .unwrap()
-            .parse::<u32>()
-            .unwrap()
+    // Strict mode flag
+    pub fn strict_mode(e: &Env) -> bool {
+        settings::get_strict_mode(e)
     }
 
-    // Return expiration date for a given asset
+    // Return whether history records are read from the temporary storage before the cache
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Quoted asset
+    // Storage-first read strategy flag
+    pub fn storage_first(e: &Env) -> bool {
+        settings::get_storage_first(e)
+    }
+
+    // Return whether price record TTLs are extended on reads
     //
     // # Returns
     //
-    // Asset expiration timestamp or None if asset is not supported
+    // Read bump flag
+    pub fn read_bump(e: &Env) -> bool {
+        settings::get_read_bump(e)
+    }
+
+    // Return placeholder price served for newly added assets until their first price update
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if asset is not supported
-    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
-        assets::expires(e, asset)
+    // Placeholder price or None if placeholder prices are disabled
+    pub fn placeholder_price(e: &Env) -> Option<i128> {
+        settings::get_placeholder_price(e)
     }
 
-    // Extends the asset expiration date by a given amount of tokens.
+    // Return maximum allowed price value
+    //
+    // # Returns
+    //
+    // Maximum price or 0 if not limited
+    pub fn max_price(e: &Env) -> i128 {
+        settings::get_max_price(e)
+    }
+
+    // Return feeder public keys allowed to sign price attestations
+    //
+    // # Returns
+    //
+    // Feeder ed25519 public keys with the feeder addresses they are linked to
+    pub fn feeder_keys(e: &Env) -> Vec<(BytesN<32>, Address)> {
+        let mut res = Vec::new(e);
+        for (public_key, feeder) in auth::load_feeder_keys(e).iter() {
+            res.push_back((public_key, feeder));
+        }
+        res
+    }
+
+    // Return allowed feeders with the time of their last price update
+    //
+    // # Returns
+    //
+    // Feeder addresses with last activity timestamps (in seconds, zero if never submitted)
+    pub fn feeder_activity(e: &Env) -> Vec<(Address, u64)> {
+        let mut res = Vec::new(e);
+        for feeder in auth::load_feeders(e).iter() {
+            let timestamp = auth::get_feeder_activity(e, &feeder);
+            res.push_back((feeder, timestamps::to_seconds(timestamp)));
+        }
+        res
+    }
+
+    // Return timestamps of price records whose storage TTL is estimated to end within the given number of ledgers
     //
     // # Arguments
     //
-    // * `sponsor` - Address that sponsors price feed
-    // * `asset` - Quoted asset
-    // * `amount` - Amount of tokens to burn for extending the expiration date
-    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    // * `within_ledgers` - Number of ledgers to look ahead
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if the asset is not supported or if retention config is malformed/missing
-    pub fn extend_asset_ttl(
-        e: &Env,
-        sponsor: Address,
-        asset: Asset,
-        amount: i128,
-        initial_expiration_period: u32,
-    ) {
-        //check sponsor authorization
-        sponsor.require_auth();
-        assets::extend_ttl(e, sponsor, asset, amount, initial_expiration_period);
+    // Timestamps (in seconds) of the records at risk of expiration
+    pub fn records_at_risk(e: &Env, within_ledgers: u32) -> Vec<u64> {
+        let mut res = Vec::new(e);
+        for timestamp in prices::find_records_at_risk(e, within_ledgers).iter() {
+            res.push_back(timestamps::to_seconds(timestamp));
+        }
+        res
     }
 
-    // Return the fee token address daily price feed retainer fee amount
+    // Return whether price updates are paused
     //
     // # Returns
     //
-    // Fee token address and daily price feed retainer fee amount
-    pub fn fee_config(e: &Env) -> FeeConfig {
-        settings::get_fee_config(e)
+    // Paused flag
+    pub fn paused(e: &Env) -> bool {
+        settings::get_paused(e)
     }
 
-    // Return contract admin address
+    // Return price deviation limits applied to new price updates
     //
     // # Returns
     //
-    // Contract admin account address
-    pub fn admin(e: &Env) -> Option<Address> {
-        auth::get_admin(e)
+    // Reject and auto-pause thresholds (in basis points) or None if not set
+    pub fn deviation_limits(e: &Env) -> Option<(u32, u32)> {
+        settings::get_deviation_limits(e)
     }
 
-    // Returns price  for an asset at specific timestamp
+    // Return minimum number of recent updates required to serve asset prices
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Asset to quote
-    // * `timestamp` - Timestamp in seconds
+    // Required number of updates and the number of recent periods to check, or None if not set
+    pub fn min_coverage(e: &Env) -> Option<(u32, u32)> {
+        settings::get_min_coverage(e)
+    }
+
+    // Return minimum number of records TWAP requests should span
     //
     // # Returns
     //
-    // Price record for given asset at given timestamp or None if not found
-    pub fn price(e: &Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
-        //normalize timestamp
-        let ts = timestamps::normalize(e, timestamp * 1000);
-        //resolve index for the asset
-        let asset = assets::resolve_asset_index(e, &asset)?;
-        prices::retrieve_asset_price_data(e, asset, ts)
+    // Minimum TWAP window (0 if not set)
+    pub fn min_twap_records(e: &Env) -> u32 {
+        settings::get_min_twap_records(e)
     }
 
-    // Returns most recent price for an asset
+    // Return maximum number of price records returned by a single query
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Asset to quote
+    // Maximum number of records
+    pub fn max_records(e: &Env) -> u32 {
+        settings::get_max_records(e)
+    }
+
+    // Return maximum number of price snapshots accepted in a single batch
     //
     // # Returns
     //
-    // Most recent price for given asset or None if asset is not supported
-    pub fn lastprice(e: &Env, asset: Asset) -> Option<PriceData> {
-        //get the last timestamp
-        let ts = prices::obtain_last_record_timestamp(&e);
-        if ts == 0 {
-            return None;
-        }
-        //get the price
-        let asset = assets::resolve_asset_index(e, &asset)?;
-        //resolve index for the asset
-        prices::retrieve_asset_price_data(e, asset, ts)
+    // Maximum batch size
+    pub fn max_batch_size(e: &Env) -> u32 {
+        settings::get_max_batch_size(e)
     }
 
-    // Return last N price records for given asset
+    // Return all quoted assets
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Asset to quote
-    // * `records` - Number of records to return
+    // Quoted assets, purged assets are omitted even before their slots are compacted
+    pub fn assets(e: &Env) -> Vec<Asset> {
+        let tombstones = assets::load_tombstones(e);
+        let mut res = Vec::new(e);
+        for (asset_index, asset) in assets::load_all_assets(e).iter().enumerate() {
+            if !tombstones.contains(asset_index as u32) {
+                res.push_back(asset);
+            }
+        }
+        res
+    }
+
+    // Return configuration snapshot for off-chain backup
     //
     // # Returns
     //
-    // Prices for given asset or None if asset is not supported
-    pub fn prices(e: &Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
-        let asset_index = assets::resolve_asset_index(e, &asset)?; //get the asset index to avoid multiple calls
-        prices::load_prices(
-            &e,
-            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
-            records,
+    // Quoted assets, asset expiration timestamps, most recent price update timestamp (in seconds) and fee config
+    pub fn export_snapshot(e: &Env) -> (Vec<Asset>, Vec<u64>, u64, FeeConfig) {
+        (
+            assets::load_all_assets(e),
+            assets::load_expiration_records(e),
+            Self::last_timestamp(e),
+            settings::get_fee_config(e),
         )
     }
 
-    // Returns most recent cross price record for pair of assets
+    // Return assets updated in the price record at specific timestamp
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp in seconds
     //
     // # Returns
     //
-    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
-    pub fn x_last_price(e: &Env, base_asset: Asset, quote_asset: Asset) -> Option<PriceData> {
-        let timestamp = prices::obtain_last_record_timestamp(&e);
-        if timestamp == 0 {
-            return None;
+    // Assets included in the update record or empty vector if record not found
+    pub fn updated_assets_at(e: &Env, timestamp: u64) -> Vec<Asset> {
+        let ts = timestamps::normalize(e, timestamps::to_milliseconds(timestamp));
+        let mut res = Vec::new(e);
+        if let Some(record) = prices::load_history_record(e, ts) {
+            for (asset_index, asset) in assets::load_all_assets(e).iter().enumerate() {
+                if mapping::check_period_updated(&record.mask, asset_index as u32) {
+                    res.push_back(asset);
+                }
+            }
         }
-        let decimals = settings::get_decimals(e);
-        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
-        prices::load_cross_price(&e, asset_pair_indexes, timestamp, decimals)
+        res
     }
 
-    // Return cross price for pair of assets at specific timestamp
-    //
-    // # Arguments
-    //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `timestamp` - Timestamp
+    // Return assets omitted from the most recent price update
     //
     // # Returns
     //
-    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
-    pub fn x_price(
-        e: &Env,
-        base_asset: Asset,
-        quote_asset: Asset,
-        timestamp: u64,
-    ) -> Option<PriceData> {
-        //convert to milliseconds and normalize
-        let ts = timestamps::normalize(e, timestamp * 1000);
-        let decimals = settings::get_decimals(e);
-        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
-        prices::load_cross_price(e, asset_pair_indexes, ts, decimals)
+    // Assets without a price in the most recent period or empty vector if all assets were updated
+    pub fn missing_in_latest(e: &Env) -> Vec<Asset> {
+        let history_map = prices::get_history_map(e);
+        let tombstones = assets::load_tombstones(e);
+        let mut res = Vec::new(e);
+        for (asset_index, asset) in assets::load_all_assets(e).iter().enumerate() {
+            let asset_index = asset_index as u32;
+            if tombstones.contains(asset_index) {
+                continue; //removed assets are not expected in updates
+            }
+            if !mapping::check_history_updated(&history_map, asset_index, 0) {
+                res.push_back(asset);
+            }
+        }
+        res
     }
 
-    // Returns last N cross price records of for pair of assets
+    // Return the most recent timestamps that have price records for given asset
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to fetch
+    // * `asset` - Asset to quote
+    // * `limit` - Maximum number of timestamps to return (capped at 256)
     //
     // # Returns
     //
-    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
-    pub fn x_prices(
-        e: &Env,
-        base_asset: Asset,
-        quote_asset: Asset,
-        records: u32,
-    ) -> Option<Vec<PriceData>> {
-        let asset_pair_indexes = assets::resolve_asset_pair_indexes(&e, base_asset, quote_asset)?;
-        let decimals = settings::get_decimals(e);
-        prices::load_prices(
-            &e,
-            |timestamp| prices::load_cross_price(&e, asset_pair_indexes, timestamp, decimals),
-            records,
-        )
+    // Timestamps in seconds starting from the most recent one or empty vector if asset is not supported
+    pub fn asset_timestamps(e: &Env, asset: Asset, limit: u32) -> Vec<u64> {
+        match assets::resolve_asset_index(e, &asset) {
+            Some(asset_index) => prices::load_asset_timestamps(e, asset_index, limit),
+            None => Vec::new(e),
+        }
     }
 
-    // Returns time-weighted average price for given asset over N recent records
+    // Return the oldest timestamp tracked in the history that has price record for given asset
     //
     // # Arguments
     //
     // * `asset` - Asset to quote
-    // * `records` - Number of records to process
     //
     // # Returns
     //
-    // TWAP for the given asset over N recent records or None if asset is not supported
-    pub fn twap(e: &Env, asset: Asset, records: u32) -> Option<i128> {
-        let asset_index = assets::resolve_asset_index(e, &asset)?; //get the asset index to avoid multiple calls
-        prices::calculate_twap(
-            &e,
-            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
-            records,
-        )
+    // Oldest timestamp in seconds or None if there are no records or asset is not supported
+    pub fn oldest_timestamp(e: &Env, asset: Asset) -> Option<u64> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        prices::find_oldest_asset_timestamp(e, asset_index)
     }
 
-    // Returns time-weighted average cross price for given asset pair over N recent records
+    // Return the number of periods with price records for given asset within the 256 periods tracked in the history
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to process
+    // * `asset` - Asset to quote
     //
     // # Returns
     //
-    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported
-    pub fn x_twap(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> Option<i128> {
-        //get asset index to avoid multiple calls
-        let asset_pair_indexes = assets::resolve_asset_pair_indexes(&e, base_asset, quote_asset)?;
+    // Number of populated periods or 0 if asset is not supported
+    pub fn populated_depth(e: &Env, asset: Asset) -> u32 {
+        match assets::resolve_asset_index(e, &asset) {
+            Some(asset_index) => prices::count_asset_periods(e, asset_index),
+            None => 0,
+        }
+    }
+
+    // Run all checks performed on price update submission without storing the update
+    //
+    // # Arguments
+    //
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `backfill` - Validate the update as `backfill_price` submission
+    //
+    // # Returns
+    //
+    // Error that would be raised by `set_price` (or `backfill_price` in backfill mode) for this update, if any,
+    // `Paused` is also returned for updates that would pause the contract due to severe price deviation
+    pub fn validate_update(
+        e: &Env,
+        update: PriceUpdate,
+        timestamp: u64,
+        backfill: bool,
+    ) -> Result<(), Error> {
+        if settings::get_paused(e) {
+            return Err(Error::Paused);
+        }
+        if update.prices.is_empty() {
+            return Ok(()); //empty updates are skipped
+        }
+        let asset_prices = validate_price_update(e, &update, timestamp, backfill)?;
+        let is_backfill = timestamp < prices::get_last_timestamp(e);
+        if check_price_deviation(e, &asset_prices, is_backfill)?.is_some() {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+
+    // Preview the history mask after applying a hypothetical price update, without modifying the contract state
+    //
+    // # Arguments
+    //
+    // * `current` - Current history mask
+    // * `prices` - Prices for all assets, zero for assets without updates
+    //
+    // # Returns
+    //
+    // Updated history mask
+    pub fn preview_history_mask(e: &Env, current: Bytes, prices: Vec<i128>) -> Bytes {
+        mapping::update_history_mask(e, current, &prices)
+    }
+
+    // Count assets marked as updated in the price update mask
+    //
+    // # Arguments
+    //
+    // * `mask` - Price update record mask
+    //
+    // # Returns
+    //
+    // Number of set bits, expected to match the number of prices in the update
+    pub fn mask_popcount(_e: &Env, mask: Bytes) -> u32 {
+        mapping::count_updated_assets(&mask)
+    }
+
+    // Return most recent price update timestamp in seconds
+    //
+    // # Returns
+    //
+    // Timestamp of last recorded price update
+    pub fn last_timestamp(e: &Env) -> u64 {
+        timestamps::to_seconds(prices::get_last_timestamp(e))
+    }
+
+    // Return most recent price update timestamp in milliseconds
+    //
+    // # Returns
+    //
+    // Raw timestamp of last recorded price update, as used for history record keys
+    pub fn last_timestamp_ms(e: &Env) -> u64 {
+        prices::get_last_timestamp(e)
+    }
+
+    // Return time remaining until the next expected price update
+    //
+    // # Returns
+    //
+    // Seconds until the next update is due (0 if it is already overdue) or None if there is no fresh data
+    pub fn next_update_eta(e: &Env) -> Option<u64> {
+        let last_timestamp = prices::obtain_last_record_timestamp(e);
+        if last_timestamp == 0 {
+            return None;
+        }
+        let next_timestamp = last_timestamp + settings::get_resolution(e) as u64;
+        let now = timestamps::ledger_timestamp(e);
+        Some(timestamps::to_seconds(next_timestamp.saturating_sub(now)))
+    }
+
+    // Return current contract protocol version
+    //
+    // # Returns
+    //
+    // Contract protocol version
+    pub fn version(_e: &Env) -> u32 {
+        env!("CARGO_PKG_VERSION")
+            .split(".")
+            .next()
+            .unwrap()
+            .parse::<u32>()
+            .unwrap()
+    }
+
+    // Return expiration date for a given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in milliseconds) or None if asset has no expiration record
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
+        assets::expires(e, asset)
+    }
+
+    // Return expiration date for a given asset (in seconds)
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in seconds) or None if asset has no expiration record
+    //
+    // # Panics
+    //
+    // Panics if asset is not supported
+    pub fn expires_s(e: &Env, asset: Asset) -> Option<u64> {
+        assets::expires(e, asset).map(timestamps::to_seconds)
+    }
+
+    // Return expiration timestamps for the given assets
+    //
+    // # Arguments
+    //
+    // * `assets` - Quoted assets
+    //
+    // # Returns
+    //
+    // Expiration timestamps (in seconds) aligned with the input, None for unknown assets or assets without expiration record
+    pub fn expirations_of(e: &Env, assets: Vec<Asset>) -> Vec<Option<u64>> {
+        let expirations = assets::load_expiration_records(e);
+        let mut result = Vec::new(e);
+        for asset in assets.iter() {
+            let expiration = assets::resolve_asset_index(e, &asset)
+                .and_then(|asset_index| expirations.get(asset_index))
+                .filter(|expiration| *expiration > 0)
+                .map(timestamps::to_seconds);
+            result.push_back(expiration);
+        }
+        result
+    }
+
+    // Return time remaining until the asset price feed expiration
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Seconds until expiration (negative if already expired) or None if asset has no expiration record
+    pub fn ttl_remaining(e: &Env, asset: Asset) -> Option<i128> {
+        assets::resolve_asset_index(e, &asset)?;
+        //expiration is not tracked for the asset otherwise
+        let expiration = assets::expires(e, asset)?;
+        let now = timestamps::ledger_timestamp(e);
+        if expiration >= now {
+            Some(timestamps::to_seconds(expiration - now) as i128)
+        } else {
+            Some(-(timestamps::to_seconds(now - expiration) as i128))
+        }
+    }
+
+    // Return whether the asset price feed is not expired and has a price in the most recent record
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // True if the feed is active, false otherwise or if asset is not supported
+    pub fn is_feed_active(e: &Env, asset: Asset) -> bool {
+        Self::feed_summary(e, asset).2
+    }
+
+    // Return expiration, latest price, and active flag for the asset price feed
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Expiration timestamp (in seconds), most recent price, and active flag
+    pub fn feed_summary(e: &Env, asset: Asset) -> (Option<u64>, Option<PriceData>, bool) {
+        let asset_index = match assets::resolve_asset_index(e, &asset) {
+            Some(asset_index) => asset_index,
+            None => return (None, None, false),
+        };
+        let expiration = assets::expires(e, asset);
+        let price = load_last_asset_price(e, asset_index);
+        //missing expiration means that the feed expiration is not tracked
+        let expired = match expiration {
+            Some(expiration) => expiration <= timestamps::ledger_timestamp(e),
+            None => false,
+        };
+        let active = !expired && price.is_some();
+        (expiration.map(timestamps::to_seconds), price, active)
+    }
+
+    // Extends the asset expiration date by a given amount of tokens.
+    //
+    // # Arguments
+    //
+    // * `sponsor` - Address that sponsors price feed
+    // * `asset` - Quoted asset
+    // * `amount` - Amount of tokens to burn for extending the expiration date
+    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    //
+    // # Panics
+    //
+    // Panics if the asset is not supported or if retention config is malformed/missing
+    pub fn extend_asset_ttl(
+        e: &Env,
+        sponsor: Address,
+        asset: Asset,
+        amount: i128,
+        initial_expiration_period: u32,
+    ) {
+        //check sponsor authorization
+        sponsor.require_auth();
+        assets::extend_ttl(e, sponsor, asset, amount, initial_expiration_period);
+    }
+
+    // Return whether fee config has been explicitly set rather than defaulted
+    //
+    // # Returns
+    //
+    // True if fee config present in the contract storage
+    pub fn fee_config_explicitly_set(e: &Env) -> bool {
+        settings::has_fee_config(e)
+    }
+
+    // Return human-readable label for a given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset label or None if not set or asset is not supported
+    pub fn asset_label(e: &Env, asset: Asset) -> Option<Symbol> {
+        assets::get_asset_label(e, &asset)
+    }
+
+    // Return price feed resolution for a given asset (in seconds)
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset resolution override or global resolution if not set, None if asset is not supported
+    pub fn asset_resolution(e: &Env, asset: Asset) -> Option<u32> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        Some(timestamps::to_seconds(assets::get_asset_resolution(e, asset_index) as u64) as u32)
+    }
+
+    // Return minimum price of the asset accepted as a cross-price denominator
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Price floor (zero if not set) or None if asset is not supported
+    pub fn asset_price_floor(e: &Env, asset: Asset) -> Option<i128> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        Some(assets::get_asset_price_floor(e, asset_index))
+    }
+
+    // Return canonical asset the given asset is registered as an alias of
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to check
+    //
+    // # Returns
+    //
+    // Canonical asset or None if no alias is configured
+    pub fn asset_alias(e: &Env, asset: Asset) -> Option<Asset> {
+        let canonical = assets::resolve_canonical_asset(e, &asset);
+        if canonical == asset {
+            return None;
+        }
+        Some(canonical)
+    }
+
+    // Return scheduled fee config change
+    //
+    // # Returns
+    //
+    // Pending fee config and the timestamp (in seconds) it can be applied at, or None if nothing is scheduled
+    pub fn pending_fee_config(e: &Env) -> Option<(FeeConfig, u64)> {
+        let (fee_config, apply_at) = fees::get_pending_fee_config(e)?;
+        Some((fee_config, timestamps::to_seconds(apply_at)))
+    }
+
+    // Return timestamp of the last fee token migration
+    //
+    // # Returns
+    //
+    // Migration timestamp (in seconds) or None if fee token has never been migrated
+    pub fn fee_migration_timestamp(e: &Env) -> Option<u64> {
+        fees::get_fee_migration_timestamp(e).map(timestamps::to_seconds)
+    }
+
+    // Return fee split between burn and treasury transfer
+    //
+    // # Returns
+    //
+    // Burn share, treasury share (in basis points), and treasury address or None if all fees are burned
+    pub fn fee_split(e: &Env) -> Option<(u32, u32, Address)> {
+        settings::get_fee_split(e)
+    }
+
+    // Return the fee token address daily price feed retainer fee amount
+    //
+    // # Returns
+    //
+    // Fee token address and daily price feed retainer fee amount
+    pub fn fee_config(e: &Env) -> FeeConfig {
+        settings::get_fee_config(e)
+    }
+
+    // Return total amount of fee tokens burned by the contract
+    //
+    // # Returns
+    //
+    // Cumulative amount of burned fee tokens
+    pub fn total_fees_burned(e: &Env) -> i128 {
+        fees::get_total_burned(e)
+    }
+
+    // Return contract admin address
+    //
+    // # Returns
+    //
+    // Contract admin account address
+    pub fn admin(e: &Env) -> Option<Address> {
+        auth::get_admin(e)
+    }
+
+    // Returns price  for an asset at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics in strict mode if asset is not supported or price not found
+    pub fn price(e: &Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        //resolve index for the asset
+        let asset = assets::resolve_asset_index(e, &asset);
+        let asset = require_in_strict_mode(e, asset, Error::AssetMissing)?;
+        //normalize timestamp using asset resolution
+        let ts = normalize_asset_timestamp(e, asset, timestamps::to_milliseconds(timestamp));
+        let price = prices::retrieve_asset_price_data(e, asset, ts);
+        require_in_strict_mode(e, price, Error::NoPrice)
+    }
+
+    // Returns price for an asset at specific timestamp, distinguishing future-dated requests from missing data
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found,
+    // InvalidTimestamp error if the timestamp is ahead of the current ledger time,
+    // AssetMissing error if asset is not supported
+    pub fn price_checked(
+        e: &Env,
+        asset: Asset,
+        timestamp: u64,
+    ) -> Result<Option<PriceData>, Error> {
+        let asset = assets::resolve_asset_index(e, &asset).ok_or(Error::AssetMissing)?;
+        let timestamp = timestamps::to_milliseconds(timestamp);
+        //future-dated reads usually indicate clock skew on the client side
+        if timestamp > timestamps::ledger_timestamp(e) {
+            return Err(Error::InvalidTimestamp);
+        }
+        let ts = normalize_asset_timestamp(e, asset, timestamp);
+        Ok(prices::retrieve_asset_price_data(e, asset, ts))
+    }
+
+    // Returns raw stored price for an asset at specific timestamp, without timestamp conversion
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp_ms` - Timestamp in milliseconds
+    //
+    // # Returns
+    //
+    // Price exactly as stored in the record for given asset at given timestamp or None if not found
+    //
+    // # Panics
+    //
+    // Panics in strict mode if asset is not supported or price not found
+    pub fn raw_asset_price(e: &Env, asset: Asset, timestamp_ms: u64) -> Option<i128> {
+        let asset = assets::resolve_asset_index(e, &asset);
+        let asset = require_in_strict_mode(e, asset, Error::AssetMissing)?;
+        let ts = normalize_asset_timestamp(e, asset, timestamp_ms);
+        let price = prices::retrieve_asset_price_data(e, asset, ts).map(|data| data.price);
+        require_in_strict_mode(e, price, Error::NoPrice)
+    }
+
+    // Returns price for an asset at specific timestamp along with the number of periods
+    // elapsed since the record until the last price update
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record with its period offset relative to the last update timestamp or None if not found
+    pub fn price_with_age_periods(
+        e: &Env,
+        asset: Asset,
+        timestamp: u64,
+    ) -> Option<(PriceData, u32)> {
+        let price = Self::price(e, asset, timestamp)?;
+        let period =
+            prices::calculate_period_offset(e, timestamps::to_milliseconds(price.timestamp))?;
+        Some((price, period.min(u32::MAX as u64) as u32))
+    }
+
+    // Returns most recent price for an asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if asset is not supported
+    //
+    // # Panics
+    //
+    // Panics in strict mode if asset is not supported or price not found
+    pub fn lastprice(e: &Env, asset: Asset) -> Option<PriceData> {
+        //resolve index for the asset
+        let asset = assets::resolve_asset_index(e, &asset);
+        let asset = require_in_strict_mode(e, asset, Error::AssetMissing)?;
+        let price = load_served_last_price(e, asset).map(|(price, _)| price);
+        require_in_strict_mode(e, price, Error::NoPrice)
+    }
+
+    // Returns most recent price for an asset along with the record source, used for cache tuning
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price or None if not found, and whether the record has been served from the cache
+    pub fn lastprice_debug(e: &Env, asset: Asset) -> (Option<PriceData>, bool) {
+        let asset_index = match assets::resolve_asset_index(e, &asset) {
+            Some(asset_index) => asset_index,
+            None => return (None, false),
+        };
+        match load_served_last_price(e, asset_index) {
+            Some((price, cached)) => (Some(price), cached),
+            None => (None, false),
+        }
+    }
+
+    // Returns most recent price for an asset along with the reason if it is unavailable
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price, or the status explaining why the price is not available
+    pub fn lastprice_status(e: &Env, asset: Asset) -> PriceStatus {
+        let asset = match assets::resolve_asset_index(e, &asset) {
+            Some(asset) => asset,
+            None => return PriceStatus::Unsupported,
+        };
+        let ts = normalize_asset_timestamp(e, asset, prices::obtain_last_record_timestamp(e));
+        if ts == 0 {
+            //distinguish outdated oracle from the one that has never been updated
+            if prices::get_last_timestamp(e) == 0 {
+                return PriceStatus::NoData;
+            }
+            return PriceStatus::Stale;
+        }
+        match prices::retrieve_asset_price_data(e, asset, ts) {
+            Some(price) => PriceStatus::Ok(price),
+            None => PriceStatus::NoData,
+        }
+    }
+
+    // Returns most recent price for an asset if it is within allowed deviation from the reference price
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `reference_price` - Expected price
+    // * `max_deviation_bps` - Maximum allowed deviation from the reference price (in basis points)
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if asset is not supported or has no price updates yet
+    //
+    // # Panics
+    //
+    // Panics if reference price is not positive or the price deviates more than allowed
+    pub fn price_within(
+        e: &Env,
+        asset: Asset,
+        reference_price: i128,
+        max_deviation_bps: u32,
+    ) -> Option<PriceData> {
+        if reference_price <= 0 {
+            panic_with_error!(e, Error::InvalidAmount);
+        }
+        //placeholder prices (zero timestamp) are not real quotes
+        let price = Self::lastprice(e, asset).filter(|price| price.timestamp > 0)?;
+        if prices::deviation_bps(price.price, reference_price) > max_deviation_bps as i128 {
+            panic_with_error!(e, Error::DeviationExceeded);
+        }
+        Some(price)
+    }
+
+    // Estimates execution price for a hypothetical trade using a linear impact model:
+    // price * (depth + notional) / depth
+    //
+    // The oracle doesn't track liquidity, so the result is a rough model
+    // based on the caller-supplied depth rather than an observed market price
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `notional` - Trade size (positive for buys, negative for sells)
+    // * `depth_param` - Liquidity depth, notional equal to depth doubles the price
+    //
+    // # Returns
+    //
+    // Estimated execution price or None if price not found (placeholder prices are ignored) or calculation overflows
+    //
+    // # Panics
+    //
+    // Panics if depth is not positive
+    pub fn price_impact(e: &Env, asset: Asset, notional: i128, depth_param: i128) -> Option<i128> {
+        if depth_param <= 0 {
+            panic_with_error!(e, Error::InvalidAmount);
+        }
+        let price = Self::lastprice(e, asset).filter(|price| price.timestamp > 0)?;
+        let execution_price = price
+            .price
+            .checked_mul(depth_param.checked_add(notional)?)?
+            .checked_div(depth_param)?;
+        //sells can't push the price below zero
+        Some(execution_price.max(0))
+    }
+
+    // Return last N price records for given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to return
+    //
+    // # Returns
+    //
+    // Prices for given asset or None if asset is not supported
+    pub fn prices(e: &Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?; //get the asset index to avoid multiple calls
+        prices::load_prices(
+            e,
+            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
+            records,
+            assets::get_asset_resolution(e, asset_index) as u64,
+        )
+    }
+
+    // Return price records for given asset within the time range, capped by the max records setting
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    //
+    // # Returns
+    //
+    // Most recent prices within the range or None if asset is not supported
+    pub fn prices_range(
+        e: &Env,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+    ) -> Option<Vec<PriceData>> {
+        Self::prices_range_paged(e, asset, from_timestamp, to_timestamp, 0)
+            .map(|(prices, _)| prices)
+    }
+
+    // Return a page of price records for given asset within the time range
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    // * `offset` - Number of most recent records within the range to skip
+    //
+    // # Returns
+    //
+    // Prices within the range (at most max records) along with the flag indicating whether
+    // the range contains more records, or None if asset is not supported
+    pub fn prices_range_paged(
+        e: &Env,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+        offset: u32,
+    ) -> Option<(Vec<PriceData>, bool)> {
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        Some(prices::load_prices_range(
+            e,
+            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
+            timestamps::to_milliseconds(from_timestamp),
+            timestamps::to_milliseconds(to_timestamp),
+            assets::get_asset_resolution(e, asset_index) as u64,
+            offset,
+        ))
+    }
+
+    // Returns most recent cross price record for pair of assets
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
+    pub fn x_last_price(e: &Env, base_asset: Asset, quote_asset: Asset) -> Option<PriceData> {
+        let timestamp = prices::obtain_last_record_timestamp(e);
+        if timestamp == 0 {
+            return None;
+        }
+        let decimals = settings::get_decimals(e);
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
+        prices::load_cross_price(e, asset_pair_indexes, timestamp, decimals)
+    }
+
+    // Returns spread between the highest and the lowest recent cross price of the base asset
+    // against each of the quote assets
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quotes` - Quote assets
+    //
+    // # Returns
+    //
+    // Spread (in basis points of the lowest cross price) or None if less than two cross prices are available
+    pub fn basket_cross_spread(e: &Env, base_asset: Asset, quotes: Vec<Asset>) -> Option<i128> {
+        let mut lowest: Option<i128> = None;
+        let mut highest: Option<i128> = None;
+        let mut found = 0;
+        for quote_asset in quotes.iter() {
+            if let Some(cross) = Self::x_last_price(e, base_asset.clone(), quote_asset) {
+                lowest = Some(lowest.map_or(cross.price, |price| price.min(cross.price)));
+                highest = Some(highest.map_or(cross.price, |price| price.max(cross.price)));
+                found += 1;
+            }
+        }
+        if found < 2 {
+            return None;
+        }
+        Some(prices::deviation_bps(highest?, lowest?))
+    }
+
+    // Returns implied price of the oracle base asset in a weighted basket of quote assets
+    //
+    // # Arguments
+    //
+    // * `quotes` - Quote assets
+    // * `weights` - Weights of the quote assets, in the same order
+    //
+    // # Returns
+    //
+    // Weighted average of the base asset cross prices or None if any of the quotes is unavailable or weights mismatch
+    pub fn base_in_basket(e: &Env, quotes: Vec<Asset>, weights: Vec<i128>) -> Option<i128> {
+        if quotes.is_empty() || quotes.len() != weights.len() {
+            return None;
+        }
+        let timestamp = prices::obtain_last_record_timestamp(e);
+        if timestamp == 0 {
+            return None;
+        }
+        let decimals = settings::get_decimals(e);
+        let mut weighted_sum: i128 = 0;
+        let mut total_weight: i128 = 0;
+        for (quote_asset, weight) in quotes.iter().zip(weights.iter()) {
+            if weight <= 0 {
+                return None;
+            }
+            let quote_index = assets::resolve_asset_index(e, &quote_asset)?;
+            let cross = prices::load_base_cross_price(e, quote_index, timestamp, decimals)?;
+            weighted_sum = weighted_sum.checked_add(cross.price.checked_mul(weight)?)?;
+            total_weight = total_weight.checked_add(weight)?;
+        }
+        Some(weighted_sum / total_weight)
+    }
+
+    // Return cross price for pair of assets at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp
+    //
+    // # Returns
+    //
+    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
+    //
+    // # Panics
+    //
+    // Panics in strict mode if any of the assets is not supported or price not found
+    pub fn x_price(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamp: u64,
+    ) -> Option<PriceData> {
+        //convert to milliseconds and normalize
+        let ts = timestamps::normalize(e, timestamps::to_milliseconds(timestamp));
+        let decimals = settings::get_decimals(e);
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset);
+        let asset_pair_indexes =
+            require_in_strict_mode(e, asset_pair_indexes, Error::AssetMissing)?;
+        let price = prices::load_cross_price(e, asset_pair_indexes, ts, decimals);
+        require_in_strict_mode(e, price, Error::NoPrice)
+    }
+
+    // Return cross prices for pair of assets at multiple timestamps
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamps` - Timestamps (in seconds)
+    //
+    // # Returns
+    //
+    // Cross prices (base_asset_price/quote_asset_price) aligned with the input timestamps, None for missing records
+    pub fn x_prices_at(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamps: Vec<u64>,
+    ) -> Vec<Option<PriceData>> {
+        let mut res = Vec::new(e);
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset);
+        let decimals = settings::get_decimals(e);
+        for timestamp in timestamps.iter() {
+            let price = asset_pair_indexes.and_then(|asset_pair_indexes| {
+                //convert to milliseconds and normalize
+                let ts = timestamps::normalize(e, timestamps::to_milliseconds(timestamp));
+                prices::load_cross_price(e, asset_pair_indexes, ts, decimals)
+            });
+            res.push_back(price);
+        }
+        res
+    }
+
+    // Returns price of the asset denominated in another asset at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `denom` - Asset the price is expressed in
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price of the asset in denom terms (asset_price/denom_price) or None if any of the prices not found
+    pub fn price_in(e: &Env, asset: Asset, denom: Asset, timestamp: u64) -> Option<PriceData> {
+        //convert to milliseconds and normalize
+        let ts = timestamps::normalize(e, timestamps::to_milliseconds(timestamp));
+        let decimals = settings::get_decimals(e);
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, asset, denom)?;
+        prices::load_cross_price(e, asset_pair_indexes, ts, decimals)
+    }
+
+    // Return cross price for pair of assets at specific timestamp, looking up each asset price
+    // independently within the tolerance window
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp
+    // * `tolerance_periods` - Maximum number of periods to look back for each asset price
+    //
+    // # Returns
+    //
+    // Cross price (base_asset_price/quote_asset_price) timestamped with the older of two records or None if any of the prices not found within tolerance
+    pub fn x_price_tolerant(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamp: u64,
+        tolerance_periods: u32,
+    ) -> Option<PriceData> {
+        //convert to milliseconds and normalize
+        let ts = timestamps::normalize(e, timestamps::to_milliseconds(timestamp));
+        let decimals = settings::get_decimals(e);
+        let (base_index, quote_index) =
+            assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
+        if base_index == quote_index {
+            return prices::load_cross_price(e, (base_index, quote_index), ts, decimals);
+        }
+        let base_price = prices::find_asset_price_data(e, base_index, ts, tolerance_periods)?;
+        let quote_price = prices::find_asset_price_data(e, quote_index, ts, tolerance_periods)?;
+        Some(PriceData {
+            price: prices::fixed_div_floor(e, base_price.price, quote_price.price, decimals),
+            timestamp: base_price.timestamp.min(quote_price.timestamp),
+        })
+    }
+
+    // Returns last N cross price records of for pair of assets
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to fetch
+    //
+    // # Returns
+    //
+    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
+    pub fn x_prices(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<Vec<PriceData>> {
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
         let decimals = settings::get_decimals(e);
+        prices::load_prices(
+            e,
+            |timestamp| prices::load_cross_price(e, asset_pair_indexes, timestamp, decimals),
+            records,
+            settings::get_resolution(e) as u64,
+        )
+    }
+
+    // Returns time-weighted average price for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP for the given asset over N recent records or None if asset is not supported
+    pub fn twap(e: &Env, asset: Asset, records: u32) -> Option<i128> {
+        panic_if_twap_window_too_short(e, records);
+        let asset_index = assets::resolve_asset_index(e, &asset)?; //get the asset index to avoid multiple calls
+        if !prices::has_min_coverage(e, asset_index) {
+            return None; //thin feeds are not served
+        }
         prices::calculate_twap(
-            &e,
-            |timestamp| prices::load_cross_price(&e, asset_pair_indexes, timestamp, decimals),
+            e,
+            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
             records,
+            assets::get_asset_resolution(e, asset_index) as u64,
+        )
+    }
+
+    // Returns weighted composite of time-weighted average prices for given assets over N recent records
+    //
+    // # Arguments
+    //
+    // * `assets` - Index constituents
+    // * `weights` - Weights of the constituents, normalized to their sum
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // Index price or None if any of the constituents is unavailable, weights don't match assets or any of them is not positive
+    pub fn index_twap(
+        e: &Env,
+        assets: Vec<Asset>,
+        weights: Vec<i128>,
+        records: u32,
+    ) -> Option<i128> {
+        if assets.is_empty() || assets.len() != weights.len() {
+            return None;
+        }
+        let mut weighted_sum = 0i128;
+        let mut total_weight = 0i128;
+        for (asset, weight) in assets.iter().zip(weights.iter()) {
+            if weight <= 0 {
+                return None;
+            }
+            let twap = Self::twap(e, asset, records)?;
+            weighted_sum = weighted_sum.checked_add(twap.checked_mul(weight)?)?;
+            total_weight = total_weight.checked_add(weight)?;
+        }
+        Some(weighted_sum / total_weight)
+    }
+
+    // Returns weighted median price for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    // * `weights` - Weights of the records, starting from the most recent one
+    //
+    // # Returns
+    //
+    // Weighted median for the given asset over N recent records or None if asset is not supported, prices are stale or the total weight overflows
+    //
+    // # Panics
+    //
+    // Panics if weights length doesn't match the number of records, any of the weights is not positive,
+    // or the window is shorter than the minimum TWAP window
+    pub fn weighted_median(
+        e: &Env,
+        asset: Asset,
+        records: u32,
+        weights: Vec<i128>,
+    ) -> Option<i128> {
+        if weights.len() != records || weights.iter().any(|weight| weight <= 0) {
+            panic_with_error!(e, Error::InvalidAmount);
+        }
+        panic_if_twap_window_too_short(e, records);
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        prices::calculate_weighted_median(
+            e,
+            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
+            &weights,
+            assets::get_asset_resolution(e, asset_index) as u64,
         )
     }
 
-    /* Admin section */
+    // Returns deviation of the most recent price from TWAP for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // Spot price deviation from TWAP (in basis points) or None if any of them is unavailable
+    //
+    // # Panics
+    //
+    // Panics if the window is shorter than the minimum TWAP window
+    pub fn spot_vs_twap_bps(e: &Env, asset: Asset, records: u32) -> Option<i128> {
+        panic_if_twap_window_too_short(e, records);
+        let asset_index = assets::resolve_asset_index(e, &asset)?;
+        let timestamp =
+            normalize_asset_timestamp(e, asset_index, prices::obtain_last_record_timestamp(e));
+        if timestamp == 0 {
+            return None;
+        }
+        let spot = prices::retrieve_asset_price_data(e, asset_index, timestamp)?;
+        let twap = prices::calculate_twap(
+            e,
+            |timestamp| prices::retrieve_asset_price_data(e, asset_index, timestamp),
+            records,
+            assets::get_asset_resolution(e, asset_index) as u64,
+        )?;
+        if twap == 0 {
+            return None;
+        }
+        Some((spot.price - twap) * 10_000 / twap)
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported
+    pub fn x_twap(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> Option<i128> {
+        panic_if_twap_window_too_short(e, records);
+        //get asset index to avoid multiple calls
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
+        calculate_cross_twap(e, asset_pair_indexes, records)
+    }
+
+    // Return the oracle contract balance of the configured fee token
+    //
+    // # Returns
+    //
+    // Fee token balance or 0 if fee config is not set
+    pub fn fee_balance(e: &Env) -> i128 {
+        fees::get_fee_balance(e)
+    }
+
+    // Check whether both assets of the pair are supported by the oracle
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // True if both assets are supported
+    pub fn pair_supported(e: &Env, base_asset: Asset, quote_asset: Asset) -> bool {
+        assets::resolve_asset_pair_indexes(e, base_asset, quote_asset).is_some()
+    }
+
+    // Check whether time-weighted average cross price for given asset pair over N recent records is available
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // True if both assets have fresh records for the entire window
+    // (false for windows shorter than the minimum TWAP window)
+    pub fn x_twap_available(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> bool {
+        match assets::resolve_asset_pair_indexes(e, base_asset, quote_asset) {
+            Some(asset_pair_indexes) => {
+                prices::has_fresh_cross_records(e, asset_pair_indexes, records)
+            }
+            None => false,
+        }
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records,
+    // ensuring that both assets have fresh records over the entire window
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported or any of them is stale
+    pub fn x_twap_checked(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<i128> {
+        panic_if_twap_window_too_short(e, records);
+        let asset_pair_indexes = assets::resolve_asset_pair_indexes(e, base_asset, quote_asset)?;
+        //check each leg individually before averaging the cross price
+        if !prices::has_fresh_cross_records(e, asset_pair_indexes, records) {
+            return None;
+        }
+        calculate_cross_twap(e, asset_pair_indexes, records)
+    }
+
+    /* Admin section */
+
+    // Initializes contract configuration
+    // Requires admin authorization
+    // # Arguments
+    //
+    // * `admin` - Admin address
+    // * `base` - Base asset
+    // * `decimals` - Number of decimals for price records
+    // * `resolution` - History timeframe resolution (in milliseconds, whole seconds only)
+    // * `history_retention_period` - Price history retention period (in milliseconds)
+    // * `cache_size` - Number of rounds held in instance cache
+    // * `fee_config` - Contract retention config
+    // * `assets` - Initial list of supported assets
+    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    //
+    // # Panics
+    //
+    // Panics if not authorized, if contract is already initialized, assets list is empty, resolution is not a whole number of seconds, or retention period is too short
+    pub fn config(e: &Env, config: ConfigData, initial_expiration_period: u32) {
+        //do not allow to configure more than once, checked before any other step
+        if settings::is_initialized(e) {
+            panic_with_error!(e, Error::AlreadyInitialized);
+        }
+        //should be invoked by admin
+        config.admin.require_auth();
+        //oracle without assets has nothing to quote
+        if config.assets.is_empty() {
+            panic_with_error!(e, Error::NoAssets);
+        }
+        //resolution is exposed in seconds, so it should be convertible without truncation
+        let resolution = config.resolution as u64;
+        if resolution == 0 || !timestamps::is_whole_seconds(resolution) {
+            panic_with_error!(e, Error::InvalidAmount);
+        }
+        //records should outlive the minimum TTL extension period
+        if !prices::is_valid_retention_period(config.history_retention_period) {
+            panic_with_error!(e, Error::RetentionPeriodTooShort);
+        }
+        //apply settings
+        settings::init(
+            e,
+            &config.base_asset,
+            config.decimals,
+            config.resolution,
+            config.history_retention_period,
+            config.cache_size,
+            &config.fee_config,
+        );
+        auth::set_admin(e, &config.admin);
+        protocol::set_protocol_version(e, protocol::CURRENT_PROTOCOL);
+        //add initial assets
+        assets::add_assets(e, config.assets, initial_expiration_period);
+        //mark as initialized only after all steps succeeded
+        settings::set_initialized(e);
+    }
+
+    // Update contract cache size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `cache_size` - New cache size (number of rounds stored in cache)
+    //
+    // Growing the cache doesn't populate it with older records, use `warm_cache` for that
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_cache_size(e: &Env, cache_size: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_cache_size(e, cache_size);
+    }
+
+    // Sets policy applied when the price records cache exceeds its size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `policy` - Cache eviction policy, `Strided` keeps records aligned to the given number of rounds
+    //
+    // # Panics
+    //
+    // Panics if not authorized or stride is less than 2 rounds
+    pub fn set_cache_eviction_policy(e: &Env, policy: CacheEvictionPolicy) {
+        auth::panic_if_not_admin(e);
+        settings::set_cache_eviction_policy(e, &policy);
+    }
+
+    // Rewrite legacy (protocol 1) price records in the current format, so they are served without the legacy read path
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the legacy records to migrate (in seconds)
+    //
+    // # Returns
+    //
+    // Number of migrated records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the number of timestamps exceeds the max batch size
+    pub fn migrate_v1_records(e: &Env, timestamps: Vec<u64>) -> u32 {
+        auth::panic_if_not_admin(e);
+        if timestamps.len() > settings::get_max_batch_size(e) {
+            panic_with_error!(e, Error::BatchSizeExceeded);
+        }
+        let total = assets::load_all_assets(e).len();
+        let mut migrated = 0;
+        for timestamp in timestamps.iter() {
+            if prices::migrate_price_v1(e, timestamps::to_milliseconds(timestamp), total) {
+                migrated += 1;
+            }
+        }
+        migrated
+    }
+
+    // Load price records for given timestamps from the temporary storage into the instance cache
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the records to cache (in seconds)
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn warm_cache(e: &Env, timestamps: Vec<u64>) {
+        auth::panic_if_not_admin(e);
+        let mut ts = Vec::new(e);
+        for timestamp in timestamps.iter() {
+            ts.push_back(timestamps::to_milliseconds(timestamp));
+        }
+        prices::warm_cache(e, &ts);
+    }
+
+    // Remove all cached price records, subsequent reads fall back to the temporary storage
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn clear_cache(e: &Env) {
+        auth::panic_if_not_admin(e);
+        prices::clear_cache(e);
+    }
+
+    // Toggle strict mode, when enabled `price`, `lastprice`, and `x_price` panic instead of returning None
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `strict` - Whether strict mode is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_strict_mode(e: &Env, strict: bool) {
+        auth::panic_if_not_admin(e);
+        settings::set_strict_mode(e, strict);
+    }
+
+    // Toggle TTL extension on reads, when enabled every read extends TTL of the price record
+    // up to the history retention period (reads become more expensive)
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `read_bump` - Whether TTL extension on reads is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_read_bump(e: &Env, read_bump: bool) {
+        auth::panic_if_not_admin(e);
+        settings::set_read_bump(e, read_bump);
+    }
+
+    // Toggle read strategy, when enabled history records are loaded from the temporary storage first,
+    // and the cache is consulted only for records missing there
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `storage_first` - Whether storage-first read strategy is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_storage_first(e: &Env, storage_first: bool) {
+        auth::panic_if_not_admin(e);
+        settings::set_storage_first(e, storage_first);
+    }
+
+    // Adds given assets to the contract quoted assets list
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `assets` - Assets to add
+    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    //
+    // # Panics
+    //
+    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
+    pub fn add_assets(e: &Env, assets: Vec<Asset>, initial_expiration_period: u32) {
+        auth::panic_if_not_admin(e);
+        assets::add_assets(e, assets, initial_expiration_period);
+    }
+
+    // Removes assets expired more than the grace period ago
+    // Removed asset slots are kept as tombstones, so positions of other assets don't change
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `grace_days` - Grace period after expiration (in days)
+    //
+    // # Returns
+    //
+    // Number of removed assets
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn purge_expired(e: &Env, grace_days: u32) -> u32 {
+        auth::panic_if_not_admin(e);
+        assets::purge_expired(e, grace_days)
+    }
+
+    // Compacts live assets to contiguous indexes, removing tombstones of purged assets
+    // Asset indexes, expiration records, per-asset settings, history mask and recent price records are rewritten together
+    // Requires admin authorization
+    //
+    // # Returns
+    //
+    // Number of removed asset slots
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn reindex_assets(e: &Env) -> u32 {
+        auth::panic_if_not_admin(e);
+        let total = assets::load_all_assets(e).len();
+        if assets::load_tombstones(e).is_empty() {
+            return 0;
+        }
+        let live = assets::reindex_assets(e);
+        prices::reindex_history(e, &live, total);
+        total - live.len()
+    }
+
+    // Sets human-readable label for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `label` - Asset label
+    //
+    // # Panics
+    //
+    // Panics if not authorized or asset is not supported
+    pub fn set_asset_label(e: &Env, asset: Asset, label: Symbol) {
+        auth::panic_if_not_admin(e);
+        assets::set_asset_label(e, asset, label);
+    }
+
+    // Sets human-readable labels for multiple assets at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `entries` - Quoted assets with their labels
+    //
+    // # Panics
+    //
+    // Panics if not authorized or any of the assets is not supported
+    pub fn set_asset_labels(e: &Env, entries: Vec<(Asset, Symbol)>) {
+        auth::panic_if_not_admin(e);
+        assets::set_asset_labels(e, entries);
+    }
+
+    // Sets price feed resolution override for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `resolution` - Asset resolution (in seconds), multiple of the global resolution or zero to reset
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or resolution is not aligned with the global resolution
+    pub fn set_asset_resolution(e: &Env, asset: Asset, resolution: u32) {
+        auth::panic_if_not_admin(e);
+        let resolution = u32::try_from(timestamps::to_milliseconds(resolution as u64))
+            .unwrap_or_else(|_| panic_with_error!(e, Error::InvalidAmount));
+        assets::set_asset_resolution(e, asset, resolution);
+    }
+
+    // Sets minimum price of the asset accepted as a cross-price denominator
+    // Cross prices quoted in the asset with a price below the floor are not returned
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `floor` - Minimum denominator price, zero to remove the floor
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or floor is negative
+    pub fn set_asset_price_floor(e: &Env, asset: Asset, floor: i128) {
+        auth::panic_if_not_admin(e);
+        assets::set_asset_price_floor(e, asset, floor);
+    }
+
+    // Registers an alias for the canonical asset, preventing both forms from being listed at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `alias` - Alternative form of the asset
+    // * `canonical` - Canonical form of the asset, passing the alias itself removes the mapping
+    //
+    // # Panics
+    //
+    // Panics if not authorized, canonical asset is an alias itself, or alias is a canonical asset of another alias
+    pub fn set_asset_alias(e: &Env, alias: Asset, canonical: Asset) {
+        auth::panic_if_not_admin(e);
+        assets::set_asset_alias(e, alias, canonical);
+    }
+
+    // Sets sanity bounds for the asset prices, updates outside of bounds are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `min` - Minimum allowed price
+    // * `max` - Maximum allowed price
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported, or bounds are invalid
+    pub fn set_price_bounds(e: &Env, asset: Asset, min: i128, max: i128) {
+        auth::panic_if_not_admin(e);
+        assets::set_price_bounds(e, asset, min, max);
+    }
 
-    // Initializes contract configuration
+    // Sets placeholder price served by `lastprice` for newly added assets until their first price update,
+    // placeholder records are flagged with zero timestamp
+    // Only assets added while placeholder prices are enabled are affected
     // Requires admin authorization
+    //
     // # Arguments
     //
-    // * `admin` - Admin address
-    // * `base` - Base asset
-    // * `decimals` - Number of decimals for price records
-    // * `resolution` - History timeframe resolution (in seconds)
-    // * `history_retention_period` - Price history retention period (in seconds)
-    // * `cache_size` - Number of rounds held in instance cache
-    // * `fee_config` - Contract retention config
-    // * `assets` - Initial list of supported assets
-    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    // * `placeholder_price` - Placeholder price (0 disables placeholder prices)
     //
     // # Panics
     //
-    // Panics if not authorized or if contract is already initialized
-    pub fn config(e: &Env, config: ConfigData, initial_expiration_period: u32) {
-        //should be invoked by admin
-        config.admin.require_auth();
-        //apply settings
-        settings::init(
-            e,
-            &config.base_asset,
-            config.decimals,
-            config.resolution,
-            config.history_retention_period,
-            config.cache_size,
-            &config.fee_config,
-        );
-        auth::set_admin(e, &config.admin);
-        protocol::set_protocol_version(e, protocol::CURRENT_PROTOCOL);
-        //add initial assets
-        assets::add_assets(&e, config.assets, initial_expiration_period);
+    // Panics if not authorized or placeholder price is negative
+    pub fn set_placeholder_price(e: &Env, placeholder_price: i128) {
+        auth::panic_if_not_admin(e);
+        settings::set_placeholder_price(e, placeholder_price);
     }
 
-    // Update contract cache size
+    // Sets maximum allowed price value, updates containing higher prices are rejected
     // Requires admin authorization
     //
     // # Arguments
     //
-    // * `cache_size` - New cache size (number of rounds stored in cache)
+    // * `max_price` - Maximum price (0 removes the limit)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max price is negative
+    pub fn set_max_price(e: &Env, max_price: i128) {
+        auth::panic_if_not_admin(e);
+        settings::set_max_price(e, max_price);
+    }
+
+    // Adds feeder account allowed to submit price updates
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
     //
     // # Panics
     //
     // Panics if not authorized
-    pub fn set_cache_size(e: &Env, cache_size: u32) {
+    pub fn add_feeder(e: &Env, feeder: Address) {
         auth::panic_if_not_admin(e);
-        settings::set_cache_size(e, cache_size);
+        auth::add_feeder(e, &feeder);
     }
 
-    // Adds given assets to the contract quoted assets list
+    // Removes feeder account from the allowed feeders
     // Requires admin authorization
     //
     // # Arguments
     //
-    // * `assets` - Assets to add
-    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    // * `feeder` - Feeder account address
     //
     // # Panics
     //
-    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
-    pub fn add_assets(e: &Env, assets: Vec<Asset>, initial_expiration_period: u32) {
+    // Panics if not authorized
+    pub fn remove_feeder(e: &Env, feeder: Address) {
         auth::panic_if_not_admin(e);
-        assets::add_assets(&e, assets, initial_expiration_period);
+        auth::remove_feeder(e, &feeder);
     }
 
-    // Sets history retention period for the prices
+    // Adds feeder public key allowed to sign price attestations on behalf of the feeder
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Allowed feeder account the key belongs to
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the feeder is not allowed
+    pub fn add_feeder_key(e: &Env, feeder: Address, public_key: BytesN<32>) {
+        auth::panic_if_not_admin(e);
+        auth::add_feeder_key(e, &feeder, &public_key);
+    }
+
+    // Removes feeder public key from the allowed attestation signers
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn remove_feeder_key(e: &Env, public_key: BytesN<32>) {
+        auth::panic_if_not_admin(e);
+        auth::remove_feeder_key(e, &public_key);
+    }
+
+    // Pauses price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn pause(e: &Env) {
+        auth::panic_if_not_admin(e);
+        settings::set_paused(e, true);
+    }
+
+    // Resumes price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn unpause(e: &Env) {
+        auth::panic_if_not_admin(e);
+        settings::set_paused(e, false);
+    }
+
+    // Sets price deviation limits applied to new price updates
+    // Updates deviating from the last prices more than the reject threshold are rejected,
+    // while updates exceeding the severe threshold pause the contract
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `reject_bps` - Reject threshold (in basis points)
+    // * `pause_bps` - Auto-pause threshold (in basis points), greater than the reject threshold
+    //
+    // # Panics
+    //
+    // Panics if not authorized or thresholds are invalid
+    pub fn set_deviation_limits(e: &Env, reject_bps: u32, pause_bps: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_deviation_limits(e, reject_bps, pause_bps);
+    }
+
+    // Sets minimum number of updates within recent periods required for the asset before
+    // `lastprice` and `twap` return its prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `min_updates` - Required number of updates, zero along with zero periods removes the guard
+    // * `periods` - Number of recent periods to check (up to 256)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or coverage parameters are invalid
+    pub fn set_min_coverage(e: &Env, min_updates: u32, periods: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_min_coverage(e, min_updates, periods);
+    }
+
+    // Sets minimum number of records TWAP requests should span, shorter windows are rejected
     // Requires admin authorization
     //
     // # Arguments
     //
-    // * `period` - History retention period (in seconds)
+    // * `min_records` - Minimum TWAP window, zero disables the check
     //
     // # Panics
     //
     // Panics if not authorized
+    pub fn set_min_twap_records(e: &Env, min_records: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_min_twap_records(e, min_records);
+    }
+
+    // Sets maximum number of price snapshots accepted in a single batch
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_batch_size` - Maximum batch size
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max batch size is zero
+    pub fn set_max_batch_size(e: &Env, max_batch_size: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_max_batch_size(e, max_batch_size);
+    }
+
+    // Sets maximum number of price records returned by a single query
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_records` - Maximum number of records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max records is zero
+    pub fn set_max_records(e: &Env, max_records: u32) {
+        auth::panic_if_not_admin(e);
+        settings::set_max_records(e, max_records);
+    }
+
+    // Sets history retention period for the prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `period` - History retention period (in milliseconds), zero disables the retention
+    //
+    // # Panics
+    //
+    // Panics if not authorized or if the period is too short to extend records TTL beyond the minimum
     pub fn set_history_retention_period(e: &Env, period: u64) {
         auth::panic_if_not_admin(e);
+        if !prices::is_valid_retention_period(period) {
+            panic_with_error!(e, Error::RetentionPeriodTooShort);
+        }
         settings::set_history_retention_period(e, period);
     }
 
@@ -417,6 +2309,73 @@ impl PriceOracleContractBase {
         assets::init_expiration_config(e, initial_expiration_period);
     }
 
+    // Schedule fee token address and daily price feed retainer fee amount change
+    // The change takes effect only after it is applied once the timelock passes
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    // * `apply_at` - Timestamp (in seconds) after which the change can be applied
+    //
+    // # Panics
+    //
+    // Panics if not authorized or apply timestamp is not in the future
+    pub fn schedule_fee_config(e: &Env, fee_config: FeeConfig, apply_at: u64) {
+        auth::panic_if_not_admin(e);
+        fees::schedule_fee_config(e, &fee_config, timestamps::to_milliseconds(apply_at));
+    }
+
+    // Apply scheduled fee config change if its timelock has passed
+    //
+    // # Arguments
+    //
+    // * `initial_expiration_period` - Initial expiration period for new assets (in days)
+    //
+    // # Returns
+    //
+    // True if the scheduled change has been applied
+    pub fn apply_pending_fee_config(e: &Env, initial_expiration_period: u32) -> bool {
+        if fees::apply_pending_fee_config(e).is_none() {
+            return false;
+        }
+        assets::init_expiration_config(e, initial_expiration_period);
+        true
+    }
+
+    // Switch fee token and daily price feed retainer fee amount, keeping existing expirations intact
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `new_token` - New fee token address
+    // * `new_rate` - Daily price feed retainer fee amount in new tokens
+    //
+    // # Panics
+    //
+    // Panics if not authorized or rate is not positive
+    pub fn migrate_fee_token(e: &Env, new_token: Address, new_rate: i128) {
+        auth::panic_if_not_admin(e);
+        fees::migrate_fee_token(e, &new_token, new_rate);
+    }
+
+    // Sets fee split between burn and treasury transfer applied to all charged fees
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `burn_bps` - Share of the fee to burn (in basis points)
+    // * `treasury_bps` - Share of the fee to transfer to the treasury (in basis points)
+    // * `treasury` - Treasury address
+    //
+    // # Panics
+    //
+    // Panics if not authorized or shares don't sum up to 10000
+    pub fn set_fee_split(e: &Env, burn_bps: u32, treasury_bps: u32, treasury: Address) {
+        auth::panic_if_not_admin(e);
+        settings::set_fee_split(e, burn_bps, treasury_bps, &treasury);
+    }
+
     // Record new price feed history snapshot
     // Requires admin authorization
     //
@@ -424,32 +2383,156 @@ impl PriceOracleContractBase {
     //
     // * `updates` - Price feed snapshot
     // * `timestamp` - History snapshot timestamp
+    // * `backfill` - Whether the snapshot is allowed to be older than the last update
     //
     // # Panics
     //
     // Panics if not authorized or price snapshot record is invalid
-    pub fn set_price(e: &Env, update: PriceUpdate, timestamp: u64) {
+    pub fn set_price(e: &Env, update: PriceUpdate, timestamp: u64, backfill: bool) {
+        auth::panic_if_not_admin(e);
+        record_price_update(e, update, timestamp, backfill);
+    }
+
+    // Record new price feed history snapshot on behalf of an allowed feeder
+    // Requires feeder authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    //
+    // # Panics
+    //
+    // Panics if not authorized, feeder is not allowed, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_by_feeder(e: &Env, feeder: Address, update: PriceUpdate, timestamp: u64) {
+        auth::panic_if_not_feeder(e, &feeder);
+        //feeders can't overwrite already recorded snapshots
+        panic_if_not_newer(e, timestamp);
+        record_price_update(e, update, timestamp, false);
+    }
+
+    // Record new price feed history snapshot signed off-chain by an allowed feeder key
+    // Can be submitted by any account (e.g. a relayer), the activity is recorded for the linked feeder
+    //
+    // # Arguments
+    //
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `signature` - Ed25519 signature of the XDR-encoded (contract address, update, timestamp) tuple
+    // * `signer` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if signer is not allowed, signature is invalid, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_attested(
+        e: &Env,
+        update: PriceUpdate,
+        timestamp: u64,
+        signature: BytesN<64>,
+        signer: BytesN<32>,
+    ) {
+        //bind the payload to this contract to prevent replays on other oracles
+        let payload = (e.current_contract_address(), update.clone(), timestamp).to_xdr(e);
+        auth::panic_if_not_attested(e, &signer, &payload, &signature);
+        //signed payloads can be replayed by anyone, so only newer snapshots are accepted
+        panic_if_not_newer(e, timestamp);
+        record_price_update(e, update, timestamp, false);
+    }
+
+    // Record new price feed history snapshot only if the last update timestamp matches the expected one
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `expected_last_timestamp` - Expected timestamp of the most recent update (in seconds), as returned by `last_timestamp`
+    //
+    // # Panics
+    //
+    // Panics if not authorized, last update timestamp doesn't match the expected value, or price snapshot record is invalid
+    pub fn set_price_cas(
+        e: &Env,
+        update: PriceUpdate,
+        timestamp: u64,
+        expected_last_timestamp: u64,
+    ) {
+        auth::panic_if_not_admin(e);
+        if timestamps::to_seconds(prices::get_last_timestamp(e)) != expected_last_timestamp {
+            panic_with_error!(e, Error::StaleUpdate);
+        }
+        record_price_update(e, update, timestamp, false);
+    }
+
+    // Overwrites prices of multiple assets in the existing price record
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamp` - Timestamp of the record to correct (in seconds)
+    // * `corrections` - Assets with corrected prices
+    //
+    // # Panics
+    //
+    // Panics if not authorized, record not found, any of the assets is not supported or has no price in the record,
+    // or corrected price is invalid
+    pub fn correct_prices(e: &Env, timestamp: u64, corrections: Vec<(Asset, i128)>) {
         auth::panic_if_not_admin(e);
-        if update.prices.len() == 0 {
-            return; //skip empty updates
+        let ts = timestamps::to_milliseconds(timestamp);
+        if ts == 0 || !timestamps::is_valid(e, ts) {
+            panic_with_error!(e, Error::InvalidTimestamp);
+        }
+        let record = match prices::load_history_record(e, ts) {
+            Some(record) => record,
+            None => panic_with_error!(e, Error::NoPrice),
+        };
+        let total = assets::load_all_assets(e).len();
+        let mut corrected = prices::extract_update_record_prices(e, &record, total);
+        //validate all corrections before rewriting the record
+        for (asset, price) in corrections.iter() {
+            let asset_index = match assets::resolve_asset_index(e, &asset) {
+                Some(asset_index) => asset_index,
+                None => panic_with_error!(e, Error::AssetMissing),
+            };
+            if !mapping::check_period_updated(&record.mask, asset_index) {
+                panic_with_error!(e, Error::NoPrice);
+            }
+            if price <= 0 {
+                panic_with_error!(e, Error::InvalidAmount);
+            }
+            corrected.set(asset_index, price);
         }
-        if update.prices.len() > assets::load_all_assets(e).len() {
-            panic_with_error!(&e, Error::InvalidPricesUpdate);
+        assets::check_price_bounds(e, &corrected).unwrap_or_else(|err| panic_with_error!(e, err));
+        prices::correct_record(e, ts, &corrected);
+    }
+
+    // Record multiple price feed history snapshots in a single invocation
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshots with corresponding history snapshot timestamps
+    //
+    // Snapshot triggering the auto-pause pauses the contract, remaining snapshots are skipped
+    //
+    // # Panics
+    //
+    // Panics if not authorized, batch size exceeds the limit, or any price snapshot record is invalid
+    pub fn set_prices_batch(e: &Env, updates: Vec<(PriceUpdate, u64)>) {
+        auth::panic_if_not_admin(e);
+        if updates.len() > settings::get_max_batch_size(e) {
+            panic_with_error!(e, Error::BatchSizeExceeded);
         }
-        //validate record timestamp
-        let ledger_timestamp = timestamps::ledger_timestamp(&e);
-        if timestamp == 0 || !timestamps::is_valid(e, timestamp) || timestamp > ledger_timestamp {
-            panic_with_error!(&e, Error::InvalidTimestamp);
+        for (update, timestamp) in updates.iter() {
+            record_price_update(e, update, timestamp, false);
+            //remaining updates would be rejected and roll back the auto-pause, so stop here
+            if settings::get_paused(e) {
+                return;
+            }
         }
-        //extract prices for all assets from update record
-        let all = assets::load_all_assets(e);
-        let asset_prices = prices::extract_update_record_prices(e, &update, all.len());
-        //store history timestamps for all assets
-        prices::update_history_mask(e, &asset_prices, timestamp);
-        //prepare and publish update event
-        events::publish_update_event(e, &asset_prices, &all, timestamp);
-        //store new prices
-        prices::store_prices(e, &update, timestamp, &asset_prices);
     }
 
     // Update contract source code
@@ -1,22 +1,25 @@
-use crate::types::{PriceData, PriceUpdate};
-use crate::{mapping, protocol, settings, timestamps};
-use soroban_sdk::{Bytes, Env, Vec};
+use crate::types::{CacheEvictionPolicy, Error, PriceData, PriceUpdate};
+use crate::{assets, mapping, protocol, settings, timestamps};
+use soroban_sdk::{panic_with_error, Bytes, Env, Vec};
 
 const CACHE_KEY: &str = "cache";
 const LAST_TIMESTAMP_KEY: &str = "last_timestamp";
 const HISTORY_KEY: &str = "history";
+const SINGLE_ASSET_PRICE_KEY: &str = "single";
+//minimum TTL (in ledgers) of the temporary entries
+const MIN_RECORD_TTL: u32 = 16;
 
 fn normalize_price_data(price: i128, timestamp: u64) -> PriceData {
     PriceData {
         price,
-        timestamp: timestamp / 1000, //convert to seconds
+        timestamp: timestamps::to_seconds(timestamp),
     }
 }
 
 // Get last known record timestamp
 pub fn obtain_last_record_timestamp(e: &Env) -> u64 {
     let last_timestamp = get_last_timestamp(e);
-    let ledger_timestamp = timestamps::ledger_timestamp(&e);
+    let ledger_timestamp = timestamps::ledger_timestamp(e);
     let resolution = settings::get_resolution(e) as u64;
     if last_timestamp == 0 //no prices yet
         || last_timestamp > ledger_timestamp //last timestamp is in the future
@@ -30,36 +33,111 @@ pub fn obtain_last_record_timestamp(e: &Env) -> u64 {
 
 // Retrieve price from record for specific asset
 pub fn retrieve_asset_price_data(e: &Env, asset: u32, timestamp: u64) -> Option<PriceData> {
-    //if protocol version < 2, use legacy method
-    if !protocol::at_latest_protocol_version(e) {
-        let price = get_price_v1(e, asset as u8, timestamp)?;
-        return Some(normalize_price_data(price, timestamp));
-    }
-    let last = get_last_timestamp(e);
-    //get the timestamp index in the bitmask
-    if last < timestamp {
-        return None;
+    retrieve_asset_price_data_with_source(e, asset, timestamp).map(|(price, _)| price)
+}
+
+// Retrieve price from record for specific asset along with the flag indicating whether
+// the record has been served from the instance cache rather than the temporary storage
+pub fn retrieve_asset_price_data_with_source(
+    e: &Env,
+    asset: u32,
+    timestamp: u64,
+) -> Option<(PriceData, bool)> {
+    //if protocol version < 2, try legacy method first (upgrade is scheduled only on price updates)
+    if !protocol::is_latest_protocol_version(e) {
+        return retrieve_asset_price_data_v1(e, asset, timestamp)
+            .map(|price| (price, false))
+            .or_else(|| retrieve_asset_price_data_v2(e, asset, timestamp));
     }
-    let mut period = 0;
-    if last > timestamp {
-        period = (last - timestamp) / settings::get_resolution(e) as u64;
+    //records written before the protocol upgrade may be available only in legacy format
+    retrieve_asset_price_data_v2(e, asset, timestamp).or_else(|| {
+        if protocol::has_legacy_records(e, timestamp) {
+            return retrieve_asset_price_data_v1(e, asset, timestamp).map(|price| (price, false));
+        }
+        None
+    })
+}
+
+// Retrieve price from legacy per-asset record
+fn retrieve_asset_price_data_v1(e: &Env, asset: u32, timestamp: u64) -> Option<PriceData> {
+    if asset > u8::MAX as u32 {
+        return None; //legacy records support only 256 assets
     }
+    let price = get_price_v1(e, asset as u8, timestamp)?;
+    Some(normalize_price_data(price, timestamp))
+}
+
+// Retrieve price from the update record using the history mask
+fn retrieve_asset_price_data_v2(e: &Env, asset: u32, timestamp: u64) -> Option<(PriceData, bool)> {
+    //get the timestamp index in the bitmask
+    let period = calculate_period_offset(e, timestamp)?;
     if period > 255 {
         return None; //we cannot track more than 256 updates in the bitmask
     }
-    if !has_price(e, asset, period as u32) {
+    let history_map = get_history_map(e);
+    if !mapping::check_history_updated(&history_map, asset, period as u32) {
         return None; //no price record
     }
+    //keep frequently read records alive
+    if settings::get_read_bump(e) {
+        extend_record_ttl(e, timestamp);
+    }
+    //single-asset oracles store bare prices without update records
+    let single_asset_layout = settings::get_single_asset_layout(e);
+    if single_asset_layout {
+        if let Some(price) = load_single_asset_price(e, timestamp) {
+            return Some((normalize_price_data(price, timestamp), false));
+        }
+    }
     //load the prices for the timestamp
-    let record = load_history_record(e, timestamp)?;
-    //get price for the asset index
-    let price = extract_single_update_record_price(&record, asset);
-    Some(normalize_price_data(price, timestamp))
+    match load_history_record_with_source(e, timestamp) {
+        //get price for the asset index
+        Some((record, cached)) => {
+            let price = extract_single_update_record_price(&record, asset);
+            Some((normalize_price_data(price, timestamp), cached))
+        }
+        //records written before more assets were added keep the single-asset layout,
+        //which holds only the price of the first asset
+        None if !single_asset_layout && asset == 0 => load_single_asset_price(e, timestamp)
+            .map(|price| (normalize_price_data(price, timestamp), false)),
+        None => None,
+    }
+}
+
+// Calculate number of periods between the timestamp and the last price update timestamp
+pub fn calculate_period_offset(e: &Env, timestamp: u64) -> Option<u64> {
+    let last = get_last_timestamp(e);
+    if last < timestamp {
+        return None; //timestamp is newer than the last update
+    }
+    Some((last - timestamp) / settings::get_resolution(e) as u64)
+}
+
+// Find the most recent price record for specific asset within the tolerance window preceding the timestamp
+pub fn find_asset_price_data(
+    e: &Env,
+    asset: u32,
+    mut timestamp: u64,
+    tolerance_periods: u32,
+) -> Option<PriceData> {
+    let resolution = settings::get_resolution(e) as u64;
+    //no reason to look beyond the history mask range
+    for _ in 0..=tolerance_periods.min(255) {
+        if let Some(price) = retrieve_asset_price_data(e, asset, timestamp) {
+            return Some(price);
+        }
+        if timestamp < resolution {
+            break;
+        }
+        timestamp -= resolution;
+    }
+    None
 }
 
 // Extract prices for all assets from update record
+// Update record is expected to contain exactly one price per each position set in the mask
 pub fn extract_update_record_prices(e: &Env, update: &PriceUpdate, total: u32) -> Vec<i128> {
-    let mut res = Vec::new(&e);
+    let mut res = Vec::new(e);
     let mut update_index = 0;
     for asset_index in 0..total {
         let mut price = 0;
@@ -101,19 +179,35 @@ pub fn set_last_timestamp(e: &Env, timestamp: u64) {
 }
 
 // Load history mask containing the map of all periods that had price updates
-fn get_history_map(e: &Env) -> Bytes {
+pub fn get_history_map(e: &Env) -> Bytes {
     e.storage()
         .instance()
         .get(&HISTORY_KEY)
         .unwrap_or_else(|| Bytes::new(e))
 }
 
+// Check whether the asset has enough price updates within recent periods to serve its prices
+pub fn has_min_coverage(e: &Env, asset: u32) -> bool {
+    match settings::get_min_coverage(e) {
+        Some((min_updates, periods)) => {
+            mapping::count_history_updates(&get_history_map(e), asset, periods) >= min_updates
+        }
+        None => true,
+    }
+}
+
 //
 pub fn update_history_mask(e: &Env, prices: &Vec<i128>, timestamp: u64) {
     //load state
     let last_timestamp = get_last_timestamp(e);
     let mut history_map = get_history_map(e);
     let resolution = settings::get_resolution(e) as u64;
+    //resubmitted record replaces the most recent period in place without shifting the history
+    if last_timestamp > 0 && timestamp == last_timestamp {
+        history_map = mapping::update_history_period(e, history_map, prices, 0);
+        e.storage().instance().set(&HISTORY_KEY, &history_map);
+        return;
+    }
     //find the delta in updates
     let mut update_delta = 0;
     if last_timestamp > 0 && timestamp > last_timestamp {
@@ -137,25 +231,144 @@ pub fn update_history_mask(e: &Env, prices: &Vec<i128>, timestamp: u64) {
     e.storage().instance().set(&HISTORY_KEY, &history_map);
 }
 
+// Update history mask for a period older than the last update timestamp
+pub fn backfill_history_mask(e: &Env, prices: &Vec<i128>, timestamp: u64) {
+    let last_timestamp = get_last_timestamp(e);
+    let resolution = settings::get_resolution(e) as u64;
+    //find the position of the period relative to the last update
+    let period = (last_timestamp - timestamp) / resolution;
+    let history_map = mapping::update_history_period(e, get_history_map(e), prices, period as u32);
+    //store updated timestamps
+    e.storage().instance().set(&HISTORY_KEY, &history_map);
+}
+
 pub fn has_price(e: &Env, asset_index: u32, periods_ago: u32) -> bool {
     let timestamps = get_history_map(e);
     mapping::check_history_updated(&timestamps, asset_index, periods_ago)
 }
 
+// Check price availability for multiple periods at once, loading the history map only once
+// (periods beyond the 256 tracked ones are reported as missing)
+pub fn has_prices(e: &Env, asset_index: u32, periods: Vec<u32>) -> Vec<bool> {
+    let timestamps = get_history_map(e);
+    let mut res = Vec::new(e);
+    for periods_ago in periods.iter() {
+        res.push_back(
+            periods_ago < 256
+                && mapping::check_history_updated(&timestamps, asset_index, periods_ago),
+        );
+    }
+    res
+}
+
+// Count periods with price records for specific asset among all periods tracked in the history mask
+pub fn count_asset_periods(e: &Env, asset_index: u32) -> u32 {
+    if get_last_timestamp(e) == 0 {
+        return 0;
+    }
+    mapping::count_history_updates(&get_history_map(e), asset_index, 256)
+}
+
+// Load timestamps of the most recent periods that have price records for specific asset
+pub fn load_asset_timestamps(e: &Env, asset_index: u32, limit: u32) -> Vec<u64> {
+    let mut res = Vec::new(e);
+    let last_timestamp = get_last_timestamp(e);
+    if last_timestamp == 0 {
+        return res;
+    }
+    let resolution = settings::get_resolution(e) as u64;
+    let history_map = get_history_map(e);
+    //we cannot track more than 256 updates in the bitmask
+    let limit = limit.min(256);
+    for period in 0..256u32 {
+        if res.len() >= limit {
+            break;
+        }
+        let shift = period as u64 * resolution;
+        if shift > last_timestamp {
+            break;
+        }
+        if mapping::check_history_updated(&history_map, asset_index, period) {
+            res.push_back(timestamps::to_seconds(last_timestamp - shift));
+        }
+    }
+    res
+}
+
+// Find timestamp of the oldest period tracked in the history mask that has price record for specific asset
+pub fn find_oldest_asset_timestamp(e: &Env, asset_index: u32) -> Option<u64> {
+    let last_timestamp = get_last_timestamp(e);
+    if last_timestamp == 0 {
+        return None;
+    }
+    let resolution = settings::get_resolution(e) as u64;
+    let history_map = get_history_map(e);
+    //scan from the furthest period forward
+    for period in (0..256u32).rev() {
+        let shift = period as u64 * resolution;
+        if shift >= last_timestamp {
+            continue;
+        }
+        if mapping::check_history_updated(&history_map, asset_index, period) {
+            return Some(timestamps::to_seconds(last_timestamp - shift));
+        }
+    }
+    None
+}
+
 // Load prices for a given timestamp
 pub fn load_history_record(e: &Env, timestamp: u64) -> Option<PriceUpdate> {
-    //check if the timestamp is in the cache
-    let cache = load_price_records_cache(e);
-    if cache.is_some() {
-        //check the cache first
-        for (ts, prices) in cache.unwrap() {
-            if ts == timestamp {
-                return Some(prices);
-            }
+    load_history_record_with_source(e, timestamp).map(|(record, _)| record)
+}
+
+// Load prices for a given timestamp along with the flag indicating whether the record
+// has been served from the instance cache
+fn load_history_record_with_source(e: &Env, timestamp: u64) -> Option<(PriceUpdate, bool)> {
+    //with storage-first strategy, skip the cache scan for records still present in the temporary storage
+    //(this pays off only for large caches, since each read adds a temporary ledger entry access)
+    if settings::get_storage_first(e) {
+        if let Some(record) = load_stored_record(e, timestamp) {
+            return Some((record, false));
         }
+        return find_cached_record(e, timestamp).map(|record| (record, true));
+    }
+    //check the cache first
+    if let Some(record) = find_cached_record(e, timestamp) {
+        return Some((record, true));
     }
     //get the price from the temporary storage
-    e.storage().temporary().get(&timestamp)
+    load_stored_record(e, timestamp).map(|record| (record, false))
+}
+
+// Load prices for a given timestamp from the temporary storage, regardless of the storage layout
+fn load_stored_record(e: &Env, timestamp: u64) -> Option<PriceUpdate> {
+    if let Some(record) = e.storage().temporary().get(&timestamp) {
+        return Some(record);
+    }
+    //restore the update record from the single-asset layout (only the first asset is stored there)
+    let price = load_single_asset_price(e, timestamp)?;
+    Some(PriceUpdate {
+        prices: Vec::from_array(e, [price]),
+        mask: Bytes::from_array(e, &[1u8]),
+    })
+}
+
+// Load the price stored in the single-asset layout
+fn load_single_asset_price(e: &Env, timestamp: u64) -> Option<i128> {
+    e.storage()
+        .temporary()
+        .get(&(SINGLE_ASSET_PRICE_KEY, timestamp))
+}
+
+// Find the record for a given timestamp in the instance cache
+fn find_cached_record(e: &Env, timestamp: u64) -> Option<PriceUpdate> {
+    let cache = load_price_records_cache(e)?;
+    for (ts, prices) in cache {
+        if ts == timestamp {
+            return Some(prices);
+        }
+    }
+    None
 }
 
 // Update prices stored in the oracle
@@ -167,63 +380,242 @@ pub fn store_prices(e: &Env, update: &PriceUpdate, timestamp: u64, update_v1: &V
         set_last_timestamp(e, timestamp);
     }
 
+    //calculate TTL, backfilled records don't outlive their retention period either
+    let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp);
     //set the price
-    let temps_storage = e.storage().temporary();
-    temps_storage.set(&timestamp, &update);
+    write_stored_record(e, update, timestamp, update_v1, ledgers_to_live);
     //update cache
     let cache_size = settings::get_cache_size(e);
     if cache_size > 0 {
         //if cache size is non-empty, store it in the instance
-        let mut cache = load_price_records_cache(e).unwrap_or(Vec::new(&e));
-        cache.push_front((timestamp, update.clone()));
-        while cache.len() > cache_size {
-            cache.pop_back(); //remove the oldest record if cache size exceeded
+        let mut cache = load_price_records_cache(e).unwrap_or(Vec::new(e));
+        match cache.iter().position(|(ts, _)| ts == timestamp) {
+            //overwritten record must not be served from the stale cached copy
+            Some(position) => {
+                cache.set(position as u32, (timestamp, update.clone()));
+                e.storage().instance().set(&CACHE_KEY, &cache);
+            }
+            //backfilled records are not cached to avoid evicting recent ones
+            None if timestamp >= last_timestamp => {
+                cache.push_front((timestamp, update.clone()));
+                evict_cached_records(e, &mut cache, cache_size);
+                //write cache entry
+                e.storage().instance().set(&CACHE_KEY, &cache);
+            }
+            None => {}
         }
-        //write cache entry
-        e.storage().instance().set(&CACHE_KEY, &cache);
-    }
-    //calculate TTL
-    let retention_period = settings::get_history_retention_period(e);
-    let ledgers_to_live = ((retention_period / 1000 / 5 + 1) * 2) as u32;
-    //bump if needed
-    if ledgers_to_live > 16 {
-        //16 ledgers is the minimum extension period
-        temps_storage.extend_ttl(&timestamp, ledgers_to_live, ledgers_to_live)
     }
 
     //if the protocol hasn't updated to the latest version yet
     if !protocol::at_latest_protocol_version(e) {
         store_price_v1(e, update_v1, timestamp, ledgers_to_live);
+    } else {
+        protocol::clear_expired_legacy_cutoff(e);
+    }
+}
+
+// Calculate TTL (in ledgers) for the record that keeps it alive until the end of the retention period
+// counted from the record timestamp
+fn calculate_record_ledgers_to_live(e: &Env, timestamp: u64) -> u32 {
+    let age = timestamps::ledger_timestamp(e).saturating_sub(timestamp);
+    retention_period_to_ledgers(settings::get_history_retention_period(e).saturating_sub(age))
+}
+
+// Convert history retention period (in milliseconds) to price records TTL (in ledgers)
+fn retention_period_to_ledgers(retention_period: u64) -> u32 {
+    ((timestamps::to_seconds(retention_period) / 5 + 1) * 2) as u32
+}
+
+// Check whether the retention period (in milliseconds) yields TTL above the minimum extension period,
+// zero retention period disables the enforcement
+pub fn is_valid_retention_period(retention_period: u64) -> bool {
+    retention_period == 0 || retention_period_to_ledgers(retention_period) > MIN_RECORD_TTL
+}
+
+// Write price record into the temporary storage
+fn write_stored_record(
+    e: &Env,
+    update: &PriceUpdate,
+    timestamp: u64,
+    prices: &Vec<i128>,
+    ledgers_to_live: u32,
+) {
+    let temps_storage = e.storage().temporary();
+    //oracles tracking a single asset store the price directly, without the update record mask
+    if assets::load_all_assets(e).len() == 1 {
+        //the flag is cleared once more assets are added
+        if !settings::get_single_asset_layout(e) {
+            settings::set_single_asset_layout(e, true);
+        }
+        let key = (SINGLE_ASSET_PRICE_KEY, timestamp);
+        temps_storage.set(&key, &prices.get_unchecked(0));
+        //16 ledgers is the minimum extension period
+        if ledgers_to_live > 16 {
+            temps_storage.extend_ttl(&key, ledgers_to_live, ledgers_to_live)
+        }
+    } else {
+        temps_storage.set(&timestamp, update);
+        if ledgers_to_live > 16 {
+            temps_storage.extend_ttl(&timestamp, ledgers_to_live, ledgers_to_live)
+        }
+    }
+}
+
+// Extend TTL of the stored record up to the end of its history retention period
+fn extend_record_ttl(e: &Env, timestamp: u64) {
+    //extension never exceeds the remaining retention period, so records can't outlive it
+    let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp);
+    //16 ledgers is the minimum extension period
+    if ledgers_to_live <= 16 {
+        return;
+    }
+    let temps_storage = e.storage().temporary();
+    if temps_storage.has(&timestamp) {
+        temps_storage.extend_ttl(&timestamp, ledgers_to_live, ledgers_to_live);
+    } else {
+        let key = (SINGLE_ASSET_PRICE_KEY, timestamp);
+        if temps_storage.has(&key) {
+            temps_storage.extend_ttl(&key, ledgers_to_live, ledgers_to_live);
+        }
+    }
+}
+
+// Find timestamps of the records expiring within the given number of ledgers
+// (TTL of temporary entries can't be queried on-chain, so it is estimated from the record timestamp
+// and the retention period, which may report records a bit earlier than they actually expire)
+pub fn find_records_at_risk(e: &Env, within_ledgers: u32) -> Vec<u64> {
+    let mut res = Vec::new(e);
+    let last_timestamp = get_last_timestamp(e);
+    if last_timestamp == 0 {
+        return res;
+    }
+    let resolution = settings::get_resolution(e) as u64;
+    let history_map = get_history_map(e);
+    //records outside the history mask range are not reachable anyway
+    for period in 0..256u32 {
+        let shift = period as u64 * resolution;
+        if shift > last_timestamp {
+            break;
+        }
+        if !mapping::check_period_recorded(&history_map, period) {
+            continue;
+        }
+        let timestamp = last_timestamp - shift;
+        let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp).max(MIN_RECORD_TTL);
+        if ledgers_to_live <= within_ledgers {
+            res.push_front(timestamp);
+        }
+    }
+    res
+}
+
+// Rewrite history mask and recent price records after assets compaction, keeping only given (old) asset indexes
+pub fn reindex_history(e: &Env, live: &Vec<u32>, total: u32) {
+    let history_map = mapping::compact_history_mask(e, &get_history_map(e), live);
+    e.storage().instance().set(&HISTORY_KEY, &history_map);
+    //rewrite cached records
+    if let Some(cache) = load_price_records_cache(e) {
+        let mut compacted = Vec::new(e);
+        for (timestamp, record) in cache {
+            let (update, _) = compact_update_record(e, &record, live, total);
+            compacted.push_back((timestamp, update));
+        }
+        e.storage().instance().set(&CACHE_KEY, &compacted);
+    }
+    //rewrite all records reachable through the history mask
+    let last_timestamp = get_last_timestamp(e);
+    let resolution = settings::get_resolution(e) as u64;
+    for period in 0..256u64 {
+        let shift = period * resolution;
+        if shift > last_timestamp {
+            break;
+        }
+        let timestamp = last_timestamp - shift;
+        if let Some(record) = load_stored_record(e, timestamp) {
+            let (update, prices) = compact_update_record(e, &record, live, total);
+            //drop the record in the previous layout
+            e.storage().temporary().remove(&timestamp);
+            //rewritten records keep the remaining part of their retention period
+            let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp);
+            write_stored_record(e, &update, timestamp, &prices, ledgers_to_live);
+        }
+    }
+}
+
+// Replace the stored record (and its cached copy) with the corrected prices of all assets
+pub fn correct_record(e: &Env, timestamp: u64, prices: &Vec<i128>) {
+    let mut updated = Vec::new(e);
+    for price in prices.iter() {
+        if price > 0 {
+            updated.push_back(price);
+        }
+    }
+    let update = PriceUpdate {
+        prices: updated,
+        mask: mapping::build_update_record_mask(e, prices),
+    };
+    let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp);
+    write_stored_record(e, &update, timestamp, prices, ledgers_to_live);
+    if let Some(mut cache) = load_price_records_cache(e) {
+        if let Some(position) = cache.iter().position(|(ts, _)| ts == timestamp) {
+            cache.set(position as u32, (timestamp, update));
+            e.storage().instance().set(&CACHE_KEY, &cache);
+        }
     }
 }
 
-// Load requested number of price records with a price function callback
+// Build update record containing prices only for given asset indexes
+fn compact_update_record(
+    e: &Env,
+    record: &PriceUpdate,
+    live: &Vec<u32>,
+    total: u32,
+) -> (PriceUpdate, Vec<i128>) {
+    let all_prices = extract_update_record_prices(e, record, total);
+    let mut prices = Vec::new(e);
+    let mut updated = Vec::new(e);
+    for asset_index in live.iter() {
+        let price = all_prices.get(asset_index).unwrap_or_default();
+        prices.push_back(price);
+        if price > 0 {
+            updated.push_back(price);
+        }
+    }
+    let update = PriceUpdate {
+        prices: updated,
+        mask: mapping::build_update_record_mask(e, &prices),
+    };
+    (update, prices)
+}
+
+// Load requested number of price records with a price function callback, stepping by the given resolution
 pub fn load_prices<F: Fn(u64) -> Option<PriceData>>(
     e: &Env,
     get_price_fn: F,
     mut records: u32,
+    resolution: u64,
 ) -> Option<Vec<PriceData>> {
-    let mut timestamp = obtain_last_record_timestamp(e);
+    let mut timestamp = timestamps::normalize_to(obtain_last_record_timestamp(e), resolution);
     if timestamp == 0 {
         return None;
     }
 
     let mut prices = Vec::new(e);
-    let resolution = settings::get_resolution(e) as u64;
 
-    //limit the number of returned records to 20
-    records = records.min(20);
+    //limit the number of returned records
+    records = records.min(settings::get_max_records(e));
 
     while records > 0 {
         //invoke price fetch callback for each record
         if let Some(price) = get_price_fn(timestamp) {
             prices.push_back(price);
         }
-        if timestamp < resolution {
-            break;
-        }
         //decrement remaining records counter in every iteration
         records -= 1;
+        //the period at timestamp == resolution is the oldest possible one
+        if timestamp <= resolution {
+            break;
+        }
         timestamp -= resolution;
     }
 
@@ -234,31 +626,161 @@ pub fn load_prices<F: Fn(u64) -> Option<PriceData>>(
     }
 }
 
-// Calculate TWAP approximation from loaded price range
-pub fn calculate_twap<F: Fn(u64) -> Option<PriceData>>(
+// Load price records within the time range (in milliseconds), most recent first,
+// skipping `offset` records and returning at most max records along with the flag indicating whether more records remain
+pub fn load_prices_range<F: Fn(u64) -> Option<PriceData>>(
+    e: &Env,
+    get_price_fn: F,
+    from_timestamp: u64,
+    to_timestamp: u64,
+    resolution: u64,
+    offset: u32,
+) -> (Vec<PriceData>, bool) {
+    let mut prices = Vec::new(e);
+    let max_records = settings::get_max_records(e);
+    //records newer than the last update do not exist
+    let to_timestamp = to_timestamp.min(obtain_last_record_timestamp(e));
+    let mut timestamp = timestamps::normalize_to(to_timestamp, resolution);
+    //records beyond the history mask range can't be referenced, no need to scan them
+    let mask_window = 255 * settings::get_resolution(e) as u64;
+    let mut from_timestamp = from_timestamp.max(timestamp.saturating_sub(mask_window));
+    //records older than the retention period have already expired
+    let retention_period = settings::get_history_retention_period(e);
+    if retention_period > 0 {
+        from_timestamp = from_timestamp.max(timestamp.saturating_sub(retention_period));
+    }
+    let mut skipped = 0;
+    while timestamp > 0 && timestamp >= from_timestamp {
+        if let Some(price) = get_price_fn(timestamp) {
+            if skipped < offset {
+                skipped += 1;
+            } else if prices.len() < max_records {
+                prices.push_back(price);
+            } else {
+                //range exceeds the cap
+                return (prices, true);
+            }
+        }
+        if timestamp <= resolution {
+            break;
+        }
+        timestamp -= resolution;
+    }
+    (prices, false)
+}
+
+// Load exactly the requested number of recent price records, ensuring that the last one is fresh
+pub fn load_fresh_prices<F: Fn(u64) -> Option<PriceData>>(
     e: &Env,
     get_price_fn: F,
     records: u32,
-) -> Option<i128> {
-    let prices = load_prices(&e, get_price_fn, records)?;
+    resolution: u64,
+) -> Option<Vec<PriceData>> {
+    let prices = load_prices(e, get_price_fn, records, resolution)?;
 
     if prices.len() != records {
         return None;
     }
 
-    let last_price_timestamp = prices.first()?.timestamp * 1000; //convert to milliseconds to match the timestamp format
-    let timeframe = settings::get_resolution(e) as u64;
-    let current_time = timestamps::ledger_timestamp(&e);
+    let last_price_timestamp = timestamps::to_milliseconds(prices.first()?.timestamp); //convert to match the timestamp format
+    let current_time = timestamps::ledger_timestamp(e);
 
     //check if the last price is too old
-    if last_price_timestamp + timeframe + 60 * 1000 < current_time {
+    if last_price_timestamp + resolution + timestamps::to_milliseconds(60) < current_time {
         return None;
     }
+    Some(prices)
+}
+
+// Check whether both assets have price records for the requested number of recent periods, ensuring that the last one is fresh
+pub fn has_fresh_cross_records(e: &Env, asset_pair_indexes: (u32, u32), records: u32) -> bool {
+    //mirror x_twap and load_fresh_prices constraints
+    if records == 0
+        || records < settings::get_min_twap_records(e)
+        || records > settings::get_max_records(e)
+    {
+        return false;
+    }
+    let last_timestamp = obtain_last_record_timestamp(e);
+    if last_timestamp == 0 {
+        return false;
+    }
+    let resolution = settings::get_resolution(e) as u64;
+    if last_timestamp + resolution + timestamps::to_milliseconds(60)
+        < timestamps::ledger_timestamp(e)
+    {
+        return false;
+    }
+    //not enough periods since the history start
+    if (records as u64 - 1) * resolution >= last_timestamp {
+        return false;
+    }
+    let (base_asset, quote_asset) = asset_pair_indexes;
+    let history_map = get_history_map(e);
+    //cross prices are not served for quote prices below the floor (not applied to the same-asset pair)
+    let price_floor = if base_asset == quote_asset {
+        0
+    } else {
+        assets::get_asset_price_floor(e, quote_asset)
+    };
+    (0..records).all(|period| {
+        mapping::check_history_updated(&history_map, base_asset, period)
+            && mapping::check_history_updated(&history_map, quote_asset, period)
+            && (price_floor == 0
+                || retrieve_asset_price_data(
+                    e,
+                    quote_asset,
+                    last_timestamp - period as u64 * resolution,
+                )
+                .is_some_and(|price| price.price >= price_floor))
+    })
+}
+
+// Calculate TWAP approximation from loaded price range
+pub fn calculate_twap<F: Fn(u64) -> Option<PriceData>>(
+    e: &Env,
+    get_price_fn: F,
+    records: u32,
+    resolution: u64,
+) -> Option<i128> {
+    let prices = load_fresh_prices(e, get_price_fn, records, resolution)?;
 
     let sum: i128 = prices.iter().map(|price_data| price_data.price).sum();
     Some(sum / prices.len() as i128)
 }
 
+// Calculate weighted median from loaded price range, weights correspond to records starting from the most recent
+pub fn calculate_weighted_median<F: Fn(u64) -> Option<PriceData>>(
+    e: &Env,
+    get_price_fn: F,
+    weights: &Vec<i128>,
+    resolution: u64,
+) -> Option<i128> {
+    let prices = load_fresh_prices(e, get_price_fn, weights.len(), resolution)?;
+    //sort price-weight pairs by price (insertion sort is fine for up to 20 records)
+    let mut sorted: Vec<(i128, i128)> = Vec::new(e);
+    for (price_data, weight) in prices.iter().zip(weights.iter()) {
+        let mut position = 0;
+        while position < sorted.len() && sorted.get_unchecked(position).0 <= price_data.price {
+            position += 1;
+        }
+        sorted.insert(position, (price_data.price, weight));
+    }
+    //find the price where cumulative weight crosses half of the total weight
+    //weights are positive, so the cumulative weight never exceeds the total once it fits into i128
+    let total = weights
+        .iter()
+        .try_fold(0i128, |total, weight| total.checked_add(weight))?;
+    let mut cumulative = 0;
+    for (price, weight) in sorted.iter() {
+        cumulative += weight;
+        if cumulative >= total - cumulative {
+            return Some(price);
+        }
+    }
+    None
+}
+
 // Load prices for a pair of assets
 pub fn load_cross_price(
     e: &Env,
@@ -270,16 +792,45 @@ pub fn load_cross_price(
     let (base_asset, quote_asset) = asset_pair_indexes;
     //check if the asset are the same
     if base_asset == quote_asset {
+        //unit price is served only if the asset itself has a price record at the timestamp,
+        //all assets share the same precision, so the asset's own scale matches the cross decimals
+        retrieve_asset_price_data(e, base_asset, timestamp)?;
         return Some(normalize_price_data(10i128.pow(decimals), timestamp));
     }
     //get the price for base_asset
     let base_asset_price = retrieve_asset_price_data(e, base_asset, timestamp)?;
+    calculate_cross_price(e, base_asset_price.price, quote_asset, timestamp, decimals)
+}
+
+// Load price of the oracle base asset expressed in the given quote asset
+pub fn load_base_cross_price(
+    e: &Env,
+    quote_asset: u32,
+    timestamp: u64,
+    decimals: u32,
+) -> Option<PriceData> {
+    //the base asset is always priced at one unit
+    calculate_cross_price(e, 10i128.pow(decimals), quote_asset, timestamp, decimals)
+}
+
+// Calculate cross price for the base asset price against the quote asset price recorded at the timestamp
+fn calculate_cross_price(
+    e: &Env,
+    base_asset_price: i128,
+    quote_asset: u32,
+    timestamp: u64,
+    decimals: u32,
+) -> Option<PriceData> {
     //get the price for quote_asset
     let quote_asset_price = retrieve_asset_price_data(e, quote_asset, timestamp)?;
+    //tiny denominators produce unreasonably large cross prices
+    if quote_asset_price.price < assets::get_asset_price_floor(e, quote_asset) {
+        return None;
+    }
 
     //calculate the cross price
     Some(normalize_price_data(
-        fixed_div_floor(base_asset_price.price, quote_asset_price.price, decimals),
+        fixed_div_floor(e, base_asset_price, quote_asset_price.price, decimals),
         timestamp,
     ))
 }
@@ -289,6 +840,73 @@ fn load_price_records_cache(e: &Env) -> Option<Vec<(u64, PriceUpdate)>> {
     e.storage().instance().get(&CACHE_KEY)
 }
 
+// Load timestamps of the records held in the instance cache
+pub fn load_cached_timestamps(e: &Env) -> Vec<u64> {
+    let mut timestamps = Vec::new(e);
+    if let Some(cache) = load_price_records_cache(e) {
+        for (timestamp, _) in cache {
+            timestamps.push_back(timestamp);
+        }
+    }
+    timestamps
+}
+
+// Load specified records from the temporary storage into the instance cache
+pub fn warm_cache(e: &Env, timestamps: &Vec<u64>) {
+    let cache_size = settings::get_cache_size(e);
+    if cache_size == 0 {
+        return;
+    }
+    let mut cache = load_price_records_cache(e).unwrap_or(Vec::new(e));
+    for timestamp in timestamps.iter() {
+        //skip already cached records
+        if cache.iter().any(|(ts, _)| ts == timestamp) {
+            continue;
+        }
+        if let Some(record) = load_stored_record(e, timestamp) {
+            //keep cache records ordered, most recent first
+            let position = cache
+                .iter()
+                .position(|(ts, _)| ts < timestamp)
+                .unwrap_or(cache.len() as usize);
+            cache.insert(position as u32, (timestamp, record));
+        }
+    }
+    evict_cached_records(e, &mut cache, cache_size);
+    e.storage().instance().set(&CACHE_KEY, &cache);
+}
+
+// Evict cached records exceeding the cache size according to the configured eviction policy
+fn evict_cached_records(e: &Env, cache: &mut Vec<(u64, PriceUpdate)>, cache_size: u32) {
+    let stride = match settings::get_cache_eviction_policy(e) {
+        CacheEvictionPolicy::Strided(stride) => stride as u64 * settings::get_resolution(e) as u64,
+        CacheEvictionPolicy::Contiguous => 0,
+    };
+    while cache.len() > cache_size {
+        //the most recent record is always kept, the oldest one not aligned to the stride goes first
+        let unaligned = if stride > 0 {
+            (1..cache.len())
+                .rev()
+                .find(|&index| !cache.get_unchecked(index).0.is_multiple_of(stride))
+        } else {
+            None
+        };
+        match unaligned {
+            Some(index) => {
+                cache.remove(index);
+            }
+            None => {
+                cache.pop_back(); //remove the oldest record if cache size exceeded
+            }
+        }
+    }
+}
+
+// Remove all cached records from the instance storage
+pub fn clear_cache(e: &Env) {
+    e.storage().instance().remove(&CACHE_KEY);
+}
+
 // Update price in legacy format (deprecated)
 pub fn store_price_v1(e: &Env, updates: &Vec<i128>, timestamp: u64, ledgers_to_live: u32) {
     //iterate over the updates
@@ -311,6 +929,48 @@ pub fn store_price_v1(e: &Env, updates: &Vec<i128>, timestamp: u64, ledgers_to_l
     }
 }
 
+// Rewrite legacy per-asset records for the timestamp into the update record format,
+// returns false if there is nothing to migrate or the record is out of the history mask range
+pub fn migrate_price_v1(e: &Env, timestamp: u64, total: u32) -> bool {
+    //already available in the current format
+    if load_stored_record(e, timestamp).is_some() {
+        return false;
+    }
+    match calculate_period_offset(e, timestamp) {
+        Some(period) if period <= 255 => {}
+        _ => return false, //the record cannot be referenced by the history mask
+    }
+    //legacy records support only 256 assets
+    let mut prices = Vec::new(e);
+    let mut updated = Vec::new(e);
+    for asset in 0..total.min(u8::MAX as u32 + 1) {
+        let price = get_price_v1(e, asset as u8, timestamp).unwrap_or_default();
+        if price > 0 {
+            updated.push_back(price);
+        }
+        prices.push_back(price);
+    }
+    if updated.is_empty() {
+        return false;
+    }
+    let update = PriceUpdate {
+        prices: updated,
+        mask: mapping::build_update_record_mask(e, &prices),
+    };
+    //the migrated record lives only until the end of its retention period
+    let ledgers_to_live = calculate_record_ledgers_to_live(e, timestamp);
+    write_stored_record(e, &update, timestamp, &prices, ledgers_to_live);
+    backfill_history_mask(e, &prices, timestamp);
+    //drop legacy records that are no longer needed
+    let temp_storage = e.storage().temporary();
+    for (asset, price) in prices.iter().enumerate() {
+        if price > 0 {
+            temp_storage.remove(&format_price_key_v1(asset as u8, timestamp));
+        }
+    }
+    true
+}
+
 // Load price in legacy format (deprecated)
 pub fn get_price_v1(e: &Env, asset: u8, timestamp: u64) -> Option<i128> {
     //load the price from temporary storage
@@ -324,10 +984,21 @@ fn format_price_key_v1(asset: u8, timestamp: u64) -> u128 {
     (timestamp as u128) << 64 | asset as u128
 }
 
-// Div+floor with a specified precision
-pub fn fixed_div_floor(dividend: i128, divisor: i128, decimals: u32) -> i128 {
+// Calculate absolute price deviation from the reference price in basis points
+pub fn deviation_bps(price: i128, reference_price: i128) -> i128 {
+    (price - reference_price).abs() * 10_000 / reference_price
+}
+
+// Div+floor with a specified precision, panics if any of the arguments is not positive
+pub fn fixed_div_floor(e: &Env, dividend: i128, divisor: i128, decimals: u32) -> i128 {
+    try_fixed_div_floor(dividend, divisor, decimals)
+        .unwrap_or_else(|| panic_with_error!(e, Error::InvalidDivision))
+}
+
+// Div+floor with a specified precision, returns None if any of the arguments is not positive
+pub fn try_fixed_div_floor(dividend: i128, divisor: i128, decimals: u32) -> Option<i128> {
     if dividend <= 0 || divisor <= 0 {
-        panic!("invalid division arguments")
+        return None;
     }
     let ashift = core::cmp::min(38 - dividend.ilog10(), decimals);
     let bshift = core::cmp::max(decimals - ashift, 0);
@@ -340,5 +1011,5 @@ pub fn fixed_div_floor(dividend: i128, divisor: i128, decimals: u32) -> i128 {
     if bshift > 0 {
         vdivisor /= 10_i128.pow(bshift);
     }
-    vdividend / vdivisor
+    Some(vdividend / vdivisor)
 }
@@ -1,4 +1,4 @@
-use crate::timestamps;
+use crate::{settings, timestamps};
 use soroban_sdk::Env;
 
 //current protocol version
@@ -7,6 +7,7 @@ pub const CURRENT_PROTOCOL: u32 = 2;
 //storage keys
 const UPDATE_TS_KEY: &str = "protocol_update";
 const PROTOCOL_KEY: &str = "protocol";
+const LEGACY_CUTOFF_KEY: &str = "legacy_cutoff";
 
 // Load current protocol version
 #[inline(always)]
@@ -20,6 +21,12 @@ pub fn set_protocol_version(e: &Env, protocol: u32) {
     e.storage().instance().set(&PROTOCOL_KEY, &protocol);
 }
 
+// Check whether the oracle already uses the latest protocol version without scheduling the upgrade
+#[inline(always)]
+pub fn is_latest_protocol_version(e: &Env) -> bool {
+    get_protocol_version(e) == CURRENT_PROTOCOL
+}
+
 // Check whether the oracle already uses the latest protocol version and if not - schedule the upgrade
 pub fn at_latest_protocol_version(e: &Env) -> bool {
     //load current protocol version
@@ -34,7 +41,7 @@ pub fn at_latest_protocol_version(e: &Env) -> bool {
 // Schedule protocol update
 fn schedule_update(e: &Env) -> bool {
     //get current ledger ts
-    let ledger_timestamp = timestamps::ledger_timestamp(&e);
+    let ledger_timestamp = timestamps::ledger_timestamp(e);
     let scheduled_update_ts = e.storage().instance().get(&UPDATE_TS_KEY).unwrap_or(0);
     if scheduled_update_ts == 0 {
         set_protocol_upgrade_timestamp(e, ledger_timestamp); //set update timestamp to now if not set
@@ -44,11 +51,45 @@ fn schedule_update(e: &Env) -> bool {
     if scheduled_update_ts + timestamps::days_to_milliseconds(1) < ledger_timestamp {
         set_protocol_version(e, CURRENT_PROTOCOL);
         set_protocol_upgrade_timestamp(e, 0); // reset update timestamp
+        e.storage()
+            .instance()
+            .set(&LEGACY_CUTOFF_KEY, &ledger_timestamp); //legacy records may exist only before this point
         return true; //now we are at the latest protocol version
     }
     false
 }
 
+// Check whether a legacy price record may exist for the timestamp after the protocol upgrade
+#[inline(always)]
+pub fn has_legacy_records(e: &Env, timestamp: u64) -> bool {
+    let cutoff = get_legacy_cutoff(e);
+    timestamp < cutoff && !is_legacy_cutoff_expired(e, cutoff)
+}
+
+// Remove legacy cutoff once all records written before the protocol upgrade are past the retention period
+pub fn clear_expired_legacy_cutoff(e: &Env) {
+    let cutoff = get_legacy_cutoff(e);
+    if cutoff > 0 && is_legacy_cutoff_expired(e, cutoff) {
+        e.storage().instance().remove(&LEGACY_CUTOFF_KEY);
+    }
+}
+
+// Load the timestamp legacy records may exist before (zero if there are no legacy records)
+#[inline(always)]
+fn get_legacy_cutoff(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&LEGACY_CUTOFF_KEY)
+        .unwrap_or_default()
+}
+
+// Check whether the retention period has passed since the legacy cutoff (never if retention is not limited)
+fn is_legacy_cutoff_expired(e: &Env, cutoff: u64) -> bool {
+    let retention_period = settings::get_history_retention_period(e);
+    retention_period > 0
+        && timestamps::ledger_timestamp(e).saturating_sub(cutoff) > retention_period
+}
+
 fn set_protocol_upgrade_timestamp(e: &Env, timestamp: u64) {
     e.storage().instance().set(&UPDATE_TS_KEY, &timestamp);
 }
@@ -1,4 +1,4 @@
-use crate::types::{Asset, Error, FeeConfig};
+use crate::types::{Asset, CacheEvictionPolicy, Error, FeeConfig};
 use soroban_sdk::{Address, Env};
 
 const RETENTION_PERIOD_KEY: &str = "period";
@@ -7,9 +7,26 @@ const DECIMALS_KEY: &str = "decimals";
 const RESOLUTION_KEY: &str = "resolution";
 const RETENTION_KEY: &str = "retention";
 const CACHE_SIZE_KEY: &str = "cache_size";
+const STRICT_MODE_KEY: &str = "strict";
+const STORAGE_FIRST_KEY: &str = "storage_first";
+const READ_BUMP_KEY: &str = "read_bump";
+const MAX_PRICE_KEY: &str = "max_price";
+const FEE_SPLIT_KEY: &str = "fee_split";
+const MAX_BATCH_SIZE_KEY: &str = "max_batch";
+const PAUSED_KEY: &str = "paused";
+const DEVIATION_LIMITS_KEY: &str = "deviation_limits";
+const MIN_COVERAGE_KEY: &str = "min_coverage";
+const MIN_TWAP_RECORDS_KEY: &str = "min_twap";
+const PLACEHOLDER_PRICE_KEY: &str = "placeholder";
+const CACHE_EVICTION_KEY: &str = "cache_eviction";
+const INITIALIZED_KEY: &str = "initialized";
+const MAX_RECORDS_KEY: &str = "max_records";
+const SINGLE_ASSET_LAYOUT_KEY: &str = "single_layout";
 
 pub const XRF_TOKEN_ADDRESS: &str = "CBLLEW7HD2RWATVSMLAGWM4G3WCHSHDJ25ALP4DI6LULV5TU35N2CIZA";
 const DEFAULT_RETENTION_FEE: i128 = 100_000_000;
+const DEFAULT_MAX_BATCH_SIZE: u32 = 50;
+const DEFAULT_MAX_RECORDS: u32 = 20;
 
 #[inline]
 pub fn init(
@@ -35,6 +52,17 @@ pub fn init(
     set_fee_config(e, fee_config);
 }
 
+// Whether the contract configuration has been fully applied
+#[inline]
+pub fn is_initialized(e: &Env) -> bool {
+    e.storage().instance().has(&INITIALIZED_KEY)
+}
+
+#[inline]
+pub fn set_initialized(e: &Env) {
+    e.storage().instance().set(&INITIALIZED_KEY, &true);
+}
+
 #[inline]
 pub fn get_base_asset(e: &Env) -> Asset {
     e.storage().instance().get(&BASE_KEY).unwrap()
@@ -85,6 +113,12 @@ pub fn set_fee_config(e: &Env, fee_config: &FeeConfig) {
     e.storage().instance().set(&RETENTION_KEY, &fee_config);
 }
 
+// Whether fee config has been explicitly stored (as opposed to the default XRF config)
+#[inline]
+pub fn has_fee_config(e: &Env) -> bool {
+    e.storage().instance().has(&RETENTION_KEY)
+}
+
 #[inline]
 pub fn get_fee_config(e: &Env) -> FeeConfig {
     e.storage()
@@ -98,3 +132,227 @@ pub fn get_fee_config(e: &Env) -> FeeConfig {
             ))
         })
 }
+
+#[inline]
+pub fn get_strict_mode(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&STRICT_MODE_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_strict_mode(e: &Env, strict: bool) {
+    e.storage().instance().set(&STRICT_MODE_KEY, &strict);
+}
+
+#[inline]
+pub fn get_cache_eviction_policy(e: &Env) -> CacheEvictionPolicy {
+    e.storage()
+        .instance()
+        .get(&CACHE_EVICTION_KEY)
+        .unwrap_or(CacheEvictionPolicy::Contiguous)
+}
+
+#[inline]
+pub fn set_cache_eviction_policy(e: &Env, policy: &CacheEvictionPolicy) {
+    if let CacheEvictionPolicy::Strided(stride) = policy {
+        //stride of a single round is the same as contiguous eviction
+        if *stride < 2 {
+            e.panic_with_error(Error::InvalidAmount);
+        }
+    }
+    e.storage().instance().set(&CACHE_EVICTION_KEY, policy);
+}
+
+#[inline]
+pub fn get_storage_first(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&STORAGE_FIRST_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_storage_first(e: &Env, storage_first: bool) {
+    e.storage()
+        .instance()
+        .set(&STORAGE_FIRST_KEY, &storage_first);
+}
+
+#[inline]
+pub fn get_read_bump(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&READ_BUMP_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_read_bump(e: &Env, read_bump: bool) {
+    e.storage().instance().set(&READ_BUMP_KEY, &read_bump);
+}
+
+#[inline]
+pub fn get_single_asset_layout(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&SINGLE_ASSET_LAYOUT_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_single_asset_layout(e: &Env, single_asset_layout: bool) {
+    e.storage()
+        .instance()
+        .set(&SINGLE_ASSET_LAYOUT_KEY, &single_asset_layout);
+}
+
+#[inline]
+pub fn get_max_price(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&MAX_PRICE_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_max_price(e: &Env, max_price: i128) {
+    if max_price < 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage().instance().set(&MAX_PRICE_KEY, &max_price);
+}
+
+#[inline]
+pub fn get_placeholder_price(e: &Env) -> Option<i128> {
+    e.storage().instance().get(&PLACEHOLDER_PRICE_KEY)
+}
+
+#[inline]
+pub fn set_placeholder_price(e: &Env, placeholder_price: i128) {
+    if placeholder_price < 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    if placeholder_price == 0 {
+        e.storage().instance().remove(&PLACEHOLDER_PRICE_KEY);
+    } else {
+        e.storage()
+            .instance()
+            .set(&PLACEHOLDER_PRICE_KEY, &placeholder_price);
+    }
+}
+
+#[inline]
+pub fn get_max_batch_size(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&MAX_BATCH_SIZE_KEY)
+        .unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+}
+
+#[inline]
+pub fn set_max_batch_size(e: &Env, max_batch_size: u32) {
+    if max_batch_size == 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage()
+        .instance()
+        .set(&MAX_BATCH_SIZE_KEY, &max_batch_size);
+}
+
+#[inline]
+pub fn get_max_records(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&MAX_RECORDS_KEY)
+        .unwrap_or(DEFAULT_MAX_RECORDS)
+}
+
+#[inline]
+pub fn set_max_records(e: &Env, max_records: u32) {
+    if max_records == 0 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage().instance().set(&MAX_RECORDS_KEY, &max_records);
+}
+
+#[inline]
+pub fn get_min_twap_records(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&MIN_TWAP_RECORDS_KEY)
+        .unwrap_or_default()
+}
+
+#[inline]
+pub fn set_min_twap_records(e: &Env, min_records: u32) {
+    e.storage()
+        .instance()
+        .set(&MIN_TWAP_RECORDS_KEY, &min_records);
+}
+
+#[inline]
+pub fn get_paused(e: &Env) -> bool {
+    e.storage().instance().get(&PAUSED_KEY).unwrap_or_default()
+}
+
+#[inline]
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage().instance().set(&PAUSED_KEY, &paused);
+}
+
+#[inline]
+pub fn get_deviation_limits(e: &Env) -> Option<(u32, u32)> {
+    e.storage().instance().get(&DEVIATION_LIMITS_KEY)
+}
+
+#[inline]
+pub fn set_deviation_limits(e: &Env, reject_bps: u32, pause_bps: u32) {
+    if reject_bps == 0 && pause_bps == 0 {
+        e.storage().instance().remove(&DEVIATION_LIMITS_KEY);
+        return;
+    }
+    //severe threshold should be distinct from the reject threshold
+    if reject_bps == 0 || pause_bps <= reject_bps {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage()
+        .instance()
+        .set(&DEVIATION_LIMITS_KEY, &(reject_bps, pause_bps));
+}
+
+#[inline]
+pub fn get_min_coverage(e: &Env) -> Option<(u32, u32)> {
+    e.storage().instance().get(&MIN_COVERAGE_KEY)
+}
+
+#[inline]
+pub fn set_min_coverage(e: &Env, min_updates: u32, periods: u32) {
+    if min_updates == 0 && periods == 0 {
+        e.storage().instance().remove(&MIN_COVERAGE_KEY);
+        return;
+    }
+    //history mask tracks only 256 recent periods
+    if min_updates == 0 || min_updates > periods || periods > 256 {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage()
+        .instance()
+        .set(&MIN_COVERAGE_KEY, &(min_updates, periods));
+}
+
+#[inline]
+pub fn get_fee_split(e: &Env) -> Option<(u32, u32, Address)> {
+    e.storage().instance().get(&FEE_SPLIT_KEY)
+}
+
+#[inline]
+pub fn set_fee_split(e: &Env, burn_bps: u32, treasury_bps: u32, treasury: &Address) {
+    if burn_bps.checked_add(treasury_bps) != Some(10_000) {
+        e.panic_with_error(Error::InvalidAmount);
+    }
+    e.storage()
+        .instance()
+        .set(&FEE_SPLIT_KEY, &(burn_bps, treasury_bps, treasury.clone()));
+}
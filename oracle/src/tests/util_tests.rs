@@ -3,7 +3,7 @@ extern crate std;
 
 use soroban_sdk::{log, Bytes, Env, Vec};
 
-use crate::{mapping, prices};
+use crate::{mapping, prices, timestamps};
 use std::panic::{self, AssertUnwindSafe};
 
 fn generate_update_record_mask(e: &Env, updates: &Vec<i128>) -> Bytes {
@@ -38,24 +38,33 @@ fn fixed_div_floor_tests() {
         (-1, -1, -1),
     ];
 
+    let e = Env::default();
     for (a, b, expected) in test_cases.iter() {
-        let result = panic::catch_unwind(AssertUnwindSafe(|| {
-            prices::fixed_div_floor(a.clone(), *b, 14)
-        }));
+        let result =
+            panic::catch_unwind(AssertUnwindSafe(|| prices::fixed_div_floor(&e, *a, *b, 14)));
         if expected == &-1 {
             assert!(result.is_err());
         } else {
             assert_eq!(result.unwrap(), *expected);
         }
+        let result = prices::try_fixed_div_floor(*a, *b, 14);
+        assert_eq!(result.unwrap_or(-1), *expected);
     }
 }
 
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn fixed_div_floor_zero_divisor_test() {
+    let e = Env::default();
+    prices::fixed_div_floor(&e, 1, 0, 14);
+}
+
 #[test]
 fn position_encoding_bitmask_test() {
     let e = Env::default();
     let mut mask = Bytes::new(&e);
     let total_assets = 5;
-    let mut total_periods = 130;
+    let mut total_periods: u32 = 130;
     for period in 0..total_periods {
         let mut updates = Vec::new(&e);
         for asset_index in 0..total_assets {
@@ -70,16 +79,12 @@ fn position_encoding_bitmask_test() {
     log!(&e, "entire mask", mask);
 
     //check previous prices
-    let period_diff = if total_periods > 255 {
-        total_periods - 255
-    } else {
-        0
-    };
+    let period_diff = total_periods.saturating_sub(255);
     total_periods = std::cmp::min(total_periods, 255);
     for period in 0..total_periods {
         let check_period = total_periods - period - 1;
         for asset_index in 0..total_assets {
-            let expected = asset_index > 0 && ((period + period_diff) % asset_index == 0);
+            let expected = asset_index > 0 && (period + period_diff).is_multiple_of(asset_index);
             let found = mapping::check_history_updated(&mask, asset_index, check_period);
             assert_eq!(found, expected);
         }
@@ -110,3 +115,38 @@ fn update_record_bitmask_test() {
         }
     }
 }
+
+#[test]
+fn history_mask_record_size_test() {
+    let e = Env::default();
+    e.cost_estimate().budget().reset_unlimited();
+    let total_assets = 4u32;
+    let mut mask = Bytes::new(&e);
+    for period in 0..300u32 {
+        let mut updates = Vec::new(&e);
+        for asset_index in 0..total_assets {
+            //first asset is always updated to fill the entire mask, last one never
+            let price = match asset_index {
+                0 => 1,
+                3 => 0,
+                _ => (period % (asset_index + 1) == 0) as i128,
+            };
+            updates.push_back(price);
+        }
+        mask = mapping::update_history_mask(&e, mask, &updates);
+        //every asset slice retains exactly 32 bytes
+        assert_eq!(mask.len(), total_assets * 32);
+    }
+    for period in 0..256 {
+        assert!(mapping::check_history_updated(&mask, 0, period));
+        assert!(!mapping::check_history_updated(&mask, 3, period));
+    }
+}
+
+#[test]
+fn time_units_conversion_test() {
+    assert_eq!(timestamps::to_seconds(300_000), 300);
+    assert_eq!(timestamps::to_seconds(300_999), 300);
+    assert_eq!(timestamps::to_milliseconds(300), 300_000);
+    assert_eq!(timestamps::days_to_milliseconds(1), 86_400_000);
+}
@@ -1,9 +1,16 @@
 use crate::settings;
 use soroban_sdk::Env;
 
+const MILLISECONDS_PER_SECOND: u64 = 1000;
+const LEDGER_CLOSE_TIME: u64 = 5_000; //assumed average ledger close time (in milliseconds)
+
 // Normalize timestamp trimming it to the timeframe resolution defined in settings
 pub fn normalize(e: &Env, value: u64) -> u64 {
-    let timeframe = settings::get_resolution(e) as u64;
+    normalize_to(value, settings::get_resolution(e) as u64)
+}
+
+// Normalize timestamp trimming it to the given timeframe
+pub fn normalize_to(value: u64, timeframe: u64) -> u64 {
     if value == 0 || timeframe == 0 {
         return 0;
     }
@@ -17,10 +24,37 @@ pub fn is_valid(e: &Env, value: u64) -> bool {
 
 // Convert days to milliseconds
 pub fn days_to_milliseconds(days: u32) -> u64 {
-    (days as u64) * 24 * 60 * 60 * 1000
+    to_milliseconds((days as u64) * 24 * 60 * 60)
+}
+
+// Convert milliseconds (internal time unit) to seconds (public API time unit)
+#[inline]
+pub fn to_seconds(value: u64) -> u64 {
+    value / MILLISECONDS_PER_SECOND
+}
+
+// Convert seconds (public API time unit) to milliseconds (internal time unit)
+#[inline]
+pub fn to_milliseconds(value: u64) -> u64 {
+    value * MILLISECONDS_PER_SECOND
+}
+
+// Whether the value (in milliseconds) represents a whole number of seconds
+#[inline]
+pub fn is_whole_seconds(value: u64) -> bool {
+    value.is_multiple_of(MILLISECONDS_PER_SECOND)
 }
 
 // Get timestamp for current ledger
 pub fn ledger_timestamp(e: &Env) -> u64 {
-    e.ledger().timestamp() * 1000 //convert to milliseconds
+    to_milliseconds(e.ledger().timestamp())
+}
+
+// Approximate close timestamp of the ledger sequence assuming constant ledger close time
+pub fn ledger_sequence_to_timestamp(e: &Env, sequence: u32) -> Option<u64> {
+    let ledger = e.ledger().sequence();
+    if sequence > ledger {
+        return None; //ledger hasn't been closed yet
+    }
+    ledger_timestamp(e).checked_sub((ledger - sequence) as u64 * LEDGER_CLOSE_TIME)
 }
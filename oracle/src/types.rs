@@ -58,8 +58,26 @@ pub struct PriceUpdate {
     pub mask: Bytes,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+// Status of the most recent price for an asset
+pub enum PriceStatus {
+    Ok(PriceData), // Most recent price record
+    Stale,         // Oracle hasn't received updates recently
+    Unsupported,   // Asset is not supported
+    NoData,        // No price record for the asset in the most recent update
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+// Policy applied when the price records cache exceeds its size
+pub enum CacheEvictionPolicy {
+    Contiguous,   // Evict the oldest record, keeping the newest contiguous block
+    Strided(u32), // Evict records not aligned to the stride (in rounds) first, keeping the most recent one
+}
+
 #[contracterror]
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 // Standard contract errors
 pub enum Error {
     // Contract already initialized
@@ -80,4 +98,28 @@ pub enum Error {
     InvalidAmount = 7,
     // Prices update is invalid
     InvalidPricesUpdate = 8,
+    // Price deviates from the reference price more than allowed
+    DeviationExceeded = 9,
+    // Price is outside of configured bounds
+    PriceOutOfBounds = 10,
+    // Price record not found
+    NoPrice = 11,
+    // No assets provided
+    NoAssets = 12,
+    // Too many price updates in a single batch
+    BatchSizeExceeded = 13,
+    // Last update timestamp doesn't match the expected one
+    StaleUpdate = 14,
+    // Price updates are paused
+    Paused = 15,
+    // Division arguments are not positive
+    InvalidDivision = 16,
+    // TWAP window is shorter than the configured minimum
+    TwapWindowTooShort = 17,
+    // History retention period is too short to extend price records TTL
+    RetentionPeriodTooShort = 18,
+    // Prices update doesn't contain any non-zero price
+    ZeroPrices = 19,
+    // Asset alias would point to another alias or be referenced as a canonical asset
+    ChainedAlias = 20,
 }
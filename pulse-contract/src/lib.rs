@@ -2,8 +2,10 @@
 mod tests;
 
 use oracle::price_oracle::PriceOracleContractBase;
-use oracle::types::{Asset, ConfigData, FeeConfig, PriceData, PriceUpdate};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use oracle::types::{
+    Asset, CacheEvictionPolicy, ConfigData, Error, FeeConfig, PriceData, PriceUpdate,
+};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Symbol, Vec};
 
 const INITIAL_EXPIRATION_PERIOD: u32 = 180; //6 months
 #[contract]
@@ -20,6 +22,33 @@ impl PulseOracleContract {
         PriceOracleContractBase::base(e)
     }
 
+    // Return base asset contract address
+    //
+    // # Returns
+    //
+    // Base asset address or None if the base is not a Stellar asset
+    pub fn base_address(e: &Env) -> Option<Address> {
+        PriceOracleContractBase::base_address(e)
+    }
+
+    // Return base asset symbol
+    //
+    // # Returns
+    //
+    // Base asset symbol or None if the base is a Stellar asset
+    pub fn base_symbol(e: &Env) -> Option<Symbol> {
+        PriceOracleContractBase::base_symbol(e)
+    }
+
+    // Return unit price of the base asset, only available while the oracle is fresh
+    //
+    // # Returns
+    //
+    // Base asset price (10^decimals) at the most recent record timestamp or None if the oracle is stale
+    pub fn base_price(e: &Env) -> Option<PriceData> {
+        PriceOracleContractBase::base_price(e)
+    }
+
     // Return number of decimal places used to represent price for all quoted assets
     //
     // # Returns
@@ -38,6 +67,15 @@ impl PulseOracleContract {
         PriceOracleContractBase::resolution(e)
     }
 
+    // Return default tick period timeframe (in milliseconds)
+    //
+    // # Returns
+    //
+    // Price feed resolution (in milliseconds), as used for history record keys
+    pub fn resolution_ms(e: &Env) -> u32 {
+        PriceOracleContractBase::resolution_ms(e)
+    }
+
     // Return historical records retention period (in seconds)
     //
     // # Returns
@@ -47,6 +85,27 @@ impl PulseOracleContract {
         PriceOracleContractBase::history_retention_period(e)
     }
 
+    // Return historical records retention period (in milliseconds)
+    //
+    // # Returns
+    //
+    // History retention period (in milliseconds)
+    pub fn history_retention_period_ms(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::history_retention_period_ms(e)
+    }
+
+    // Return history retention period and whether it is enforced
+    //
+    // Retention period controls how long price records are kept in the storage,
+    // while asset expiration only reflects the price feed payment status
+    //
+    // # Returns
+    //
+    // History retention period (in seconds) and true if the retention is enforced (non-zero period)
+    pub fn retention_info(e: &Env) -> (u64, bool) {
+        PriceOracleContractBase::retention_info(e)
+    }
+
     // Return price records cache size
     //
     // # Returns
@@ -56,285 +115,1371 @@ impl PulseOracleContract {
         PriceOracleContractBase::cache_size(e)
     }
 
-    // Return all quoted assets
+    // Return policy applied when the price records cache exceeds its size
     //
     // # Returns
     //
-    // Quoted assets
-    pub fn assets(e: &Env) -> Vec<Asset> {
-        PriceOracleContractBase::assets(e)
+    // Cache eviction policy
+    pub fn cache_eviction_policy(e: &Env) -> CacheEvictionPolicy {
+        PriceOracleContractBase::cache_eviction_policy(e)
     }
 
-    // Return most recent price update timestamp in seconds
+    // Return timestamps of price records held in the instance cache
     //
     // # Returns
     //
-    // Timestamp of last recorded price update
-    pub fn last_timestamp(e: &Env) -> u64 {
-        PriceOracleContractBase::last_timestamp(e)
+    // Cached records timestamps (in seconds), most recent first
+    pub fn cache_records(e: &Env) -> Vec<u64> {
+        PriceOracleContractBase::cache_records(e)
     }
 
-    // Return current contract protocol version
+    // Return whether missing prices cause errors instead of None results
     //
     // # Returns
     //
-    // Contract protocol version
-    pub fn version(e: &Env) -> u32 {
-        PriceOracleContractBase::version(e)
+    // Strict mode flag
+    pub fn strict_mode(e: &Env) -> bool {
+        PriceOracleContractBase::strict_mode(e)
     }
 
-    // Return expiration date for a given asset
+    // Return whether history records are read from the temporary storage before the cache
     //
-    // # Arguments
+    // # Returns
     //
-    // * `asset` - Quoted asset
+    // Storage-first read strategy flag
+    pub fn storage_first(e: &Env) -> bool {
+        PriceOracleContractBase::storage_first(e)
+    }
+
+    // Return feeder public keys allowed to sign price attestations
     //
     // # Returns
     //
-    // Asset expiration timestamp or None if asset is not supported
+    // Feeder ed25519 public keys with the feeder addresses they are linked to
+    pub fn feeder_keys(e: &Env) -> Vec<(BytesN<32>, Address)> {
+        PriceOracleContractBase::feeder_keys(e)
+    }
+
+    // Return allowed feeders with the time of their last price update
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if asset is not supported
-    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
-        PriceOracleContractBase::expires(e, asset)
+    // Feeder addresses with last activity timestamps (in seconds, zero if never submitted)
+    pub fn feeder_activity(e: &Env) -> Vec<(Address, u64)> {
+        PriceOracleContractBase::feeder_activity(e)
     }
 
-    // Extends the asset expiration date by a given amount of tokens.
+    // Return timestamps of price records whose storage TTL is estimated to end within the given number of ledgers
     //
     // # Arguments
     //
-    // * `sponsor` - Address that sponsors price feed
-    // * `asset` - Quoted asset
-    // * `amount` - Amount of tokens to burn for extending the expiration date
+    // * `within_ledgers` - Number of ledgers to look ahead
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if the asset is not supported or if retention config is malformed/missing
-    pub fn extend_asset_ttl(e: &Env, sponsor: Address, asset: Asset, amount: i128) {
-        PriceOracleContractBase::extend_asset_ttl(
-            e,
-            sponsor,
-            asset,
-            amount,
-            INITIAL_EXPIRATION_PERIOD,
-        );
+    // Timestamps (in seconds) of the records at risk of expiration
+    pub fn records_at_risk(e: &Env, within_ledgers: u32) -> Vec<u64> {
+        PriceOracleContractBase::records_at_risk(e, within_ledgers)
     }
 
-    // Return the fee token address daily price feed retainer fee amount
+    // Return whether price updates are paused
     //
     // # Returns
     //
-    // Fee token address and daily price feed retainer fee amount
-    pub fn fee_config(e: &Env) -> FeeConfig {
-        PriceOracleContractBase::fee_config(e)
+    // Paused flag
+    pub fn paused(e: &Env) -> bool {
+        PriceOracleContractBase::paused(e)
     }
 
-    // Return contract admin address
+    // Return price deviation limits applied to new price updates
     //
     // # Returns
     //
-    // Contract admin account address
-    pub fn admin(e: &Env) -> Option<Address> {
-        PriceOracleContractBase::admin(e)
+    // Reject and auto-pause thresholds (in basis points) or None if not set
+    pub fn deviation_limits(e: &Env) -> Option<(u32, u32)> {
+        PriceOracleContractBase::deviation_limits(e)
     }
 
-    // Returns price  for an asset at specific timestamp
+    // Return minimum number of recent updates required to serve asset prices
+    //
+    // # Returns
+    //
+    // Required number of updates and the number of recent periods to check, or None if not set
+    pub fn min_coverage(e: &Env) -> Option<(u32, u32)> {
+        PriceOracleContractBase::min_coverage(e)
+    }
+
+    // Return minimum number of records TWAP requests should span
+    //
+    // # Returns
+    //
+    // Minimum TWAP window (0 if not set)
+    pub fn min_twap_records(e: &Env) -> u32 {
+        PriceOracleContractBase::min_twap_records(e)
+    }
+
+    // Return maximum number of price records returned by a single query
+    //
+    // # Returns
+    //
+    // Maximum number of records
+    pub fn max_records(e: &Env) -> u32 {
+        PriceOracleContractBase::max_records(e)
+    }
+
+    // Return maximum number of price snapshots accepted in a single batch
+    //
+    // # Returns
+    //
+    // Maximum batch size
+    pub fn max_batch_size(e: &Env) -> u32 {
+        PriceOracleContractBase::max_batch_size(e)
+    }
+
+    // Return whether price record TTLs are extended on reads
+    //
+    // # Returns
+    //
+    // Read bump flag
+    pub fn read_bump(e: &Env) -> bool {
+        PriceOracleContractBase::read_bump(e)
+    }
+
+    // Return placeholder price served for newly added assets until their first price update
+    //
+    // # Returns
+    //
+    // Placeholder price or None if placeholder prices are disabled
+    pub fn placeholder_price(e: &Env) -> Option<i128> {
+        PriceOracleContractBase::placeholder_price(e)
+    }
+
+    // Return maximum allowed price value
+    //
+    // # Returns
+    //
+    // Maximum price or 0 if not limited
+    pub fn max_price(e: &Env) -> i128 {
+        PriceOracleContractBase::max_price(e)
+    }
+
+    // Return all quoted assets
+    //
+    // # Returns
+    //
+    // Quoted assets, purged assets are omitted even before their slots are compacted
+    pub fn assets(e: &Env) -> Vec<Asset> {
+        PriceOracleContractBase::assets(e)
+    }
+
+    // Return configuration snapshot for off-chain backup
+    //
+    // # Returns
+    //
+    // Quoted assets, asset expiration timestamps, most recent price update timestamp (in seconds) and fee config
+    pub fn export_snapshot(e: &Env) -> (Vec<Asset>, Vec<u64>, u64, FeeConfig) {
+        PriceOracleContractBase::export_snapshot(e)
+    }
+
+    // Return assets updated in the price record at specific timestamp
     //
     // # Arguments
     //
-    // * `asset` - Asset to quote
     // * `timestamp` - Timestamp in seconds
     //
     // # Returns
     //
-    // Price record for given asset at given timestamp or None if not found
-    pub fn price(e: &Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
-        PriceOracleContractBase::price(e, asset, timestamp)
+    // Assets included in the update record or empty vector if record not found
+    pub fn updated_assets_at(e: &Env, timestamp: u64) -> Vec<Asset> {
+        PriceOracleContractBase::updated_assets_at(e, timestamp)
     }
 
-    // Returns most recent price for an asset
+    // Return assets omitted from the most recent price update
+    //
+    // # Returns
+    //
+    // Assets without a price in the most recent period or empty vector if all assets were updated
+    pub fn missing_in_latest(e: &Env) -> Vec<Asset> {
+        PriceOracleContractBase::missing_in_latest(e)
+    }
+
+    // Return the most recent timestamps that have price records for given asset
     //
     // # Arguments
     //
     // * `asset` - Asset to quote
+    // * `limit` - Maximum number of timestamps to return (capped at 256)
     //
     // # Returns
     //
-    // Most recent price for given asset or None if asset is not supported
-    pub fn lastprice(e: &Env, asset: Asset) -> Option<PriceData> {
-        PriceOracleContractBase::lastprice(e, asset)
+    // Timestamps in seconds starting from the most recent one or empty vector if asset is not supported
+    pub fn asset_timestamps(e: &Env, asset: Asset, limit: u32) -> Vec<u64> {
+        PriceOracleContractBase::asset_timestamps(e, asset, limit)
     }
 
-    // Return last N price records for given asset
+    // Return the oldest timestamp tracked in the history that has price record for given asset
     //
     // # Arguments
     //
     // * `asset` - Asset to quote
-    // * `records` - Number of records to return
     //
     // # Returns
     //
-    // Prices for given asset or None if asset is not supported
-    pub fn prices(e: &Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
-        PriceOracleContractBase::prices(e, asset, records)
+    // Oldest timestamp in seconds or None if there are no records or asset is not supported
+    pub fn oldest_timestamp(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::oldest_timestamp(e, asset)
     }
 
-    // Returns most recent cross price record for pair of assets
+    // Return the number of periods with price records for given asset within the 256 periods tracked in the history
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
+    // * `asset` - Asset to quote
     //
     // # Returns
     //
-    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
-    pub fn x_last_price(e: &Env, base_asset: Asset, quote_asset: Asset) -> Option<PriceData> {
-        PriceOracleContractBase::x_last_price(e, base_asset, quote_asset)
+    // Number of populated periods or 0 if asset is not supported
+    pub fn populated_depth(e: &Env, asset: Asset) -> u32 {
+        PriceOracleContractBase::populated_depth(e, asset)
     }
 
-    // Return cross price for pair of assets at specific timestamp
+    // Run all checks performed on price update submission without storing the update
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `timestamp` - Timestamp
+    // * `update` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `backfill` - Validate the update as `backfill_price` submission
     //
     // # Returns
     //
-    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
-    pub fn x_price(
+    // Error that would be raised by `set_price` (or `backfill_price` in backfill mode) for this update, if any,
+    // `Paused` is also returned for updates that would pause the contract due to severe price deviation
+    pub fn validate_update(
         e: &Env,
-        base_asset: Asset,
-        quote_asset: Asset,
+        update: PriceUpdate,
         timestamp: u64,
-    ) -> Option<PriceData> {
-        PriceOracleContractBase::x_price(e, base_asset, quote_asset, timestamp)
+        backfill: bool,
+    ) -> Result<(), Error> {
+        PriceOracleContractBase::validate_update(e, update, timestamp, backfill)
     }
 
-    // Returns last N cross price records of for pair of assets
+    // Preview the history mask after applying a hypothetical price update, without modifying the contract state
     //
     // # Arguments
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to fetch
+    // * `current` - Current history mask
+    // * `prices` - Prices for all assets, zero for assets without updates
     //
     // # Returns
     //
-    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
-    pub fn x_prices(
-        e: &Env,
-        base_asset: Asset,
-        quote_asset: Asset,
-        records: u32,
-    ) -> Option<Vec<PriceData>> {
-        PriceOracleContractBase::x_prices(e, base_asset, quote_asset, records)
+    // Updated history mask
+    pub fn preview_history_mask(e: &Env, current: Bytes, prices: Vec<i128>) -> Bytes {
+        PriceOracleContractBase::preview_history_mask(e, current, prices)
     }
 
-    // Returns time-weighted average price for given asset over N recent records
+    // Count assets marked as updated in the price update mask
     //
     // # Arguments
     //
-    // * `asset` - Asset to quote
-    // * `records` - Number of records to process
+    // * `mask` - Price update record mask
     //
     // # Returns
     //
-    // TWAP for the given asset over N recent records or None if asset is not supported
-    pub fn twap(e: &Env, asset: Asset, records: u32) -> Option<i128> {
-        PriceOracleContractBase::twap(e, asset, records)
+    // Number of set bits, expected to match the number of prices in the update
+    pub fn mask_popcount(e: &Env, mask: Bytes) -> u32 {
+        PriceOracleContractBase::mask_popcount(e, mask)
     }
 
-    // Returns time-weighted average cross price for given asset pair over N recent records
+    // Return most recent price update timestamp in seconds
     //
-    // # Arguments
+    // # Returns
     //
-    // * `base_asset` - Base asset
-    // * `quote_asset` - Quote asset
-    // * `records` - Number of records to process
+    // Timestamp of last recorded price update
+    pub fn last_timestamp(e: &Env) -> u64 {
+        PriceOracleContractBase::last_timestamp(e)
+    }
+
+    // Return most recent price update timestamp in milliseconds
     //
     // # Returns
     //
-    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported
-    pub fn x_twap(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> Option<i128> {
-        PriceOracleContractBase::x_twap(e, base_asset, quote_asset, records)
+    // Raw timestamp of last recorded price update, as used for history record keys
+    pub fn last_timestamp_ms(e: &Env) -> u64 {
+        PriceOracleContractBase::last_timestamp_ms(e)
     }
 
-    /* Admin section */
-
-    // Initializes contract configuration
-    // Requires admin authorization
-    // # Arguments
+    // Return time remaining until the next expected price update
     //
-    // * `config` - Configuration parameters
+    // # Returns
     //
-    // # Panics
+    // Seconds until the next update is due (0 if it is already overdue) or None if there is no fresh data
+    pub fn next_update_eta(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::next_update_eta(e)
+    }
+
+    // Return current contract protocol version
     //
-    // Panics if not authorized or if contract is already initialized
-    pub fn config(e: &Env, config: ConfigData) {
-        PriceOracleContractBase::config(e, config, INITIAL_EXPIRATION_PERIOD);
+    // # Returns
+    //
+    // Contract protocol version
+    pub fn version(e: &Env) -> u32 {
+        PriceOracleContractBase::version(e)
     }
 
-    // Update contract cache size
-    // Requires admin authorization
+    // Return expiration date for a given asset
     //
     // # Arguments
     //
-    // * `cache_size` - New cache size (number of rounds stored in cache)
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in milliseconds) or None if asset has no expiration record
     //
     // # Panics
     //
-    // Panics if not authorized
-    pub fn set_cache_size(e: &Env, cache_size: u32) {
-        PriceOracleContractBase::set_cache_size(e, cache_size);
+    // Panics if asset is not supported
+    pub fn expires(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::expires(e, asset)
     }
 
-    // Adds given assets to the contract quoted assets list
-    // Requires admin authorization
+    // Return expiration date for a given asset (in seconds)
     //
     // # Arguments
     //
-    // * `assets` - Assets to add
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset expiration timestamp (in seconds) or None if asset has no expiration record
     //
     // # Panics
     //
-    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
-    pub fn add_assets(e: &Env, assets: Vec<Asset>) {
-        PriceOracleContractBase::add_assets(e, assets, INITIAL_EXPIRATION_PERIOD);
+    // Panics if asset is not supported
+    pub fn expires_s(e: &Env, asset: Asset) -> Option<u64> {
+        PriceOracleContractBase::expires_s(e, asset)
     }
 
-    // Sets history retention period for the prices
-    // Requires admin authorization
+    // Return expiration timestamps for the given assets
     //
     // # Arguments
     //
-    // * `period` - History retention period (in seconds)
+    // * `assets` - Quoted assets
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized
-    pub fn set_history_retention_period(e: &Env, period: u64) {
-        PriceOracleContractBase::set_history_retention_period(e, period);
+    // Expiration timestamps (in seconds) aligned with the input, None for unknown assets or assets without expiration record
+    pub fn expirations_of(e: &Env, assets: Vec<Asset>) -> Vec<Option<u64>> {
+        PriceOracleContractBase::expirations_of(e, assets)
     }
 
-    // Set fee token address and daily price feed retainer fee amount
-    // Requires admin authorization
+    // Return time remaining until the asset price feed expiration
     //
     // # Arguments
     //
-    // * `fee_config` - Fee token address and fee amount
+    // * `asset` - Quoted asset
     //
-    // # Panics
+    // # Returns
     //
-    // Panics if not authorized or not initialized yet
-    pub fn set_fee_config(e: &Env, fee_config: FeeConfig) {
-        PriceOracleContractBase::set_fee_config(e, fee_config, INITIAL_EXPIRATION_PERIOD);
+    // Seconds until expiration (negative if already expired) or None if asset has no expiration record
+    pub fn ttl_remaining(e: &Env, asset: Asset) -> Option<i128> {
+        PriceOracleContractBase::ttl_remaining(e, asset)
     }
 
-    // Record new price feed history snapshot
+    // Return whether the asset price feed is not expired and has a price in the most recent record
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // True if the feed is active, false otherwise or if asset is not supported
+    pub fn is_feed_active(e: &Env, asset: Asset) -> bool {
+        PriceOracleContractBase::is_feed_active(e, asset)
+    }
+
+    // Return expiration, latest price, and active flag for the asset price feed
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Expiration timestamp (in seconds), most recent price, and active flag
+    pub fn feed_summary(e: &Env, asset: Asset) -> (Option<u64>, Option<PriceData>, bool) {
+        PriceOracleContractBase::feed_summary(e, asset)
+    }
+
+    // Extends the asset expiration date by a given amount of tokens.
+    //
+    // # Arguments
+    //
+    // * `sponsor` - Address that sponsors price feed
+    // * `asset` - Quoted asset
+    // * `amount` - Amount of tokens to burn for extending the expiration date
+    //
+    // # Panics
+    //
+    // Panics if the asset is not supported or if retention config is malformed/missing
+    pub fn extend_asset_ttl(e: &Env, sponsor: Address, asset: Asset, amount: i128) {
+        PriceOracleContractBase::extend_asset_ttl(
+            e,
+            sponsor,
+            asset,
+            amount,
+            INITIAL_EXPIRATION_PERIOD,
+        );
+    }
+
+    // Return whether fee config has been explicitly set rather than defaulted
+    //
+    // # Returns
+    //
+    // True if fee config present in the contract storage
+    pub fn fee_config_explicitly_set(e: &Env) -> bool {
+        PriceOracleContractBase::fee_config_explicitly_set(e)
+    }
+
+    // Return human-readable label for a given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset label or None if not set or asset is not supported
+    pub fn asset_label(e: &Env, asset: Asset) -> Option<Symbol> {
+        PriceOracleContractBase::asset_label(e, asset)
+    }
+
+    // Return minimum price of the asset accepted as a cross-price denominator
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Price floor (zero if not set) or None if asset is not supported
+    pub fn asset_price_floor(e: &Env, asset: Asset) -> Option<i128> {
+        PriceOracleContractBase::asset_price_floor(e, asset)
+    }
+
+    // Return canonical asset the given asset is registered as an alias of
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to check
+    //
+    // # Returns
+    //
+    // Canonical asset or None if no alias is configured
+    pub fn asset_alias(e: &Env, asset: Asset) -> Option<Asset> {
+        PriceOracleContractBase::asset_alias(e, asset)
+    }
+
+    // Return price feed resolution for a given asset (in seconds)
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    //
+    // # Returns
+    //
+    // Asset resolution override or global resolution if not set, None if asset is not supported
+    pub fn asset_resolution(e: &Env, asset: Asset) -> Option<u32> {
+        PriceOracleContractBase::asset_resolution(e, asset)
+    }
+
+    // Return scheduled fee config change
+    //
+    // # Returns
+    //
+    // Pending fee config and the timestamp (in seconds) it can be applied at, or None if nothing is scheduled
+    pub fn pending_fee_config(e: &Env) -> Option<(FeeConfig, u64)> {
+        PriceOracleContractBase::pending_fee_config(e)
+    }
+
+    // Return timestamp of the last fee token migration
+    //
+    // # Returns
+    //
+    // Migration timestamp (in seconds) or None if fee token has never been migrated
+    pub fn fee_migration_timestamp(e: &Env) -> Option<u64> {
+        PriceOracleContractBase::fee_migration_timestamp(e)
+    }
+
+    // Return fee split between burn and treasury transfer
+    //
+    // # Returns
+    //
+    // Burn share, treasury share (in basis points), and treasury address or None if all fees are burned
+    pub fn fee_split(e: &Env) -> Option<(u32, u32, Address)> {
+        PriceOracleContractBase::fee_split(e)
+    }
+
+    // Return the oracle contract balance of the configured fee token
+    //
+    // # Returns
+    //
+    // Fee token balance or 0 if fee config is not set
+    pub fn fee_balance(e: &Env) -> i128 {
+        PriceOracleContractBase::fee_balance(e)
+    }
+
+    // Check whether both assets of the pair are supported by the oracle
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // True if both assets are supported
+    pub fn pair_supported(e: &Env, base_asset: Asset, quote_asset: Asset) -> bool {
+        PriceOracleContractBase::pair_supported(e, base_asset, quote_asset)
+    }
+
+    // Check whether time-weighted average cross price for given asset pair over N recent records is available
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // True if both assets have fresh records for the entire window
+    // (false for windows shorter than the minimum TWAP window)
+    pub fn x_twap_available(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> bool {
+        PriceOracleContractBase::x_twap_available(e, base_asset, quote_asset, records)
+    }
+
+    // Return the fee token address daily price feed retainer fee amount
+    //
+    // # Returns
+    //
+    // Fee token address and daily price feed retainer fee amount
+    pub fn fee_config(e: &Env) -> FeeConfig {
+        PriceOracleContractBase::fee_config(e)
+    }
+
+    // Return total amount of fee tokens burned by the contract
+    //
+    // # Returns
+    //
+    // Cumulative amount of burned fee tokens
+    pub fn total_fees_burned(e: &Env) -> i128 {
+        PriceOracleContractBase::total_fees_burned(e)
+    }
+
+    // Return contract admin address
+    //
+    // # Returns
+    //
+    // Contract admin account address
+    pub fn admin(e: &Env) -> Option<Address> {
+        PriceOracleContractBase::admin(e)
+    }
+
+    // Returns price  for an asset at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found
+    pub fn price(e: &Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        PriceOracleContractBase::price(e, asset, timestamp)
+    }
+
+    // Returns price for an asset at specific timestamp, distinguishing future-dated requests from missing data
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp` - Timestamp in seconds
+    //
+    // # Returns
+    //
+    // Price record for given asset at given timestamp or None if not found,
+    // InvalidTimestamp error if the timestamp is ahead of the current ledger time,
+    // AssetMissing error if asset is not supported
+    pub fn price_checked(
+        e: &Env,
+        asset: Asset,
+        timestamp: u64,
+    ) -> Result<Option<PriceData>, Error> {
+        PriceOracleContractBase::price_checked(e, asset, timestamp)
+    }
+
+    // Returns raw stored price for an asset at specific timestamp, without timestamp conversion
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `timestamp_ms` - Timestamp in milliseconds
+    //
+    // # Returns
+    //
+    // Price exactly as stored in the record for given asset at given timestamp or None if not found
+    pub fn raw_asset_price(e: &Env, asset: Asset, timestamp_ms: u64) -> Option<i128> {
+        PriceOracleContractBase::raw_asset_price(e, asset, timestamp_ms)
+    }
+
+    // Returns most recent price for an asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price for given asset or None if asset is not supported
+    pub fn lastprice(e: &Env, asset: Asset) -> Option<PriceData> {
+        PriceOracleContractBase::lastprice(e, asset)
+    }
+
+    // Returns most recent price for an asset along with the record source, used for cache tuning
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    //
+    // # Returns
+    //
+    // Most recent price or None if not found, and whether the record has been served from the cache
+    pub fn lastprice_debug(e: &Env, asset: Asset) -> (Option<PriceData>, bool) {
+        PriceOracleContractBase::lastprice_debug(e, asset)
+    }
+
+    // Return last N price records for given asset
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to return
+    //
+    // # Returns
+    //
+    // Prices for given asset or None if asset is not supported
+    pub fn prices(e: &Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        PriceOracleContractBase::prices(e, asset, records)
+    }
+
+    // Return price records for given asset within the time range, capped by the max records setting
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    //
+    // # Returns
+    //
+    // Most recent prices within the range or None if asset is not supported
+    pub fn prices_range(
+        e: &Env,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+    ) -> Option<Vec<PriceData>> {
+        PriceOracleContractBase::prices_range(e, asset, from_timestamp, to_timestamp)
+    }
+
+    // Return a page of price records for given asset within the time range
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `from_timestamp` - Range start timestamp (in seconds, inclusive)
+    // * `to_timestamp` - Range end timestamp (in seconds, inclusive)
+    // * `offset` - Number of most recent records within the range to skip
+    //
+    // # Returns
+    //
+    // Prices within the range (at most max records) along with the flag indicating whether
+    // the range contains more records, or None if asset is not supported
+    pub fn prices_range_paged(
+        e: &Env,
+        asset: Asset,
+        from_timestamp: u64,
+        to_timestamp: u64,
+        offset: u32,
+    ) -> Option<(Vec<PriceData>, bool)> {
+        PriceOracleContractBase::prices_range_paged(e, asset, from_timestamp, to_timestamp, offset)
+    }
+
+    // Returns most recent cross price record for pair of assets
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    //
+    // # Returns
+    //
+    // Recent cross price (base_asset_price/quote_asset_price) for given assets or None if there were no records found
+    pub fn x_last_price(e: &Env, base_asset: Asset, quote_asset: Asset) -> Option<PriceData> {
+        PriceOracleContractBase::x_last_price(e, base_asset, quote_asset)
+    }
+
+    // Return cross price for pair of assets at specific timestamp
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamp` - Timestamp
+    //
+    // # Returns
+    //
+    // Cross price (base_asset_price/quote_asset_price) at given timestamp or None if there were no records found for quoted assets
+    pub fn x_price(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamp: u64,
+    ) -> Option<PriceData> {
+        PriceOracleContractBase::x_price(e, base_asset, quote_asset, timestamp)
+    }
+
+    // Return cross prices for pair of assets at multiple timestamps
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `timestamps` - Timestamps (in seconds)
+    //
+    // # Returns
+    //
+    // Cross prices (base_asset_price/quote_asset_price) aligned with the input timestamps, None for missing records
+    pub fn x_prices_at(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        timestamps: Vec<u64>,
+    ) -> Vec<Option<PriceData>> {
+        PriceOracleContractBase::x_prices_at(e, base_asset, quote_asset, timestamps)
+    }
+
+    // Returns last N cross price records of for pair of assets
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to fetch
+    //
+    // # Returns
+    //
+    // Last N cross prices (base_asset_price/quote_asset_price) or None if there were no records found for quoted assets
+    pub fn x_prices(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<Vec<PriceData>> {
+        PriceOracleContractBase::x_prices(e, base_asset, quote_asset, records)
+    }
+
+    // Returns time-weighted average price for given asset over N recent records
+    //
+    // # Arguments
+    //
+    // * `asset` - Asset to quote
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP for the given asset over N recent records or None if asset is not supported
+    pub fn twap(e: &Env, asset: Asset, records: u32) -> Option<i128> {
+        PriceOracleContractBase::twap(e, asset, records)
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported
+    pub fn x_twap(e: &Env, base_asset: Asset, quote_asset: Asset, records: u32) -> Option<i128> {
+        PriceOracleContractBase::x_twap(e, base_asset, quote_asset, records)
+    }
+
+    // Returns time-weighted average cross price for given asset pair over N recent records,
+    // ensuring that both assets have fresh records over the entire window
+    //
+    // # Arguments
+    //
+    // * `base_asset` - Base asset
+    // * `quote_asset` - Quote asset
+    // * `records` - Number of records to process
+    //
+    // # Returns
+    //
+    // TWAP (base_asset_price/quote_asset_price) or None if assets are not supported or any of them is stale
+    pub fn x_twap_checked(
+        e: &Env,
+        base_asset: Asset,
+        quote_asset: Asset,
+        records: u32,
+    ) -> Option<i128> {
+        PriceOracleContractBase::x_twap_checked(e, base_asset, quote_asset, records)
+    }
+
+    /* Admin section */
+
+    // Initializes contract configuration
+    // Requires admin authorization
+    // # Arguments
+    //
+    // * `config` - Configuration parameters
+    //
+    // # Panics
+    //
+    // Panics if not authorized, if contract is already initialized, assets list is empty, resolution is not a whole number of seconds, or retention period is too short
+    pub fn config(e: &Env, config: ConfigData) {
+        PriceOracleContractBase::config(e, config, INITIAL_EXPIRATION_PERIOD);
+    }
+
+    // Update contract cache size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `cache_size` - New cache size (number of rounds stored in cache)
+    //
+    // Growing the cache doesn't populate it with older records, use `warm_cache` for that
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_cache_size(e: &Env, cache_size: u32) {
+        PriceOracleContractBase::set_cache_size(e, cache_size);
+    }
+
+    // Sets policy applied when the price records cache exceeds its size
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `policy` - Cache eviction policy, `Strided` keeps records aligned to the given number of rounds
+    //
+    // # Panics
+    //
+    // Panics if not authorized or stride is less than 2 rounds
+    pub fn set_cache_eviction_policy(e: &Env, policy: CacheEvictionPolicy) {
+        PriceOracleContractBase::set_cache_eviction_policy(e, policy);
+    }
+
+    // Rewrite legacy (protocol 1) price records in the current format, so they are served without the legacy read path
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the legacy records to migrate (in seconds)
+    //
+    // # Returns
+    //
+    // Number of migrated records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the number of timestamps exceeds the max batch size
+    pub fn migrate_v1_records(e: &Env, timestamps: Vec<u64>) -> u32 {
+        PriceOracleContractBase::migrate_v1_records(e, timestamps)
+    }
+
+    // Load price records for given timestamps from the temporary storage into the instance cache
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamps` - Timestamps of the records to cache (in seconds)
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn warm_cache(e: &Env, timestamps: Vec<u64>) {
+        PriceOracleContractBase::warm_cache(e, timestamps);
+    }
+
+    // Remove all cached price records, subsequent reads fall back to the temporary storage
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn clear_cache(e: &Env) {
+        PriceOracleContractBase::clear_cache(e);
+    }
+
+    // Toggle strict mode, when enabled `price`, `lastprice`, and `x_price` panic instead of returning None
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `strict` - Whether strict mode is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_strict_mode(e: &Env, strict: bool) {
+        PriceOracleContractBase::set_strict_mode(e, strict);
+    }
+
+    // Toggle TTL extension on reads, when enabled every read extends TTL of the price record
+    // up to the history retention period (reads become more expensive)
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `read_bump` - Whether TTL extension on reads is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_read_bump(e: &Env, read_bump: bool) {
+        PriceOracleContractBase::set_read_bump(e, read_bump);
+    }
+
+    // Toggle read strategy, when enabled history records are loaded from the temporary storage first,
+    // and the cache is consulted only for records missing there
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `storage_first` - Whether storage-first read strategy is enabled
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_storage_first(e: &Env, storage_first: bool) {
+        PriceOracleContractBase::set_storage_first(e, storage_first);
+    }
+
+    // Adds given assets to the contract quoted assets list
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `assets` - Assets to add
+    //
+    // # Panics
+    //
+    // Panics if not authorized, any of the assets were added earlier, or assets limit exceeded
+    pub fn add_assets(e: &Env, assets: Vec<Asset>) {
+        PriceOracleContractBase::add_assets(e, assets, INITIAL_EXPIRATION_PERIOD);
+    }
+
+    // Removes assets expired more than the grace period ago
+    // Removed asset slots are kept as tombstones, so positions of other assets don't change
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `grace_days` - Grace period after expiration (in days)
+    //
+    // # Returns
+    //
+    // Number of removed assets
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn purge_expired(e: &Env, grace_days: u32) -> u32 {
+        PriceOracleContractBase::purge_expired(e, grace_days)
+    }
+
+    // Compacts live assets to contiguous indexes, removing tombstones of purged assets
+    // Asset indexes, expiration records, per-asset settings, history mask and recent price records are rewritten together
+    // Requires admin authorization
+    //
+    // # Returns
+    //
+    // Number of removed asset slots
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn reindex_assets(e: &Env) -> u32 {
+        PriceOracleContractBase::reindex_assets(e)
+    }
+
+    // Sets human-readable label for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `label` - Asset label
+    //
+    // # Panics
+    //
+    // Panics if not authorized or asset is not supported
+    pub fn set_asset_label(e: &Env, asset: Asset, label: Symbol) {
+        PriceOracleContractBase::set_asset_label(e, asset, label);
+    }
+
+    // Sets human-readable labels for multiple assets at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `entries` - Quoted assets with their labels
+    //
+    // # Panics
+    //
+    // Panics if not authorized or any of the assets is not supported
+    pub fn set_asset_labels(e: &Env, entries: Vec<(Asset, Symbol)>) {
+        PriceOracleContractBase::set_asset_labels(e, entries);
+    }
+
+    // Sets price feed resolution override for the asset
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `resolution` - Asset resolution (in seconds), multiple of the global resolution or zero to reset
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or resolution is not aligned with the global resolution
+    pub fn set_asset_resolution(e: &Env, asset: Asset, resolution: u32) {
+        PriceOracleContractBase::set_asset_resolution(e, asset, resolution);
+    }
+
+    // Sets minimum price of the asset accepted as a cross-price denominator
+    // Cross prices quoted in the asset with a price below the floor are not returned
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `floor` - Minimum denominator price, zero to remove the floor
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported or floor is negative
+    pub fn set_asset_price_floor(e: &Env, asset: Asset, floor: i128) {
+        PriceOracleContractBase::set_asset_price_floor(e, asset, floor);
+    }
+
+    // Registers an alias for the canonical asset, preventing both forms from being listed at once
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `alias` - Alternative form of the asset
+    // * `canonical` - Canonical form of the asset, passing the alias itself removes the mapping
+    //
+    // # Panics
+    //
+    // Panics if not authorized, canonical asset is an alias itself, or alias is a canonical asset of another alias
+    pub fn set_asset_alias(e: &Env, alias: Asset, canonical: Asset) {
+        PriceOracleContractBase::set_asset_alias(e, alias, canonical);
+    }
+
+    // Sets sanity bounds for the asset prices, updates outside of bounds are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `asset` - Quoted asset
+    // * `min` - Minimum allowed price
+    // * `max` - Maximum allowed price
+    //
+    // # Panics
+    //
+    // Panics if not authorized, asset is not supported, or bounds are invalid
+    pub fn set_price_bounds(e: &Env, asset: Asset, min: i128, max: i128) {
+        PriceOracleContractBase::set_price_bounds(e, asset, min, max);
+    }
+
+    // Adds feeder account allowed to submit price updates
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn add_feeder(e: &Env, feeder: Address) {
+        PriceOracleContractBase::add_feeder(e, feeder);
+    }
+
+    // Removes feeder account from the allowed feeders
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn remove_feeder(e: &Env, feeder: Address) {
+        PriceOracleContractBase::remove_feeder(e, feeder);
+    }
+
+    // Adds feeder public key allowed to sign price attestations on behalf of the feeder
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Allowed feeder account the key belongs to
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized or the feeder is not allowed
+    pub fn add_feeder_key(e: &Env, feeder: Address, public_key: BytesN<32>) {
+        PriceOracleContractBase::add_feeder_key(e, feeder, public_key);
+    }
+
+    // Removes feeder public key from the allowed attestation signers
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `public_key` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn remove_feeder_key(e: &Env, public_key: BytesN<32>) {
+        PriceOracleContractBase::remove_feeder_key(e, public_key);
+    }
+
+    // Pauses price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn pause(e: &Env) {
+        PriceOracleContractBase::pause(e);
+    }
+
+    // Resumes price updates
+    // Requires admin authorization
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn unpause(e: &Env) {
+        PriceOracleContractBase::unpause(e);
+    }
+
+    // Sets price deviation limits applied to new price updates
+    // Updates deviating from the last prices more than the reject threshold are rejected,
+    // while updates exceeding the severe threshold pause the contract
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `reject_bps` - Reject threshold (in basis points)
+    // * `pause_bps` - Auto-pause threshold (in basis points), greater than the reject threshold
+    //
+    // # Panics
+    //
+    // Panics if not authorized or thresholds are invalid
+    pub fn set_deviation_limits(e: &Env, reject_bps: u32, pause_bps: u32) {
+        PriceOracleContractBase::set_deviation_limits(e, reject_bps, pause_bps);
+    }
+
+    // Sets minimum number of updates within recent periods required for the asset before
+    // `lastprice` and `twap` return its prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `min_updates` - Required number of updates, zero along with zero periods removes the guard
+    // * `periods` - Number of recent periods to check (up to 256)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or coverage parameters are invalid
+    pub fn set_min_coverage(e: &Env, min_updates: u32, periods: u32) {
+        PriceOracleContractBase::set_min_coverage(e, min_updates, periods);
+    }
+
+    // Sets minimum number of records TWAP requests should span, shorter windows are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `min_records` - Minimum TWAP window, zero disables the check
+    //
+    // # Panics
+    //
+    // Panics if not authorized
+    pub fn set_min_twap_records(e: &Env, min_records: u32) {
+        PriceOracleContractBase::set_min_twap_records(e, min_records);
+    }
+
+    // Sets maximum number of price snapshots accepted in a single batch
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_batch_size` - Maximum batch size
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max batch size is zero
+    pub fn set_max_batch_size(e: &Env, max_batch_size: u32) {
+        PriceOracleContractBase::set_max_batch_size(e, max_batch_size);
+    }
+
+    // Sets maximum number of price records returned by a single query
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_records` - Maximum number of records
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max records is zero
+    pub fn set_max_records(e: &Env, max_records: u32) {
+        PriceOracleContractBase::set_max_records(e, max_records);
+    }
+
+    // Sets placeholder price served by `lastprice` for newly added assets until their first price update,
+    // placeholder records are flagged with zero timestamp
+    // Only assets added while placeholder prices are enabled are affected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `placeholder_price` - Placeholder price (0 disables placeholder prices)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or placeholder price is negative
+    pub fn set_placeholder_price(e: &Env, placeholder_price: i128) {
+        PriceOracleContractBase::set_placeholder_price(e, placeholder_price);
+    }
+
+    // Sets maximum allowed price value, updates containing higher prices are rejected
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `max_price` - Maximum price (0 removes the limit)
+    //
+    // # Panics
+    //
+    // Panics if not authorized or max price is negative
+    pub fn set_max_price(e: &Env, max_price: i128) {
+        PriceOracleContractBase::set_max_price(e, max_price);
+    }
+
+    // Sets history retention period for the prices
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `period` - History retention period (in milliseconds), zero disables the retention
+    //
+    // # Panics
+    //
+    // Panics if not authorized or if the period is too short to extend records TTL beyond the minimum
+    pub fn set_history_retention_period(e: &Env, period: u64) {
+        PriceOracleContractBase::set_history_retention_period(e, period);
+    }
+
+    // Set fee token address and daily price feed retainer fee amount
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    //
+    // # Panics
+    //
+    // Panics if not authorized or not initialized yet
+    pub fn set_fee_config(e: &Env, fee_config: FeeConfig) {
+        PriceOracleContractBase::set_fee_config(e, fee_config, INITIAL_EXPIRATION_PERIOD);
+    }
+
+    // Schedule fee token address and daily price feed retainer fee amount change
+    // The change takes effect only after it is applied once the timelock passes
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `fee_config` - Fee token address and fee amount
+    // * `apply_at` - Timestamp (in seconds) after which the change can be applied
+    //
+    // # Panics
+    //
+    // Panics if not authorized or apply timestamp is not in the future
+    pub fn schedule_fee_config(e: &Env, fee_config: FeeConfig, apply_at: u64) {
+        PriceOracleContractBase::schedule_fee_config(e, fee_config, apply_at);
+    }
+
+    // Apply scheduled fee config change if its timelock has passed
+    //
+    // # Returns
+    //
+    // True if the scheduled change has been applied
+    pub fn apply_pending_fee_config(e: &Env) -> bool {
+        PriceOracleContractBase::apply_pending_fee_config(e, INITIAL_EXPIRATION_PERIOD)
+    }
+
+    // Switch fee token and daily price feed retainer fee amount, keeping existing expirations intact
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `new_token` - New fee token address
+    // * `new_rate` - Daily price feed retainer fee amount in new tokens
+    //
+    // # Panics
+    //
+    // Panics if not authorized or rate is not positive
+    pub fn migrate_fee_token(e: &Env, new_token: Address, new_rate: i128) {
+        PriceOracleContractBase::migrate_fee_token(e, new_token, new_rate);
+    }
+
+    // Sets fee split between burn and treasury transfer applied to all charged fees
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `burn_bps` - Share of the fee to burn (in basis points)
+    // * `treasury_bps` - Share of the fee to transfer to the treasury (in basis points)
+    // * `treasury` - Treasury address
+    //
+    // # Panics
+    //
+    // Panics if not authorized or shares don't sum up to 10000
+    pub fn set_fee_split(e: &Env, burn_bps: u32, treasury_bps: u32, treasury: Address) {
+        PriceOracleContractBase::set_fee_split(e, burn_bps, treasury_bps, treasury);
+    }
+
+    // Record new price feed history snapshot
     // Requires admin authorization
     //
     // # Arguments
@@ -346,7 +1491,114 @@ impl PulseOracleContract {
     //
     // Panics if not authorized or price snapshot record is invalid
     pub fn set_price(e: &Env, updates: PriceUpdate, timestamp: u64) {
-        PriceOracleContractBase::set_price(e, updates, timestamp);
+        PriceOracleContractBase::set_price(e, updates, timestamp, false);
+    }
+
+    // Record new price feed history snapshot on behalf of an allowed feeder
+    // Requires feeder authorization
+    //
+    // # Arguments
+    //
+    // * `feeder` - Feeder account address
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    //
+    // # Panics
+    //
+    // Panics if not authorized, feeder is not allowed, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_by_feeder(e: &Env, feeder: Address, updates: PriceUpdate, timestamp: u64) {
+        PriceOracleContractBase::set_price_by_feeder(e, feeder, updates, timestamp);
+    }
+
+    // Record new price feed history snapshot signed off-chain by an allowed feeder key
+    // Can be submitted by any account (e.g. a relayer), the activity is recorded for the linked feeder
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `signature` - Ed25519 signature of the XDR-encoded (contract address, updates, timestamp) tuple
+    // * `signer` - Feeder ed25519 public key
+    //
+    // # Panics
+    //
+    // Panics if signer is not allowed, signature is invalid, timestamp is not newer than the last update,
+    // or price snapshot record is invalid
+    pub fn set_price_attested(
+        e: &Env,
+        updates: PriceUpdate,
+        timestamp: u64,
+        signature: BytesN<64>,
+        signer: BytesN<32>,
+    ) {
+        PriceOracleContractBase::set_price_attested(e, updates, timestamp, signature, signer);
+    }
+
+    // Record new price feed history snapshot only if the last update timestamp matches the expected one
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    // * `expected_last_timestamp` - Expected timestamp of the most recent update (in seconds), as returned by `last_timestamp`
+    //
+    // # Panics
+    //
+    // Panics if not authorized, last update timestamp doesn't match the expected value, or price snapshot record is invalid
+    pub fn set_price_cas(
+        e: &Env,
+        updates: PriceUpdate,
+        timestamp: u64,
+        expected_last_timestamp: u64,
+    ) {
+        PriceOracleContractBase::set_price_cas(e, updates, timestamp, expected_last_timestamp);
+    }
+
+    // Overwrites prices of multiple assets in the existing price record
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `timestamp` - Timestamp of the record to correct (in seconds)
+    // * `corrections` - Assets with corrected prices
+    //
+    // # Panics
+    //
+    // Panics if not authorized, record not found, any of the assets is not supported or has no price in the record,
+    // or corrected price is invalid
+    pub fn correct_prices(e: &Env, timestamp: u64, corrections: Vec<(Asset, i128)>) {
+        PriceOracleContractBase::correct_prices(e, timestamp, corrections);
+    }
+
+    // Record multiple price feed history snapshots in a single invocation
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshots with corresponding history snapshot timestamps
+    //
+    // # Panics
+    //
+    // Panics if not authorized, batch size exceeds the limit, or any price snapshot record is invalid
+    pub fn set_prices_batch(e: &Env, updates: Vec<(PriceUpdate, u64)>) {
+        PriceOracleContractBase::set_prices_batch(e, updates);
+    }
+
+    // Record price feed history snapshot older than the most recent update
+    // Requires admin authorization
+    //
+    // # Arguments
+    //
+    // * `updates` - Price feed snapshot
+    // * `timestamp` - History snapshot timestamp
+    //
+    // # Panics
+    //
+    // Panics if not authorized, price snapshot record is invalid, or timestamp is out of history range
+    pub fn backfill_price(e: &Env, updates: PriceUpdate, timestamp: u64) {
+        PriceOracleContractBase::set_price(e, updates, timestamp, true);
     }
 
     // Update contract source code
@@ -4,13 +4,20 @@ extern crate std;
 
 use crate::tests::setup_tests::{
     convert_to_seconds, generate_assets, generate_update_record_mask, generate_updates,
-    init_contract, normalize_price, DECIMALS, RESOLUTION,
+    init_contract, normalize_price, prepare_contract_config, DECIMALS, RESOLUTION,
 };
+use crate::{PulseOracleContract, PulseOracleContractClient};
 use alloc::string::ToString;
-use oracle::types::{Asset, FeeConfig, PriceUpdate};
-use soroban_sdk::testutils::{Address as _, Events, MockAuth, MockAuthInvoke};
+use ed25519_dalek::{Signer, SigningKey};
+use oracle::types::{Asset, Error, FeeConfig, PriceUpdate};
+use soroban_sdk::testutils::{
+    storage::Temporary as _, Address as _, Events, Ledger, LedgerInfo, MockAuth, MockAuthInvoke,
+};
 use soroban_sdk::token::{StellarAssetClient, TokenClient};
-use soroban_sdk::{symbol_short, Address, IntoVal, Symbol, TryIntoVal, Vec};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryIntoVal, Val, Vec,
+};
 
 #[test]
 fn init_test() {
@@ -38,6 +45,60 @@ fn init_test() {
     assert_eq!(assets, init_data.assets);
 }
 
+#[test]
+fn init_without_assets_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PulseOracleContract, ());
+    let client = PulseOracleContractClient::new(&env, &contract_id);
+
+    let mut config = prepare_contract_config(&env);
+    config.assets = Vec::new(&env);
+    assert_eq!(client.try_config(&config), Err(Ok(Error::NoAssets.into())));
+    assert!(client.admin().is_none());
+}
+
+#[test]
+fn init_with_fractional_resolution_test() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PulseOracleContract, ());
+    let client = PulseOracleContractClient::new(&env, &contract_id);
+
+    //300.5 seconds would be reported as 300, misaligning periods computed by clients
+    let mut config = prepare_contract_config(&env);
+    config.resolution = 300_500;
+    assert_eq!(
+        client.try_config(&config),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    config.resolution = 0;
+    assert_eq!(
+        client.try_config(&config),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+
+    config.resolution = 301_000;
+    client.config(&config);
+    assert_eq!(client.resolution(), 301);
+    assert_eq!(client.resolution_ms(), 301_000);
+}
+
+#[test]
+fn reinit_test() {
+    let (env, client, init_data) = init_contract();
+
+    //another admin with different assets should not be able to overwrite the config
+    let mut config = prepare_contract_config(&env);
+    config.assets = generate_assets(&env, 3, 100);
+    assert_eq!(
+        client.try_config(&config),
+        Err(Ok(Error::AlreadyInitialized.into()))
+    );
+    assert_eq!(client.admin().unwrap(), init_data.admin);
+    assert_eq!(client.assets(), init_data.assets);
+}
+
 #[test]
 fn set_price_test() {
     let (env, client, init_data) = init_contract();
@@ -63,6 +124,44 @@ fn set_price_test() {
     );
 }
 
+#[test]
+fn update_event_mask_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let mut prices = Vec::new(&env);
+    for index in 0..assets.len() {
+        prices.push_back(if index == 1 { 0 } else { normalize_price(1) });
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: Vec::from_slice(&env, &[normalize_price(1); 9]),
+    };
+    client.set_price(&updates, &600_000);
+
+    let data: Map<Symbol, Val> = env
+        .events()
+        .all()
+        .last()
+        .unwrap()
+        .2
+        .try_into_val(&env)
+        .unwrap();
+    let mask: Bytes = data
+        .get(Symbol::new(&env, "mask"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(mask, updates.mask);
+    //update data still lists only updated assets
+    let update_data: Vec<(Val, i128)> = data
+        .get(Symbol::new(&env, "update_data"))
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(update_data.len(), 9);
+}
+
 #[test]
 #[should_panic]
 fn set_price_zero_timestamp_test() {
@@ -111,6 +210,430 @@ fn set_price_future_timestamp_test() {
     client.set_price(&updates, &timestamp);
 }
 
+#[test]
+#[should_panic]
+fn set_price_older_timestamp_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let updates = generate_updates(&env, &assets, normalize_price(100));
+
+    client.set_price(&updates, &900_000);
+    //older record without backfill flag
+    client.set_price(&updates, &600_000);
+}
+
+#[test]
+fn migrate_v1_records_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    //emulate legacy protocol version with a record available only in legacy format
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&"protocol", &1u32);
+        let legacy_prices = Vec::from_array(&env, [normalize_price(50), 0, normalize_price(70)]);
+        oracle::prices::store_price_v1(&env, &legacy_prices, 300_000, 1_000);
+    });
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(100)),
+        &600_000,
+    );
+    //switch to the current protocol without the legacy read path
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&"protocol", &2u32);
+    });
+    assert!(client.price(&assets.get_unchecked(0), &300).is_none());
+
+    assert_eq!(
+        client.migrate_v1_records(&Vec::from_array(&env, [300, 600, 1_200])),
+        1
+    );
+    assert_eq!(
+        client.price(&assets.get_unchecked(0), &300).unwrap().price,
+        normalize_price(50)
+    );
+    assert!(client.price(&assets.get_unchecked(1), &300).is_none());
+    assert_eq!(
+        client.price(&assets.get_unchecked(2), &300).unwrap().price,
+        normalize_price(70)
+    );
+    env.as_contract(&client.address, || {
+        let temps = env.storage().temporary();
+        //legacy entries are removed
+        assert!(oracle::prices::get_price_v1(&env, 0, 300_000).is_none());
+        assert!(oracle::prices::get_price_v1(&env, 2, 300_000).is_none());
+        //migrated record lives until the end of its retention period rather than the full one
+        assert_eq!(temps.get_ttl(&300_000u64), 11_762);
+        assert!(temps.get_ttl(&300_000u64) < temps.get_ttl(&600_000u64));
+    });
+    //newer records are not affected
+    assert_eq!(
+        client.price(&assets.get_unchecked(1), &600).unwrap().price,
+        normalize_price(100)
+    );
+    //already migrated records are skipped
+    assert_eq!(client.migrate_v1_records(&Vec::from_array(&env, [300])), 0);
+
+    client.set_max_batch_size(&1);
+    assert_eq!(
+        client.try_migrate_v1_records(&Vec::from_array(&env, [300, 600])),
+        Err(Ok(Error::BatchSizeExceeded.into()))
+    );
+}
+
+#[test]
+fn protocol_transition_read_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let asset = assets.first_unchecked();
+    //emulate legacy protocol version with a record available only in legacy format
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&"protocol", &1u32);
+        let legacy_prices = Vec::from_array(&env, [normalize_price(50)]);
+        oracle::prices::store_price_v1(&env, &legacy_prices, 300_000, 1_000);
+    });
+    let protocol = || {
+        env.as_contract(&client.address, || {
+            env.storage().instance().get::<_, u32>(&"protocol").unwrap()
+        })
+    };
+    assert_eq!(
+        client.price(&asset, &300).unwrap().price,
+        normalize_price(50)
+    );
+
+    //record written right before the upgrade
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(100)),
+        &600_000,
+    );
+    assert_eq!(protocol(), 1);
+    assert_eq!(
+        client.price(&asset, &600).unwrap().price,
+        normalize_price(100)
+    );
+
+    //trigger the upgrade after the scheduled window passes
+    let upgrade_timestamp = 900 + 86_400 + RESOLUTION as u64 / 1000;
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: upgrade_timestamp,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(200)),
+        &(upgrade_timestamp * 1000),
+    );
+    assert_eq!(protocol(), 2);
+
+    //both legacy and new records remain readable
+    assert_eq!(
+        client.price(&asset, &300).unwrap().price,
+        normalize_price(50)
+    );
+    assert_eq!(
+        client.price(&asset, &600).unwrap().price,
+        normalize_price(100)
+    );
+    assert_eq!(
+        client.price(&asset, &upgrade_timestamp).unwrap().price,
+        normalize_price(200)
+    );
+    assert_eq!(
+        client.lastprice(&asset).unwrap().price,
+        normalize_price(200)
+    );
+    //no legacy fallback for records after the upgrade
+    assert!(client.price(&asset, &(upgrade_timestamp + 300)).is_none());
+
+    let legacy_cutoff = || {
+        env.as_contract(&client.address, || {
+            env.storage().instance().has(&"legacy_cutoff")
+        })
+    };
+    //the legacy cutoff is kept within the retention period
+    let retention_period = client.history_retention_period().unwrap();
+    let timestamp = upgrade_timestamp + retention_period;
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(300)),
+        &(timestamp * 1000),
+    );
+    assert!(legacy_cutoff());
+    //and cleared once all legacy records are past the retention period
+    let timestamp = timestamp + 300;
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(300)),
+        &(timestamp * 1000),
+    );
+    assert!(!legacy_cutoff());
+}
+
+#[test]
+fn resubmit_price_history_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let mut prices = Vec::new(&env);
+    prices.push_back(normalize_price(1));
+    for _ in 1..assets.len() {
+        prices.push_back(0);
+    }
+    let update = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: Vec::from_array(&env, [normalize_price(1)]),
+    };
+    client.set_price(&update, &600_000);
+    //resubmitting the same timestamp must not shift the history
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(2)),
+        &900_000,
+    );
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(3)),
+        &900_000,
+    );
+
+    let asset = assets.get_unchecked(1);
+    assert!(client.price(&asset, &600).is_none());
+    assert_eq!(
+        client.price(&asset, &900).unwrap().price,
+        normalize_price(3)
+    );
+    let asset = assets.get_unchecked(0);
+    assert_eq!(
+        client.price(&asset, &600).unwrap().price,
+        normalize_price(1)
+    );
+}
+
+#[test]
+fn backfill_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    client.set_cache_size(&3);
+
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(100)),
+        &600_000,
+    );
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(300)),
+        &1_200_000,
+    );
+    //fill the gap
+    client.backfill_price(
+        &generate_updates(&env, &assets, normalize_price(200)),
+        &900_000,
+    );
+
+    assert_eq!(client.last_timestamp(), convert_to_seconds(1_200_000));
+    let asset = assets.first_unchecked();
+    let prices = client.prices(&asset, &3).unwrap();
+    assert_eq!(prices.len(), 3);
+    assert_eq!(prices.get_unchecked(0).price, normalize_price(300));
+    assert_eq!(prices.get_unchecked(1).price, normalize_price(200));
+    assert_eq!(prices.get_unchecked(2).price, normalize_price(100));
+    //backfilled records are not cached
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [1_200, 600]));
+
+    //cached copy of the overwritten record is replaced
+    client.backfill_price(
+        &generate_updates(&env, &assets, normalize_price(150)),
+        &600_000,
+    );
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [1_200, 600]));
+    assert_eq!(
+        client.price(&asset, &600).unwrap().price,
+        normalize_price(150)
+    );
+}
+
+#[test]
+fn set_price_within_bounds_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let asset = assets.first_unchecked();
+    client.set_price_bounds(&asset, &normalize_price(50), &normalize_price(150));
+
+    let updates = generate_updates(&env, &assets, normalize_price(100));
+    client.set_price(&updates, &600_000);
+
+    assert_eq!(
+        client.lastprice(&asset).unwrap().price,
+        normalize_price(100)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn set_price_out_of_bounds_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    client.set_price_bounds(
+        &assets.get_unchecked(1),
+        &normalize_price(1),
+        &normalize_price(2),
+    );
+
+    let updates = generate_updates(&env, &assets, normalize_price(100));
+    client.set_price(&updates, &600_000);
+}
+
+#[test]
+fn set_price_max_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    assert_eq!(client.max_price(), 0);
+    client.set_max_price(&normalize_price(1_000_000_000));
+    assert_eq!(client.max_price(), normalize_price(1_000_000_000));
+
+    //price that overflows fixed point shift in cross price calculation is rejected
+    let updates = generate_updates(&env, &assets, 9 * 10i128.pow(37));
+    assert_eq!(
+        client.try_set_price(&updates, &600_000),
+        Err(Ok(Error::PriceOutOfBounds.into()))
+    );
+
+    //prices within the cap are accepted
+    let updates = generate_updates(&env, &assets, normalize_price(1_000_000_000));
+    client.set_price(&updates, &600_000);
+    assert_eq!(
+        client
+            .x_price(&assets.get_unchecked(0), &assets.get_unchecked(1), &600)
+            .unwrap()
+            .price,
+        normalize_price(1)
+    );
+    assert_eq!(
+        client.try_set_max_price(&-1),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn warm_cache_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    client.set_cache_size(&1);
+    for timestamp in [300_000, 600_000, 900_000] {
+        client.set_price(
+            &generate_updates(&env, &assets, normalize_price(timestamp as i128 / 300_000)),
+            &timestamp,
+        );
+    }
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [900]));
+
+    //growing the cache doesn't populate it retroactively
+    client.set_cache_size(&3);
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [900]));
+
+    //missing and already cached records are ignored
+    client.warm_cache(&Vec::from_array(&env, [300, 900, 1_200, 600]));
+    assert_eq!(
+        client.cache_records(),
+        Vec::from_array(&env, [900, 600, 300])
+    );
+    let asset = assets.first_unchecked();
+    assert_eq!(
+        client.price(&asset, &300).unwrap().price,
+        normalize_price(1)
+    );
+
+    //cache size limit is respected
+    client.clear_cache();
+    client.set_cache_size(&2);
+    client.warm_cache(&Vec::from_array(&env, [300, 600, 900]));
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [900, 600]));
+}
+
+#[test]
+fn clear_cache_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    client.set_cache_size(&2);
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(100)),
+        &300_000,
+    );
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(200)),
+        &600_000,
+    );
+    assert_eq!(client.cache_records(), Vec::from_array(&env, [600, 300]));
+
+    client.clear_cache();
+
+    assert!(client.cache_records().is_empty());
+    assert_eq!(client.cache_size(), 2);
+    let asset = assets.first_unchecked();
+    assert_eq!(
+        client.price(&asset, &300).unwrap().price,
+        normalize_price(100)
+    );
+    assert_eq!(
+        client.price(&asset, &600).unwrap().price,
+        normalize_price(200)
+    );
+}
+
+#[test]
+fn read_does_not_schedule_protocol_update_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    //emulate legacy protocol version
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&"protocol", &1u32);
+    });
+    let scheduled_update = || {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .get::<_, u64>(&"protocol_update")
+                .unwrap_or_default()
+        })
+    };
+
+    let asset = assets.first_unchecked();
+    client.lastprice(&asset);
+    client.price(&asset, &600);
+    client.twap(&asset, &2);
+    assert_eq!(scheduled_update(), 0);
+
+    //price update schedules protocol upgrade
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(100)),
+        &600_000,
+    );
+    assert_eq!(scheduled_update(), 900_000);
+    assert_eq!(
+        client.lastprice(&asset).unwrap().price,
+        normalize_price(100)
+    );
+}
+
 #[test]
 fn add_assets_test() {
     let (env, client, init_data) = init_contract();
@@ -137,7 +660,7 @@ fn add_assets_duplicate_test() {
     let (env, client, _) = init_contract();
 
     let mut assets = Vec::new(&env);
-    let duplicate_asset = Asset::Other(Symbol::new(&env, &("ASSET_DUPLICATE")));
+    let duplicate_asset = Asset::Other(Symbol::new(&env, "ASSET_DUPLICATE"));
     assets.push_back(duplicate_asset.clone());
     assets.push_back(duplicate_asset);
 
@@ -146,6 +669,61 @@ fn add_assets_duplicate_test() {
     client.add_assets(&assets);
 }
 
+#[test]
+fn add_assets_alias_duplicate_test() {
+    let (env, client, init_data) = init_contract();
+
+    env.mock_all_auths();
+
+    let listed = init_data.assets.first_unchecked();
+    let alias = Asset::Other(Symbol::new(&env, "ALIAS"));
+    client.set_asset_alias(&alias, &listed);
+    assert_eq!(client.asset_alias(&alias), Some(listed.clone()));
+    assert_eq!(client.asset_alias(&listed), None);
+
+    //aliases can't be chained in either direction
+    let other_alias = Asset::Other(Symbol::new(&env, "CHAINED"));
+    assert_eq!(
+        client.try_set_asset_alias(&other_alias, &alias),
+        Err(Ok(Error::ChainedAlias.into()))
+    );
+    assert_eq!(
+        client.try_set_asset_alias(&listed, &other_alias),
+        Err(Ok(Error::ChainedAlias.into()))
+    );
+
+    //alias of an already listed asset
+    assert_eq!(
+        client.try_add_assets(&Vec::from_array(&env, [alias.clone()])),
+        Err(Ok(Error::AssetAlreadyExists.into()))
+    );
+
+    //canonical asset when the alias has been listed first
+    let canonical = Asset::Stellar(Address::generate(&env));
+    let listed_alias = Asset::Other(Symbol::new(&env, "LISTED_ALIAS"));
+    client.set_asset_alias(&listed_alias, &canonical);
+    client.add_assets(&Vec::from_array(&env, [listed_alias.clone()]));
+    assert_eq!(
+        client.try_add_assets(&Vec::from_array(&env, [canonical.clone()])),
+        Err(Ok(Error::AssetAlreadyExists.into()))
+    );
+
+    //both forms within a single batch
+    let other = Asset::Stellar(Address::generate(&env));
+    let other_alias = Asset::Other(Symbol::new(&env, "OTHER_ALIAS"));
+    client.set_asset_alias(&other_alias, &other);
+    assert_eq!(
+        client.try_add_assets(&Vec::from_array(&env, [other.clone(), other_alias.clone()])),
+        Err(Ok(Error::AssetAlreadyExists.into()))
+    );
+
+    //removed alias no longer blocks listing
+    client.set_asset_alias(&alias, &alias);
+    assert_eq!(client.asset_alias(&alias), None);
+    client.add_assets(&Vec::from_array(&env, [alias.clone()]));
+    assert!(client.assets().contains(&alias));
+}
+
 #[test]
 #[should_panic]
 fn asset_update_overflow_test() {
@@ -187,6 +765,63 @@ fn price_update_overflow_test() {
     client.set_price(&update, &600_000);
 }
 
+#[test]
+fn set_asset_label_test() {
+    let (env, client, init_data) = init_contract();
+
+    //first asset is a Stellar asset
+    let asset = init_data.assets.first_unchecked();
+    assert!(client.asset_label(&asset).is_none());
+
+    client.set_asset_label(&asset, &symbol_short!("BTC"));
+
+    assert_eq!(client.asset_label(&asset).unwrap(), symbol_short!("BTC"));
+    assert!(client
+        .asset_label(&init_data.assets.get_unchecked(1))
+        .is_none());
+    //unknown asset
+    let unknown = Asset::Stellar(Address::generate(&env));
+    assert!(client.asset_label(&unknown).is_none());
+}
+
+#[test]
+fn set_asset_labels_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let unknown = Asset::Stellar(Address::generate(&env));
+    //unknown asset rejects the whole batch
+    assert_eq!(
+        client.try_set_asset_labels(&Vec::from_array(
+            &env,
+            [
+                (assets.get_unchecked(0), symbol_short!("BTC")),
+                (unknown, symbol_short!("XXX")),
+            ]
+        )),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert!(client.asset_label(&assets.get_unchecked(0)).is_none());
+
+    let labels = [
+        symbol_short!("BTC"),
+        symbol_short!("ETH"),
+        symbol_short!("XLM"),
+    ];
+    let mut entries = Vec::new(&env);
+    for (index, label) in labels.iter().enumerate() {
+        entries.push_back((assets.get_unchecked(index as u32), label.clone()));
+    }
+    client.set_asset_labels(&entries);
+    for (index, label) in labels.iter().enumerate() {
+        assert_eq!(
+            client.asset_label(&assets.get_unchecked(index as u32)),
+            Some(label.clone())
+        );
+    }
+    assert!(client.asset_label(&assets.get_unchecked(3)).is_none());
+}
+
 #[test]
 fn set_history_retention_period_test() {
     let (env, client, _) = init_contract();
@@ -203,17 +838,63 @@ fn set_history_retention_period_test() {
 }
 
 #[test]
-fn set_fee_config_test() {
+fn retention_info_test() {
     let (env, client, init_data) = init_contract();
 
-    //emulate old contract state
-    env.as_contract(&client.address, || {
-        env.storage().instance().remove(&"retention");
-        env.storage().instance().remove(&"expiration");
-    });
-
-    //create fee asset token
-    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    env.mock_all_auths();
+
+    assert_eq!(
+        client.retention_info(),
+        (convert_to_seconds(init_data.history_retention_period), true)
+    );
+
+    //40 seconds (the setter takes milliseconds) is the shortest period producing TTL above the minimum extension period
+    assert_eq!(
+        client.try_set_history_retention_period(&35_000),
+        Err(Ok(Error::RetentionPeriodTooShort.into()))
+    );
+    client.set_history_retention_period(&40_000);
+    assert_eq!(client.retention_info(), (40, true));
+
+    //zero period disables the retention
+    client.set_history_retention_period(&0);
+    assert_eq!(client.retention_info(), (0, false));
+}
+
+#[test]
+fn export_snapshot_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &600_000,
+    );
+
+    let (assets, expirations, last_timestamp, fee_config) = client.export_snapshot();
+    assert_eq!(assets, client.assets());
+    assert_eq!(expirations.len(), assets.len());
+    for (asset, expiration) in assets.iter().zip(expirations.iter()) {
+        assert_eq!(client.expires(&asset), Some(expiration));
+    }
+    assert_eq!(last_timestamp, client.last_timestamp());
+    assert_eq!(last_timestamp, 600);
+    assert_eq!(fee_config, client.fee_config());
+}
+
+#[test]
+fn set_fee_config_test() {
+    let (env, client, init_data) = init_contract();
+
+    //emulate old contract state
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&"retention");
+        env.storage().instance().remove(&"expiration");
+    });
+
+    //create fee asset token
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
 
     let fee_config = FeeConfig::Some((fee_asset.address(), 7));
 
@@ -240,13 +921,857 @@ fn set_fee_config_test() {
 
     let fee_token_balance = TokenClient::new(&env, &fee_asset.address()).balance(&sponsor);
     assert_eq!(fee_token_balance, 0);
+    assert_eq!(client.total_fees_burned(), 10);
+}
+
+#[test]
+fn feed_summary_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    let asset = init_data.assets.get_unchecked(0);
+    let updates = generate_updates(&env, &init_data.assets, normalize_price(100));
+    client.set_price(&updates, &900_000);
+
+    //active feed
+    let (expiration, price, active) = client.feed_summary(&asset);
+    assert_eq!(expiration, Some(15_552_900)); // 900s current ledger timestamp + 180 days
+    assert_eq!(price, client.lastprice(&asset));
+    assert_eq!(price.unwrap().price, normalize_price(100));
+    assert!(active);
+    assert!(client.is_feed_active(&asset));
+
+    //emulate expired feed
+    env.as_contract(&client.address, || {
+        let mut expiration = Vec::new(&env);
+        expiration.push_back(600_000u64);
+        expiration.push_back(15_552_900_000u64);
+        env.storage().instance().set(&"expiration", &expiration);
+    });
+    let (expiration, price, active) = client.feed_summary(&asset);
+    assert_eq!(expiration, Some(600));
+    assert!(price.is_some());
+    assert!(!active);
+    assert!(!client.is_feed_active(&asset));
+    assert!(client.is_feed_active(&init_data.assets.get_unchecked(1)));
+
+    //unknown asset
+    let unknown = Asset::Stellar(Address::generate(&env));
+    assert_eq!(client.feed_summary(&unknown), (None, None, false));
+}
+
+#[test]
+fn fee_split_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    assert!(client.fee_split().is_none());
+
+    let treasury = Address::generate(&env);
+    //shares should sum up to 10000
+    assert_eq!(
+        client.try_set_fee_split(&7_000, &2_000, &treasury),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    client.set_fee_split(&7_000, &3_000, &treasury);
+    assert_eq!(client.fee_split(), Some((7_000, 3_000, treasury.clone())));
+
+    let sponsor = Address::generate(&env);
+    StellarAssetClient::new(&env, &fee_asset.address()).mint(&sponsor, &100);
+    let asset = init_data.assets.get_unchecked(0);
+    let expires = client.expires(&asset).unwrap();
+    client.extend_asset_ttl(&sponsor, &asset, &10);
+    //extension is based on the full amount
+    assert_eq!(client.expires(&asset).unwrap(), expires + 123428571);
+
+    let token = TokenClient::new(&env, &fee_asset.address());
+    assert_eq!(token.balance(&sponsor), 90);
+    assert_eq!(token.balance(&treasury), 3);
+    assert_eq!(client.total_fees_burned(), 7);
+}
+
+#[test]
+fn scheduled_fee_config_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    let fee_config = FeeConfig::Some((fee_asset.address(), 7));
+    //apply timestamp should be in the future
+    assert_eq!(
+        client.try_schedule_fee_config(&fee_config, &900),
+        Err(Ok(Error::InvalidTimestamp.into()))
+    );
+    client.schedule_fee_config(&fee_config, &1_800);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (
+            Symbol::new(&env, "REFLECTOR"),
+            Symbol::new(&env, "fee_scheduled"),
+            &1_800u64
+        )
+            .into_val(&env)
+    );
+    assert_eq!(
+        client.pending_fee_config(),
+        Some((fee_config.clone(), 1_800))
+    );
+
+    //timelock is still active
+    assert!(!client.apply_pending_fee_config());
+    assert_eq!(client.fee_config(), FeeConfig::None);
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_800,
+        ..ledger_info
+    });
+    assert!(client.apply_pending_fee_config());
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (
+            Symbol::new(&env, "REFLECTOR"),
+            Symbol::new(&env, "fee_applied"),
+            &1_800u64
+        )
+            .into_val(&env)
+    );
+    assert_eq!(client.fee_config(), fee_config);
+    assert!(client
+        .expires(&init_data.assets.first_unchecked())
+        .is_some());
+    assert_eq!(client.pending_fee_config(), None);
+    //nothing left to apply
+    assert!(!client.apply_pending_fee_config());
+}
+
+#[test]
+fn migrate_fee_token_test() {
+    let (env, client, init_data) = init_contract();
+
+    let old_token = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    let new_token = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((old_token.address(), 7)));
+    assert!(client.fee_migration_timestamp().is_none());
+
+    let sponsor = Address::generate(&env);
+    StellarAssetClient::new(&env, &old_token.address()).mint(&sponsor, &10);
+    StellarAssetClient::new(&env, &new_token.address()).mint(&sponsor, &10);
+    let asset = init_data.assets.get_unchecked(0);
+    let initial = client.expires(&asset).unwrap();
+    //old rate
+    client.extend_asset_ttl(&sponsor, &asset, &10);
+    let extended = initial + 123428571;
+    assert_eq!(client.expires(&asset).unwrap(), extended);
+
+    client.migrate_fee_token(&new_token.address(), &14);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (
+            Symbol::new(&env, "REFLECTOR"),
+            Symbol::new(&env, "fee_token"),
+            &900u64
+        )
+            .into_val(&env)
+    );
+    assert_eq!(client.fee_migration_timestamp(), Some(900));
+    assert_eq!(
+        client.fee_config(),
+        FeeConfig::Some((new_token.address(), 14))
+    );
+    //existing expirations are kept intact
+    assert_eq!(client.expires(&asset).unwrap(), extended);
+    assert_eq!(
+        client.expires(&init_data.assets.get_unchecked(1)).unwrap(),
+        initial
+    );
+
+    //new rate
+    client.extend_asset_ttl(&sponsor, &asset, &10);
+    assert_eq!(client.expires(&asset).unwrap(), extended + 61714285);
+    assert_eq!(
+        TokenClient::new(&env, &new_token.address()).balance(&sponsor),
+        0
+    );
+    assert_eq!(
+        client.try_migrate_fee_token(&new_token.address(), &0),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn ttl_remaining_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.get_unchecked(0);
+    //no expiration records without fee config
+    assert!(client.ttl_remaining(&asset).is_none());
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    let ttl = 180 * 86_400;
+    assert_eq!(client.ttl_remaining(&asset), Some(ttl));
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 900 + 86_400,
+        ..ledger_info
+    });
+    assert_eq!(client.ttl_remaining(&asset), Some(ttl - 86_400));
+
+    //past expiration
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 900 + 181 * 86_400,
+        ..ledger_info
+    });
+    assert_eq!(client.ttl_remaining(&asset), Some(-86_400));
+    assert!(client
+        .ttl_remaining(&Asset::Stellar(Address::generate(&env)))
+        .is_none());
+}
+
+#[test]
+fn expirations_of_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.get_unchecked(0);
+    let unknown = Asset::Stellar(Address::generate(&env));
+    //no expiration records without fee config
+    assert_eq!(
+        client.expirations_of(&Vec::from_array(&env, [asset.clone()])),
+        Vec::from_array(&env, [None])
+    );
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    //assets added without fee config don't get expiration records
+    client.set_fee_config(&FeeConfig::None);
+    let unconfigured = generate_assets(&env, 1, 100).first_unchecked();
+    client.add_assets(&Vec::from_array(&env, [unconfigured.clone()]));
+
+    let expiration = 900 + 180 * 86_400;
+    assert_eq!(
+        client.expirations_of(&Vec::from_array(&env, [asset, unknown, unconfigured])),
+        Vec::from_array(&env, [Some(expiration), None, None])
+    );
+}
+
+#[test]
+fn reindex_assets_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    let assets = init_data.assets;
+    //distinct prices for every asset and period
+    for timestamp in [300_000u64, 600_000, 900_000] {
+        let mut prices = Vec::new(&env);
+        for index in 0..assets.len() {
+            prices.push_back(normalize_price(
+                (index as u64 * 10 + timestamp / 300_000) as i128,
+            ));
+        }
+        let update = PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices,
+        };
+        client.set_price(&update, &timestamp);
+    }
+    client.set_asset_label(&assets.get_unchecked(4), &symbol_short!("ETH"));
+    //remove two middle assets
+    env.as_contract(&client.address, || {
+        let mut expiration = Vec::new(&env);
+        for index in 0..assets.len() {
+            expiration.push_back(if index == 2 || index == 5 {
+                1
+            } else {
+                100 * 86_400_000u64
+            });
+        }
+        env.storage().instance().set(&"expiration", &expiration);
+    });
+    assert_eq!(client.purge_expired(&0), 2);
+
+    let mut survivors = Vec::new(&env);
+    for (index, asset) in assets.iter().enumerate() {
+        if index != 2 && index != 5 {
+            survivors.push_back(asset);
+        }
+    }
+    let base = survivors.get_unchecked(1);
+    let quote = survivors.get_unchecked(5);
+    let mut before = Vec::new(&env);
+    for asset in survivors.iter() {
+        before.push_back(client.prices(&asset, &3).unwrap());
+    }
+    let cross_before = client.x_last_price(&base, &quote);
+
+    assert_eq!(client.reindex_assets(), 2);
+    assert_eq!(client.assets(), survivors);
+    for (index, asset) in survivors.iter().enumerate() {
+        assert_eq!(
+            client.prices(&asset, &3).unwrap(),
+            before.get_unchecked(index as u32)
+        );
+        assert_eq!(client.expires(&asset), Some(100 * 86_400_000));
+    }
+    assert_eq!(client.x_last_price(&base, &quote), cross_before);
+    assert_eq!(
+        client.asset_label(&survivors.get_unchecked(3)),
+        Some(symbol_short!("ETH"))
+    );
+    //compacted layout accepts updates for surviving assets only
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &survivors, normalize_price(100)),
+        &1_200_000,
+    );
+    let last = client.prices(&survivors.get_unchecked(7), &4).unwrap();
+    assert_eq!(last.get_unchecked(0).price, normalize_price(100));
+    assert_eq!(last.get_unchecked(1).price, normalize_price(93));
+    //nothing left to compact
+    assert_eq!(client.reindex_assets(), 0);
+}
+
+#[test]
+fn set_price_cas_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.get_unchecked(0);
+    client.set_price_cas(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &300_000,
+        &0,
+    );
+    //another feeder has already moved the oracle forward
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(2)),
+        &600_000,
+    );
+    assert_eq!(
+        client.try_set_price_cas(
+            &generate_updates(&env, &init_data.assets, normalize_price(3)),
+            &900_000,
+            &300,
+        ),
+        Err(Ok(Error::StaleUpdate.into()))
+    );
+    assert_eq!(client.lastprice(&asset).unwrap().price, normalize_price(2));
+
+    //the expected timestamp is in seconds, just like the one returned by last_timestamp
+    assert_eq!(client.last_timestamp(), 600);
+    client.set_price_cas(
+        &generate_updates(&env, &init_data.assets, normalize_price(3)),
+        &900_000,
+        &client.last_timestamp(),
+    );
+    assert_eq!(client.lastprice(&asset).unwrap().price, normalize_price(3));
+}
+
+#[test]
+fn deviation_auto_pause_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.get_unchecked(0);
+    assert_eq!(
+        client.try_set_deviation_limits(&5_000, &5_000),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    client.set_deviation_limits(&1_000, &5_000);
+    assert_eq!(client.deviation_limits(), Some((1_000, 5_000)));
+
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &300_000,
+    );
+    //moderate move is rejected without pausing
+    assert_eq!(
+        client.try_set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(120)),
+            &600_000,
+        ),
+        Err(Ok(Error::DeviationExceeded.into()))
+    );
+    assert!(!client.paused());
+
+    //catastrophic move pauses the contract
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(300)),
+        &600_000,
+    );
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("REFLECTOR"), symbol_short!("paused"), &600u64).into_val(&env)
+    );
+    assert!(client.paused());
+    //the update is not applied
+    assert!(client.price(&asset, &600).is_none());
+    assert_eq!(
+        client.try_set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(105)),
+            &600_000,
+        ),
+        Err(Ok(Error::Paused.into()))
+    );
+
+    client.unpause();
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(105)),
+        &600_000,
+    );
+    assert_eq!(
+        client.lastprice(&asset).unwrap().price,
+        normalize_price(105)
+    );
+}
+
+#[test]
+fn set_price_attested_test() {
+    let (env, client, init_data) = init_contract();
+
+    let feeder = Address::generate(&env);
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let signer = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    //keys can only be linked to allowed feeders
+    assert_eq!(
+        client.try_add_feeder_key(&feeder, &signer),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    client.add_feeder(&feeder);
+    client.add_feeder_key(&feeder, &signer);
+    assert_eq!(
+        client.feeder_keys(),
+        Vec::from_array(&env, [(signer.clone(), feeder.clone())])
+    );
+
+    let update = generate_updates(&env, &init_data.assets, normalize_price(3));
+    let timestamp = 600_000u64;
+    let sign = |key: &SigningKey, timestamp: u64| {
+        let payload = (client.address.clone(), update.clone(), timestamp).to_xdr(&env);
+        let message: std::vec::Vec<u8> = payload.iter().collect();
+        BytesN::from_array(&env, &key.sign(&message).to_bytes())
+    };
+    //signer is not allowlisted
+    let other_key = SigningKey::from_bytes(&[8; 32]);
+    let other_signer = BytesN::from_array(&env, &other_key.verifying_key().to_bytes());
+    assert_eq!(
+        client.try_set_price_attested(
+            &update,
+            &timestamp,
+            &sign(&other_key, timestamp),
+            &other_signer
+        ),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    assert_eq!(client.last_timestamp(), 0);
+
+    client.set_price_attested(&update, &timestamp, &sign(&signing_key, timestamp), &signer);
+    assert_eq!(client.last_timestamp(), 600);
+    //replaying the signed snapshot is rejected
+    assert_eq!(
+        client.try_set_price_attested(&update, &timestamp, &sign(&signing_key, timestamp), &signer),
+        Err(Ok(Error::StaleUpdate.into()))
+    );
+    //activity is recorded for the linked feeder
+    assert_eq!(
+        client.feeder_activity(),
+        Vec::from_array(&env, [(feeder.clone(), 900)])
+    );
+    assert_eq!(
+        client
+            .lastprice(&init_data.assets.get_unchecked(0))
+            .unwrap()
+            .price,
+        normalize_price(3)
+    );
+
+    client.remove_feeder_key(&signer);
+    assert!(client.feeder_keys().is_empty());
+
+    //removing the feeder drops its keys
+    client.add_feeder_key(&feeder, &signer);
+    client.remove_feeder(&feeder);
+    assert!(client.feeder_keys().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn set_price_attested_invalid_signature_test() {
+    let (env, client, init_data) = init_contract();
+
+    let feeder = Address::generate(&env);
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let signer = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.add_feeder(&feeder);
+    client.add_feeder_key(&feeder, &signer);
+
+    let update = generate_updates(&env, &init_data.assets, normalize_price(3));
+    //signature covering another timestamp is rejected
+    let payload = (client.address.clone(), update.clone(), 300_000u64).to_xdr(&env);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    let signature = BytesN::from_array(&env, &signing_key.sign(&message).to_bytes());
+    client.set_price_attested(&update, &600_000, &signature, &signer);
+}
+
+#[test]
+fn feeder_activity_test() {
+    let (env, client, init_data) = init_contract();
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.add_feeder(&first);
+    client.add_feeder(&second);
+    assert_eq!(
+        client.try_set_price_by_feeder(
+            &Address::generate(&env),
+            &generate_updates(&env, &init_data.assets, normalize_price(1)),
+            &300_000,
+        ),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+
+    client.set_price_by_feeder(
+        &first,
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &300_000,
+    );
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    client.set_price_by_feeder(
+        &second,
+        &generate_updates(&env, &init_data.assets, normalize_price(2)),
+        &1_200_000,
+    );
+    //feeders can't resubmit or overwrite already recorded snapshots
+    for timestamp in [1_200_000, 900_000] {
+        assert_eq!(
+            client.try_set_price_by_feeder(
+                &first,
+                &generate_updates(&env, &init_data.assets, normalize_price(3)),
+                &timestamp,
+            ),
+            Err(Ok(Error::StaleUpdate.into()))
+        );
+    }
+    //activity is kept in per-feeder persistent entries
+    env.as_contract(&client.address, || {
+        assert!(env
+            .storage()
+            .persistent()
+            .has(&("feeder_activity", second.clone())));
+    });
+
+    let activity = client.feeder_activity();
+    assert_eq!(activity.len(), 2);
+    assert!(activity.contains((first.clone(), 900)));
+    assert!(activity.contains((second.clone(), 1_200)));
+    assert_eq!(
+        client
+            .lastprice(&init_data.assets.get_unchecked(0))
+            .unwrap()
+            .price,
+        normalize_price(2)
+    );
+
+    client.remove_feeder(&first);
+    assert_eq!(
+        client.feeder_activity(),
+        Vec::from_array(&env, [(second, 1_200)])
+    );
+}
+
+#[test]
+fn correct_prices_test() {
+    let (env, client, init_data) = init_contract();
+
+    //cached copy should be corrected as well
+    client.set_cache_size(&2);
+    let assets = init_data.assets;
+    client.set_price(
+        &generate_updates(&env, &assets, normalize_price(1)),
+        &600_000,
+    );
+    let mut corrections = Vec::new(&env);
+    for index in 0..3 {
+        corrections.push_back((
+            assets.get_unchecked(index),
+            normalize_price(index as i128 + 5),
+        ));
+    }
+    //nothing is changed if any of the assets is not supported
+    let mut invalid = corrections.clone();
+    invalid.push_back((Asset::Stellar(Address::generate(&env)), normalize_price(1)));
+    assert_eq!(
+        client.try_correct_prices(&600, &invalid),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert_eq!(
+        client.try_correct_prices(&300, &corrections),
+        Err(Ok(Error::NoPrice.into()))
+    );
+    assert_eq!(
+        client.price(&assets.get_unchecked(0), &600).unwrap().price,
+        normalize_price(1)
+    );
+
+    client.correct_prices(&600, &corrections);
+    for index in 0..assets.len() {
+        let expected = if index < 3 { index as i128 + 5 } else { 1 };
+        assert_eq!(
+            client
+                .price(&assets.get_unchecked(index), &600)
+                .unwrap()
+                .price,
+            normalize_price(expected)
+        );
+    }
+}
+
+#[test]
+fn records_at_risk_test() {
+    let (env, client, init_data) = init_contract();
+
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &300_000,
+    );
+    //regular retention period keeps records alive for thousands of ledgers
+    assert!(client.records_at_risk(&100).is_empty());
+    assert_eq!(
+        client.records_at_risk(&20_000),
+        Vec::from_array(&env, [300])
+    );
+
+    //disabled retention leaves records with the minimum TTL
+    client.set_history_retention_period(&0);
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &600_000,
+    );
+    //the estimate is conservative for records written under the previous retention period
+    assert_eq!(
+        client.records_at_risk(&100),
+        Vec::from_array(&env, [300, 600])
+    );
+    env.as_contract(&client.address, || {
+        let ttl = env.storage().temporary().get_ttl(&600_000u64);
+        assert!(ttl <= 16);
+    });
+}
+
+#[test]
+fn read_bump_test() {
+    let (env, client, init_data) = init_contract();
+
+    //retention period of 245 seconds keeps records for 100 ledgers
+    client.set_history_retention_period(&245_000);
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &900_000,
+    );
+    let asset = init_data.assets.get_unchecked(0);
+    let get_ttl = || {
+        env.as_contract(&client.address, || {
+            env.storage().temporary().get_ttl(&900_000u64)
+        })
+    };
+    assert_eq!(get_ttl(), 100);
+    //bring the record close to expiration
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        sequence_number: ledger_info.sequence_number + 95,
+        ..ledger_info
+    });
+    assert_eq!(get_ttl(), 5);
+
+    //reads don't touch TTL by default
+    assert!(!client.read_bump());
+    client.price(&asset, &900).unwrap();
+    assert_eq!(get_ttl(), 5);
+
+    client.set_read_bump(&true);
+    client.price(&asset, &900).unwrap();
+    assert_eq!(get_ttl(), 100);
+
+    //extension is limited to the remaining part of the retention period
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: ledger_info.timestamp + 100,
+        sequence_number: ledger_info.sequence_number + 95,
+        ..ledger_info
+    });
+    client.price(&asset, &900).unwrap();
+    assert_eq!(get_ttl(), 60);
+}
+
+#[test]
+fn set_prices_batch_test() {
+    let (env, client, init_data) = init_contract();
+
+    assert_eq!(client.max_batch_size(), 50);
+    client.set_max_batch_size(&3);
+    assert_eq!(client.max_batch_size(), 3);
+
+    let mut batch = Vec::new(&env);
+    for (timestamp, price) in [(300_000, 1), (600_000, 2), (900_000, 3)] {
+        batch.push_back((
+            generate_updates(&env, &init_data.assets, normalize_price(price)),
+            timestamp,
+        ));
+    }
+    //batch at the limit is accepted
+    client.set_prices_batch(&batch);
+    let asset = init_data.assets.get_unchecked(0);
+    assert_eq!(client.lastprice(&asset).unwrap().price, normalize_price(3));
+    assert_eq!(client.prices(&asset, &3).unwrap().len(), 3);
+
+    //one over the limit is rejected
+    batch.push_back((
+        generate_updates(&env, &init_data.assets, normalize_price(4)),
+        1_200_000,
+    ));
+    assert_eq!(
+        client.try_set_prices_batch(&batch),
+        Err(Ok(Error::BatchSizeExceeded.into()))
+    );
+    assert_eq!(
+        client.try_set_max_batch_size(&0),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn set_prices_batch_auto_pause_test() {
+    let (env, client, init_data) = init_contract();
+
+    client.set_deviation_limits(&1_000, &5_000);
+    let mut batch = Vec::new(&env);
+    //the second snapshot deviates enough to pause the contract
+    for (timestamp, price) in [(300_000, 100), (600_000, 300), (900_000, 300)] {
+        batch.push_back((
+            generate_updates(&env, &init_data.assets, normalize_price(price)),
+            timestamp,
+        ));
+    }
+    client.set_prices_batch(&batch);
+
+    //the pause persists, snapshots starting from the deviating one are not applied
+    assert!(client.paused());
+    let asset = init_data.assets.get_unchecked(0);
+    assert_eq!(client.last_timestamp(), 300);
+    assert_eq!(
+        client.price(&asset, &300).unwrap().price,
+        normalize_price(100)
+    );
+    assert!(client.price(&asset, &600).is_none());
+}
+
+#[test]
+fn purge_expired_test() {
+    let (env, client, init_data) = init_contract();
+
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    let assets = init_data.assets;
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 20 * 86_400,
+        ..ledger_info
+    });
+    //first asset expired 10 days ago, second one - yesterday
+    env.as_contract(&client.address, || {
+        let mut expiration = Vec::new(&env);
+        expiration.push_back(10 * 86_400_000u64);
+        expiration.push_back(19 * 86_400_000u64);
+        for _ in 2..assets.len() {
+            expiration.push_back(200 * 86_400_000u64);
+        }
+        env.storage().instance().set(&"expiration", &expiration);
+    });
+
+    assert_eq!(client.purge_expired(&5), 1);
+    //purged asset is no longer quoted, other positions are intact
+    assert!(client.lastprice(&assets.get_unchecked(0)).is_none());
+    assert_eq!(
+        client.try_expires(&assets.get_unchecked(0)),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert_eq!(
+        client.expires(&assets.get_unchecked(1)),
+        Some(19 * 86_400_000)
+    );
+    //purged asset is no longer listed right after the purge
+    let mut listed = assets.clone();
+    listed.remove(0);
+    assert_eq!(client.assets(), listed);
+    //purged slot can't receive new prices
+    assert_eq!(
+        client.try_set_price(
+            &generate_updates(&env, &assets, normalize_price(1)),
+            &(20 * 86_400_000),
+        ),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    let mut prices = Vec::from_array(&env, [0]);
+    for _ in 1..assets.len() {
+        prices.push_back(normalize_price(1));
+    }
+    client.set_price(
+        &PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices: Vec::from_slice(&env, &[normalize_price(1); 9]),
+        },
+        &(20 * 86_400_000),
+    );
+    assert!(client.lastprice(&assets.get_unchecked(0)).is_none());
+    assert_eq!(
+        client.lastprice(&assets.get_unchecked(1)).unwrap().price,
+        normalize_price(1)
+    );
+    //already purged assets are not counted again
+    assert_eq!(client.purge_expired(&5), 0);
+}
+
+#[test]
+fn fee_config_explicitly_set_test() {
+    let env = Env::default();
+    let contract_id = env.register(PulseOracleContract, ());
+    let client = PulseOracleContractClient::new(&env, &contract_id);
+    //never configured contract falls back to default fee config
+    assert!(!client.fee_config_explicitly_set());
+    assert_ne!(client.fee_config(), FeeConfig::None);
+
+    let (env, client, init_data) = init_contract();
+    assert!(client.fee_config_explicitly_set());
+    //emulate old contract state
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&"retention");
+        env.storage().instance().remove(&"expiration");
+    });
+    assert!(!client.fee_config_explicitly_set());
+    //default config doesn't trigger expiration tracking for new assets
+    let assets = generate_assets(&env, 1, 100);
+    client.add_assets(&assets);
+    assert!(client.expires(&assets.first_unchecked()).is_none());
+    assert!(client
+        .expires(&init_data.assets.first_unchecked())
+        .is_none());
 }
 
 #[test]
 fn authorization_successful_test() {
     let (env, client, config_data) = init_contract();
 
-    let period: u64 = 100;
+    let period: u64 = 100_000;
     //set prices for assets
     client
         .mock_auths(&[MockAuth {
@@ -281,3 +1806,142 @@ fn authorization_failed_test() {
         }])
         .set_history_retention_period(&period);
 }
+
+#[test]
+fn price_update_mask_mismatch_test() {
+    let (env, client, _) = init_contract();
+
+    let price = normalize_price(100);
+    let mask = generate_update_record_mask(&env, &Vec::from_array(&env, [price, price]));
+    //more prices than positions set in the mask
+    let too_many = PriceUpdate {
+        prices: Vec::from_array(&env, [price, price, price]),
+        mask: mask.clone(),
+    };
+    assert_eq!(
+        client.try_set_price(&too_many, &600_000),
+        Err(Ok(Error::InvalidPricesUpdate.into()))
+    );
+    //fewer prices than positions set in the mask
+    let too_few = PriceUpdate {
+        prices: Vec::from_array(&env, [price]),
+        mask: mask.clone(),
+    };
+    assert_eq!(
+        client.try_set_price(&too_few, &600_000),
+        Err(Ok(Error::InvalidPricesUpdate.into()))
+    );
+    assert_eq!(client.last_timestamp(), 0);
+    //matching update is accepted
+    let update = PriceUpdate {
+        prices: Vec::from_array(&env, [price, price]),
+        mask,
+    };
+    client.set_price(&update, &600_000);
+    assert_eq!(client.last_timestamp(), 600);
+}
+
+#[test]
+fn zero_prices_update_test() {
+    let (env, client, _) = init_contract();
+
+    let price = normalize_price(100);
+    let mask = generate_update_record_mask(&env, &Vec::from_array(&env, [price, price]));
+    let update = PriceUpdate {
+        prices: Vec::from_array(&env, [0, 0]),
+        mask,
+    };
+    assert_eq!(
+        client.try_validate_update(&update, &600_000, &false),
+        Err(Ok(Error::ZeroPrices))
+    );
+    assert_eq!(
+        client.try_set_price(&update, &600_000),
+        Err(Ok(Error::ZeroPrices.into()))
+    );
+    //nothing has been recorded
+    assert_eq!(client.last_timestamp(), 0);
+}
+
+#[test]
+fn validate_update_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = init_data.assets;
+    let updates = generate_updates(&env, &assets, normalize_price(100));
+    assert_eq!(
+        client.try_validate_update(&updates, &600_000, &false),
+        Ok(Ok(()))
+    );
+
+    //misaligned and future timestamps
+    assert_eq!(
+        client.try_validate_update(&updates, &600_001, &false),
+        Err(Ok(Error::InvalidTimestamp))
+    );
+    assert_eq!(
+        client.try_validate_update(&updates, &1_200_000, &false),
+        Err(Ok(Error::InvalidTimestamp))
+    );
+    //mask doesn't match the number of prices
+    let mut broken = updates.clone();
+    broken.prices.pop_back();
+    assert_eq!(
+        client.try_validate_update(&broken, &600_000, &false),
+        Err(Ok(Error::InvalidPricesUpdate))
+    );
+    //out of bounds price
+    client.set_price_bounds(
+        &assets.get_unchecked(0),
+        &normalize_price(1),
+        &normalize_price(2),
+    );
+    assert_eq!(
+        client.try_validate_update(&updates, &600_000, &false),
+        Err(Ok(Error::PriceOutOfBounds))
+    );
+    //nothing has been stored
+    assert_eq!(client.last_timestamp(), 0);
+
+    //validation results match set_price
+    assert_eq!(
+        client.try_set_price(&broken, &600_000),
+        Err(Ok(Error::InvalidPricesUpdate.into()))
+    );
+    let updates = generate_updates(&env, &assets, normalize_price(1));
+    assert_eq!(
+        client.try_validate_update(&updates, &600_000, &false),
+        Ok(Ok(()))
+    );
+    client.set_price(&updates, &600_000);
+    //records older than the last one are rejected unless validated as a backfill
+    assert_eq!(
+        client.try_validate_update(&updates, &300_000, &false),
+        Err(Ok(Error::InvalidTimestamp))
+    );
+    assert_eq!(
+        client.try_validate_update(&updates, &300_000, &true),
+        Ok(Ok(()))
+    );
+    client.backfill_price(&updates, &300_000);
+    assert!(client.price(&assets.get_unchecked(0), &300).is_some());
+
+    //deviation checks are applied without pausing the contract
+    client.set_deviation_limits(&1_000, &5_000);
+    let moderate = generate_updates(&env, &assets, normalize_price(1) * 6 / 5);
+    assert_eq!(
+        client.try_validate_update(&moderate, &900_000, &false),
+        Err(Ok(Error::DeviationExceeded))
+    );
+    let severe = generate_updates(&env, &assets, normalize_price(2));
+    assert_eq!(
+        client.try_validate_update(&severe, &900_000, &false),
+        Err(Ok(Error::Paused))
+    );
+    assert!(!client.paused());
+    client.pause();
+    assert_eq!(
+        client.try_validate_update(&updates, &900_000, &false),
+        Err(Ok(Error::Paused))
+    );
+}
@@ -2,12 +2,14 @@
 extern crate std;
 
 use crate::tests::setup_tests::{
-    convert_to_seconds, generate_random_updates, generate_updates, init_contract, normalize_price,
+    convert_to_seconds, generate_assets, generate_random_updates, generate_update_record_mask,
+    generate_updates, init_contract, normalize_price, prepare_contract_config, RESOLUTION,
 };
+use crate::{PulseOracleContract, PulseOracleContractClient};
 use oracle::prices;
-use oracle::types::FeeConfig;
-use soroban_sdk::testutils::{Ledger, LedgerInfo};
-use soroban_sdk::Vec;
+use oracle::types::{Asset, CacheEvictionPolicy, Error, FeeConfig, PriceData, PriceUpdate};
+use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+use soroban_sdk::{Address, Bytes, Symbol, Vec};
 
 #[test]
 fn version_test() {
@@ -45,6 +47,20 @@ fn last_timestamp_test() {
     assert_eq!(result, convert_to_seconds(600_000));
 }
 
+#[test]
+fn last_timestamp_ms_test() {
+    let (env, client, init_data) = init_contract();
+
+    assert_eq!(client.last_timestamp_ms(), 0);
+
+    let updates = generate_updates(&env, &init_data.assets, normalize_price(100));
+    client.set_price(&updates, &300_000);
+    client.set_price(&updates, &600_000);
+
+    assert_eq!(client.last_timestamp_ms(), 600_000);
+    assert_eq!(client.last_timestamp(), convert_to_seconds(600_000));
+}
+
 #[test]
 fn price_test() {
     let (env, client, init_data) = init_contract();
@@ -87,7 +103,7 @@ fn prices_test() {
     for i in 0..257 {
         let timestamp = 600_000 + i * 300_000;
 
-        if timestamp != 900_000 && timestamp != 1200_000 {
+        if timestamp != 900_000 && timestamp != 1_200_000 {
             let updates = generate_random_updates(&env, &assets, normalize_price(100));
             history_prices.push_front((timestamp, updates.clone()));
             //set prices for assets
@@ -130,3 +146,1266 @@ fn prices_test() {
     assert!(had_prices);
     assert!(had_gaps);
 }
+
+#[test]
+fn x_twap_checked_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    let base = assets.get_unchecked(0);
+    let quote = assets.get_unchecked(1);
+    for timestamp in [300_000, 600_000, 900_000] {
+        client.set_price(
+            &generate_updates(&env, assets, normalize_price(2)),
+            &timestamp,
+        );
+    }
+    assert_eq!(
+        client.x_twap_checked(&base, &quote, &3),
+        client.x_twap(&base, &quote, &3)
+    );
+    assert_eq!(
+        client.x_twap_checked(&base, &quote, &3).unwrap(),
+        normalize_price(1)
+    );
+
+    //freeze the base leg, only other assets get updated
+    let mut prices = Vec::new(&env);
+    prices.push_back(0);
+    for _ in 1..assets.len() {
+        prices.push_back(normalize_price(2));
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: Vec::from_slice(&env, &[normalize_price(2); 9]),
+    };
+    client.set_price(&updates, &1_200_000);
+    assert!(client.x_twap_checked(&base, &quote, &3).is_none());
+}
+
+#[test]
+fn strict_mode_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.first_unchecked();
+    let unknown = Asset::Stellar(Address::generate(&env));
+    //non-strict mode returns None
+    assert!(!client.strict_mode());
+    assert!(client.lastprice(&asset).is_none());
+    assert!(client.lastprice(&unknown).is_none());
+    assert!(client.price(&asset, &600).is_none());
+    assert!(client.x_price(&asset, &unknown, &600).is_none());
+
+    client.set_strict_mode(&true);
+    assert!(client.strict_mode());
+    assert_eq!(client.try_lastprice(&asset), Err(Ok(Error::NoPrice.into())));
+    assert_eq!(
+        client.try_lastprice(&unknown),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+    assert_eq!(
+        client.try_price(&asset, &600),
+        Err(Ok(Error::NoPrice.into()))
+    );
+    assert_eq!(
+        client.try_x_price(&asset, &unknown, &600),
+        Err(Ok(Error::AssetMissing.into()))
+    );
+
+    //existing prices are returned as usual
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &600_000,
+    );
+    assert_eq!(client.lastprice(&asset).unwrap().price, normalize_price(1));
+    assert_eq!(
+        client
+            .x_price(&asset, &init_data.assets.get_unchecked(1), &600)
+            .unwrap()
+            .price,
+        normalize_price(1)
+    );
+}
+
+#[test]
+fn missing_in_latest_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(1)),
+        &600_000,
+    );
+    assert!(client.missing_in_latest().is_empty());
+
+    //omit assets with indexes divisible by 3
+    let mut prices = Vec::new(&env);
+    let mut expected = Vec::new(&env);
+    for (index, asset) in assets.iter().enumerate() {
+        if index % 3 == 0 {
+            prices.push_back(0);
+            expected.push_back(asset);
+        } else {
+            prices.push_back(normalize_price(1));
+        }
+    }
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: Vec::from_slice(&env, &[normalize_price(1); 6]),
+    };
+    client.set_price(&updates, &900_000);
+    assert_eq!(client.missing_in_latest(), expected);
+}
+
+#[test]
+fn updated_assets_at_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    assert!(client.updated_assets_at(&600).is_empty());
+
+    //update only assets with odd indexes
+    let mut prices = Vec::new(&env);
+    let mut expected = Vec::new(&env);
+    for (index, asset) in assets.iter().enumerate() {
+        if index % 2 == 1 {
+            prices.push_back(normalize_price(1));
+            expected.push_back(asset);
+        } else {
+            prices.push_back(0);
+        }
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: Vec::from_slice(&env, &[normalize_price(1); 5]),
+    };
+    client.set_price(&updates, &600_000);
+
+    assert_eq!(client.updated_assets_at(&600), expected);
+    //timestamp gets normalized
+    assert_eq!(client.updated_assets_at(&601), expected);
+    assert!(client.updated_assets_at(&300).is_empty());
+}
+
+#[test]
+fn asset_resolution_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 2_100,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    let coarse = assets.get_unchecked(0);
+    let regular = assets.get_unchecked(1);
+    assert_eq!(client.asset_resolution(&coarse), Some(300));
+
+    //misaligned resolution is rejected
+    assert_eq!(
+        client.try_set_asset_resolution(&coarse, &450),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    //resolution overflowing the internal representation is rejected
+    assert_eq!(
+        client.try_set_asset_resolution(&coarse, &u32::MAX),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    client.set_asset_resolution(&coarse, &600);
+    assert_eq!(client.asset_resolution(&coarse), Some(600));
+    assert_eq!(client.asset_resolution(&regular), Some(300));
+
+    for timestamp in (300_000..=2_100_000).step_by(300_000) {
+        client.set_price(
+            &generate_updates(&env, assets, normalize_price(timestamp as i128 / 300_000)),
+            &timestamp,
+        );
+    }
+
+    //coarse asset prices are spaced by its own resolution
+    let prices = client.prices(&coarse, &3).unwrap();
+    assert_eq!(prices.len(), 3);
+    let timestamps: std::vec::Vec<u64> = prices.iter().map(|p| p.timestamp).collect();
+    assert_eq!(timestamps, [1_800, 1_200, 600]);
+    assert_eq!(prices.first_unchecked().price, normalize_price(6));
+    assert_eq!(client.lastprice(&coarse).unwrap().timestamp, 1_800);
+    assert_eq!(client.price(&coarse, &2_000).unwrap().timestamp, 1_800);
+
+    //other assets keep using global resolution
+    let prices = client.prices(&regular, &3).unwrap();
+    let timestamps: std::vec::Vec<u64> = prices.iter().map(|p| p.timestamp).collect();
+    assert_eq!(timestamps, [2_100, 1_800, 1_500]);
+
+    //reset to the global resolution
+    client.set_asset_resolution(&coarse, &0);
+    assert_eq!(client.lastprice(&coarse).unwrap().timestamp, 2_100);
+}
+
+#[test]
+fn asset_timestamps_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 2_100,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    let asset = assets.get_unchecked(0);
+    assert!(client.asset_timestamps(&asset, &10).is_empty());
+
+    //skip some periods for the first asset
+    for timestamp in (300_000..=2_100_000).step_by(300_000) {
+        let mut prices = Vec::new(&env);
+        let mut updated = Vec::new(&env);
+        for index in 0..assets.len() {
+            if index == 0 && (timestamp == 600_000 || timestamp == 1_500_000) {
+                prices.push_back(0);
+            } else {
+                prices.push_back(normalize_price(1));
+                updated.push_back(normalize_price(1));
+            }
+        }
+        let updates = PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices: updated,
+        };
+        client.set_price(&updates, &timestamp);
+    }
+
+    let timestamps = client.asset_timestamps(&asset, &10);
+    assert_eq!(
+        timestamps,
+        Vec::from_slice(&env, &[2_100, 1_800, 1_200, 900, 300])
+    );
+    assert_eq!(
+        client.asset_timestamps(&asset, &2),
+        Vec::from_slice(&env, &[2_100, 1_800])
+    );
+    //listed timestamps hold prices
+    for timestamp in timestamps.iter() {
+        assert!(client.price(&asset, &timestamp).is_some());
+    }
+    assert_eq!(
+        client.asset_timestamps(&assets.get_unchecked(1), &10).len(),
+        7
+    );
+}
+
+#[test]
+fn prices_history_start_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 600,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    let asset = assets.get_unchecked(0);
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(1)),
+        &300_000,
+    );
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(3)),
+        &600_000,
+    );
+
+    //requesting more records than available stops at the first period
+    let prices = client.prices(&asset, &5).unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices.get_unchecked(0).timestamp, 600);
+    assert_eq!(prices.get_unchecked(0).price, normalize_price(3));
+    assert_eq!(prices.get_unchecked(1).timestamp, 300);
+    assert_eq!(prices.get_unchecked(1).price, normalize_price(1));
+    //exact number of records includes the oldest period
+    assert_eq!(client.prices(&asset, &2).unwrap(), prices);
+    assert_eq!(client.twap(&asset, &2), Some(normalize_price(2)));
+    assert!(client.twap(&asset, &3).is_none());
+}
+
+#[test]
+fn preview_history_mask_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let history_mask = || {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .get::<_, Bytes>(&"history")
+                .unwrap_or_else(|| Bytes::new(&env))
+        })
+    };
+
+    let mut prices = Vec::new(&env);
+    let mut updated = Vec::new(&env);
+    for index in 0..assets.len() {
+        if index % 3 == 0 {
+            prices.push_back(0);
+        } else {
+            prices.push_back(normalize_price(1));
+            updated.push_back(normalize_price(1));
+        }
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: updated,
+    };
+    for timestamp in [300_000, 600_000] {
+        let expected = client.preview_history_mask(&history_mask(), &prices);
+        client.set_price(&updates, &timestamp);
+        assert_eq!(history_mask(), expected);
+    }
+}
+
+#[test]
+fn storage_first_read_strategy_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    //only part of the records fit into the cache
+    client.set_cache_size(&2);
+    for timestamp in (300_000..=1_500_000).step_by(300_000) {
+        client.set_price(
+            &generate_random_updates(&env, assets, normalize_price(100)),
+            &timestamp,
+        );
+    }
+    let read_all = || {
+        let mut res = Vec::new(&env);
+        for asset in assets.iter() {
+            for timestamp in [300, 600, 900, 1_200, 1_500] {
+                res.push_back(client.price(&asset, &timestamp));
+            }
+            res.push_back(client.lastprice(&asset));
+        }
+        res
+    };
+    assert!(!client.storage_first());
+    let cache_first = read_all();
+
+    client.set_storage_first(&true);
+    assert!(client.storage_first());
+    assert_eq!(read_all(), cache_first);
+    let (base, quote) = (assets.get_unchecked(0), assets.get_unchecked(1));
+    let storage_first = client.x_prices(&base, &quote, &5);
+    client.set_storage_first(&false);
+    assert_eq!(client.x_prices(&base, &quote, &5), storage_first);
+}
+
+#[test]
+fn lastprice_debug_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.first_unchecked();
+    assert_eq!(client.lastprice_debug(&asset), (None, false));
+
+    client.set_cache_size(&1);
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &600_000,
+    );
+    let (price, cached) = client.lastprice_debug(&asset);
+    assert_eq!(price, client.lastprice(&asset));
+    assert!(cached);
+
+    //evicted record is served from the temporary storage
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(200)),
+        &900_000,
+    );
+    //the asset with a coarser resolution is quoted from the evicted record
+    client.set_asset_resolution(&asset, &600);
+    let (price, cached) = client.lastprice_debug(&asset);
+    assert_eq!(price, client.lastprice(&asset));
+    assert_eq!(price.unwrap().price, normalize_price(100));
+    assert!(!cached);
+    let (price, cached) = client.lastprice_debug(&init_data.assets.get_unchecked(1));
+    assert_eq!(price.unwrap().price, normalize_price(200));
+    assert!(cached);
+}
+
+#[test]
+fn oldest_timestamp_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let asset = assets.get_unchecked(0);
+    assert!(client.oldest_timestamp(&asset).is_none());
+
+    //the first asset gets its first price only in the second update
+    for timestamp in (300_000..=1_500_000).step_by(300_000) {
+        let ledger_info = env.ledger().get();
+        env.ledger().set(LedgerInfo {
+            timestamp: timestamp / 1000,
+            ..ledger_info
+        });
+        let mut prices = Vec::new(&env);
+        let mut updated = Vec::new(&env);
+        for index in 0..assets.len() {
+            if index == 0 && timestamp == 300_000 {
+                prices.push_back(0);
+            } else {
+                prices.push_back(normalize_price(1));
+                updated.push_back(normalize_price(1));
+            }
+        }
+        let updates = PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices: updated,
+        };
+        client.set_price(&updates, &timestamp);
+    }
+    assert_eq!(client.oldest_timestamp(&asset), Some(600));
+    assert_eq!(client.oldest_timestamp(&assets.get_unchecked(1)), Some(300));
+    assert!(client
+        .oldest_timestamp(&Asset::Stellar(Address::generate(&env)))
+        .is_none());
+}
+
+#[test]
+fn populated_depth_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let asset = assets.get_unchecked(0);
+    assert_eq!(client.populated_depth(&asset), 0);
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    //the first asset skips the second update, nothing is recorded in the third period
+    for timestamp in [300_000, 600_000, 1_200_000, 1_500_000] {
+        let mut prices = Vec::new(&env);
+        let mut updated = Vec::new(&env);
+        for index in 0..assets.len() {
+            if index == 0 && timestamp == 600_000 {
+                prices.push_back(0);
+            } else {
+                prices.push_back(normalize_price(1));
+                updated.push_back(normalize_price(1));
+            }
+        }
+        let updates = PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices: updated,
+        };
+        client.set_price(&updates, &timestamp);
+    }
+    assert_eq!(client.populated_depth(&asset), 3);
+    assert_eq!(client.populated_depth(&assets.get_unchecked(1)), 4);
+    assert_eq!(
+        client.populated_depth(&Asset::Stellar(Address::generate(&env))),
+        0
+    );
+}
+
+#[test]
+fn x_last_price_floor_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let base = assets.get_unchecked(0);
+    let quote = assets.get_unchecked(1);
+    //tiny quote price
+    let mut prices = Vec::new(&env);
+    for index in 0..assets.len() {
+        prices.push_back(if index == 1 { 1 } else { normalize_price(2) });
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices,
+    };
+    client.set_price(&updates, &600_000);
+    //no floor by default
+    assert_eq!(client.asset_price_floor(&quote), Some(0));
+    assert!(client.x_last_price(&base, &quote).is_some());
+
+    client.set_asset_price_floor(&quote, &1_000);
+    assert_eq!(client.asset_price_floor(&quote), Some(1_000));
+    assert!(client.x_last_price(&base, &quote).is_none());
+    //the floor applies to denominators only
+    assert!(client.x_last_price(&quote, &base).is_some());
+    assert_eq!(
+        client.try_set_asset_price_floor(&quote, &-1),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+
+    client.set_asset_price_floor(&quote, &0);
+    assert!(client.x_last_price(&base, &quote).is_some());
+}
+
+#[test]
+fn single_asset_layout_test() {
+    let (env, client, init_data) = init_contract();
+
+    //oracle tracking only the first asset
+    let asset = init_data.assets.get_unchecked(0);
+    let single_client =
+        PulseOracleContractClient::new(&env, &env.register(PulseOracleContract, ()));
+    let mut config = prepare_contract_config(&env);
+    config.assets = Vec::from_array(&env, [asset.clone()]);
+    single_client.config(&config);
+
+    for (timestamp, price) in [(300_000, 3), (600_000, 5), (900_000, 4)] {
+        client.set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(price)),
+            &timestamp,
+        );
+        single_client.set_price(
+            &generate_updates(&env, &config.assets, normalize_price(price)),
+            &timestamp,
+        );
+    }
+
+    //read results are identical for both layouts
+    assert_eq!(single_client.lastprice(&asset), client.lastprice(&asset));
+    assert_eq!(
+        single_client.price(&asset, &600),
+        client.price(&asset, &600)
+    );
+    assert_eq!(single_client.prices(&asset, &3), client.prices(&asset, &3));
+    assert_eq!(single_client.twap(&asset, &3), client.twap(&asset, &3));
+    assert_eq!(
+        single_client.updated_assets_at(&600),
+        Vec::from_array(&env, [asset.clone()])
+    );
+
+    //records written before adding more assets remain readable
+    single_client.add_assets(&Vec::from_array(&env, [init_data.assets.get_unchecked(1)]));
+    env.ledger().set_timestamp(1_200);
+    single_client.set_price(
+        &generate_updates(&env, &single_client.assets(), normalize_price(6)),
+        &1_200_000,
+    );
+    assert_eq!(
+        single_client.price(&asset, &600),
+        client.price(&asset, &600)
+    );
+    assert_eq!(
+        single_client.lastprice(&asset).unwrap().price,
+        normalize_price(6)
+    );
+    env.as_contract(&single_client.address, || {
+        assert!(!env
+            .storage()
+            .instance()
+            .get::<_, bool>(&"single_layout")
+            .unwrap());
+    });
+    //records rewritten after adding more assets are served for the right asset
+    single_client.backfill_price(
+        &generate_updates(&env, &single_client.assets(), normalize_price(7)),
+        &900_000,
+    );
+    for asset in single_client.assets().iter() {
+        assert_eq!(
+            single_client.price(&asset, &900).unwrap().price,
+            normalize_price(7)
+        );
+    }
+}
+
+// Compare read costs of the single-asset layout against the general layout,
+// run with `cargo test single_asset_layout_benchmark -- --nocapture` to see the figures
+#[test]
+fn single_asset_layout_benchmark_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.get_unchecked(0);
+    let single_client =
+        PulseOracleContractClient::new(&env, &env.register(PulseOracleContract, ()));
+    let mut config = prepare_contract_config(&env);
+    config.assets = Vec::from_array(&env, [asset.clone()]);
+    single_client.config(&config);
+    for timestamp in [300_000, 600_000, 900_000] {
+        client.set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(1)),
+            &timestamp,
+        );
+        single_client.set_price(
+            &generate_updates(&env, &config.assets, normalize_price(1)),
+            &timestamp,
+        );
+    }
+    //records are read from the temporary storage
+    client.set_cache_size(&0);
+    single_client.set_cache_size(&0);
+
+    let mut budget = env.cost_estimate().budget();
+    let mut measure = |name: &str, read: fn(&PulseOracleContractClient, &Asset)| {
+        let mut res = [(0u64, 0u64); 2];
+        for (i, oracle) in [&client, &single_client].into_iter().enumerate() {
+            budget.reset_unlimited();
+            read(oracle, &asset);
+            res[i] = (budget.cpu_instruction_cost(), budget.memory_bytes_cost());
+        }
+        let [(general_cpu, general_mem), (single_cpu, single_mem)] = res;
+        std::println!(
+            "{name}: cpu {general_cpu} -> {single_cpu} ({}%), mem {general_mem} -> {single_mem} ({}%)",
+            (general_cpu - single_cpu) * 100 / general_cpu,
+            (general_mem - single_mem) * 100 / general_mem
+        );
+        assert!(single_cpu < general_cpu);
+        assert!(single_mem < general_mem);
+    };
+    measure("price", |oracle, asset| {
+        oracle.price(asset, &600);
+    });
+    measure("prices", |oracle, asset| {
+        oracle.prices(asset, &3);
+    });
+    measure("twap", |oracle, asset| {
+        oracle.twap(asset, &3);
+    });
+}
+
+#[test]
+fn pair_supported_test() {
+    let (env, client, init_data) = init_contract();
+
+    let base = init_data.assets.get_unchecked(0);
+    let quote = init_data.assets.get_unchecked(1);
+    let unknown = Asset::Stellar(Address::generate(&env));
+    let other_unknown = Asset::Stellar(Address::generate(&env));
+
+    assert!(client.pair_supported(&base, &quote));
+    assert!(!client.pair_supported(&base, &unknown));
+    assert!(!client.pair_supported(&unknown, &quote));
+    assert!(!client.pair_supported(&unknown, &other_unknown));
+}
+
+#[test]
+fn self_cross_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let missing = assets.get_unchecked(0);
+    let priced = assets.get_unchecked(1);
+    let mut prices = Vec::new(&env);
+    let mut updated = Vec::new(&env);
+    for index in 0..assets.len() {
+        if index == 0 {
+            prices.push_back(0);
+        } else {
+            prices.push_back(normalize_price(5));
+            updated.push_back(normalize_price(5));
+        }
+    }
+    client.set_price(
+        &PriceUpdate {
+            mask: generate_update_record_mask(&env, &prices),
+            prices: updated,
+        },
+        &600_000,
+    );
+    //self-cross price equals 1 expressed with global decimals
+    assert_eq!(
+        client.x_last_price(&priced, &priced),
+        Some(PriceData {
+            price: 10i128.pow(client.decimals()),
+            timestamp: 600,
+        })
+    );
+    //asset without a record at the timestamp has no self-cross price either
+    assert_eq!(client.x_last_price(&missing, &missing), None);
+    assert_eq!(client.x_price(&missing, &missing, &600), None);
+    assert_eq!(client.x_price(&priced, &priced, &300), None);
+}
+
+#[test]
+fn time_getter_units_test() {
+    let (env, client, init_data) = init_contract();
+
+    //public getters report seconds unless suffixed with _ms
+    assert_eq!(client.resolution(), 300);
+    assert_eq!(client.resolution_ms(), 300_000);
+    assert_eq!(client.history_retention_period(), Some(30_000));
+    assert_eq!(client.history_retention_period_ms(), Some(30_000_000));
+    let asset = init_data.assets.get_unchecked(0);
+    assert_eq!(client.asset_resolution(&asset), Some(300));
+    //expiration keeps milliseconds for backward compatibility, seconds are suffixed with _s
+    let fee_asset = env.register_stellar_asset_contract_v2(init_data.admin.clone());
+    client.set_fee_config(&FeeConfig::Some((fee_asset.address(), 7)));
+    let expires = client.expires(&asset).unwrap();
+    assert_eq!(client.expires_s(&asset), Some(expires / 1000));
+
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &600_000,
+    );
+    assert_eq!(client.last_timestamp(), 600);
+    assert_eq!(client.last_timestamp_ms(), 600_000);
+    assert_eq!(client.lastprice(&asset).unwrap().timestamp, 600);
+    assert_eq!(
+        client.asset_timestamps(&asset, &1),
+        Vec::from_array(&env, [600])
+    );
+}
+
+#[test]
+fn x_twap_available_test() {
+    let (env, client, init_data) = init_contract();
+
+    let assets = &init_data.assets;
+    let base = assets.get_unchecked(0);
+    let quote = assets.get_unchecked(1);
+    assert!(!client.x_twap_available(&base, &quote, &3));
+
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(2)),
+        &300_000,
+    );
+    //the quote asset misses the update
+    let mut prices = Vec::new(&env);
+    let mut updated = Vec::new(&env);
+    for index in 0..assets.len() {
+        if index == 1 {
+            prices.push_back(0);
+        } else {
+            prices.push_back(normalize_price(2));
+            updated.push_back(normalize_price(2));
+        }
+    }
+    let updates = PriceUpdate {
+        mask: generate_update_record_mask(&env, &prices),
+        prices: updated,
+    };
+    client.set_price(&updates, &600_000);
+    client.set_price(
+        &generate_updates(&env, assets, normalize_price(2)),
+        &900_000,
+    );
+
+    //insufficient coverage
+    assert!(!client.x_twap_available(&base, &quote, &3));
+    assert!(client.x_twap(&base, &quote, &3).is_none());
+    assert!(client.x_twap_available(&base, &quote, &1));
+    assert!(client.x_twap(&base, &quote, &1).is_some());
+
+    //sufficient coverage
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_500,
+        ..ledger_info
+    });
+    for timestamp in [1_200_000, 1_500_000] {
+        client.set_price(
+            &generate_updates(&env, assets, normalize_price(2)),
+            &timestamp,
+        );
+    }
+    assert!(client.x_twap_available(&base, &quote, &3));
+    assert!(client.x_twap(&base, &quote, &3).is_some());
+    assert!(!client.x_twap_available(&base, &quote, &6));
+    assert!(client.x_twap(&base, &quote, &6).is_none());
+
+    //availability follows the records cap, the minimum TWAP window and the price floor
+    client.set_max_records(&2);
+    assert!(!client.x_twap_available(&base, &quote, &3));
+    client.set_max_records(&3);
+    client.set_min_twap_records(&3);
+    assert!(!client.x_twap_available(&base, &quote, &2));
+    assert!(client.x_twap_available(&base, &quote, &3));
+    client.set_asset_price_floor(&quote, &normalize_price(3));
+    assert!(!client.x_twap_available(&base, &quote, &3));
+    assert!(client.x_twap(&base, &quote, &3).is_none());
+}
+
+#[test]
+fn min_coverage_test() {
+    let (env, client, init_data) = init_contract();
+
+    let asset = init_data.assets.first_unchecked();
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &600_000,
+    );
+    assert!(client.lastprice(&asset).is_some());
+    assert_eq!(
+        client.try_set_min_coverage(&4, &3),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.try_set_min_coverage(&3, &257),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+
+    //require 3 updates within 4 recent periods
+    client.set_min_coverage(&3, &4);
+    assert_eq!(client.min_coverage(), Some((3, 4)));
+    assert!(client.lastprice(&asset).is_none());
+    assert!(client.twap(&asset, &1).is_none());
+
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &900_000,
+    );
+    assert!(client.lastprice(&asset).is_none());
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(100)),
+        &1_200_000,
+    );
+    assert_eq!(
+        client.lastprice(&asset).unwrap().price,
+        normalize_price(100)
+    );
+    assert_eq!(client.twap(&asset, &3), Some(normalize_price(100)));
+
+    //remove the guard
+    client.set_min_coverage(&0, &0);
+    assert_eq!(client.min_coverage(), None);
+}
+
+#[test]
+fn min_twap_records_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 900,
+        ..ledger_info
+    });
+    let assets = &init_data.assets;
+    let base = assets.get_unchecked(0);
+    let quote = assets.get_unchecked(1);
+    for timestamp in [300_000, 600_000, 900_000] {
+        client.set_price(
+            &generate_updates(&env, assets, normalize_price(2)),
+            &timestamp,
+        );
+    }
+    assert_eq!(client.min_twap_records(), 0);
+    assert_eq!(client.twap(&base, &1), Some(normalize_price(2)));
+
+    client.set_min_twap_records(&3);
+    assert_eq!(client.min_twap_records(), 3);
+    assert_eq!(
+        client.try_twap(&base, &2),
+        Err(Ok(Error::TwapWindowTooShort.into()))
+    );
+    assert_eq!(
+        client.try_x_twap(&base, &quote, &2),
+        Err(Ok(Error::TwapWindowTooShort.into()))
+    );
+    assert_eq!(
+        client.try_x_twap_checked(&base, &quote, &2),
+        Err(Ok(Error::TwapWindowTooShort.into()))
+    );
+    assert_eq!(client.twap(&base, &3), Some(normalize_price(2)));
+    assert_eq!(client.x_twap(&base, &quote, &3), Some(normalize_price(1)));
+
+    //disable the check
+    client.set_min_twap_records(&0);
+    assert_eq!(client.twap(&base, &1), Some(normalize_price(2)));
+}
+
+#[test]
+fn base_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    //no prices yet
+    assert!(client.base_price().is_none());
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 600,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(2)),
+        &600_000,
+    );
+    assert_eq!(
+        client.base_price(),
+        Some(PriceData {
+            price: normalize_price(1),
+            timestamp: 600,
+        })
+    );
+
+    //the oracle becomes stale after two missed rounds
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    assert!(client.base_price().is_none());
+}
+
+#[test]
+fn placeholder_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 600,
+        ..ledger_info
+    });
+    assert_eq!(client.placeholder_price(), None);
+    client.set_placeholder_price(&normalize_price(1));
+    assert_eq!(client.placeholder_price(), Some(normalize_price(1)));
+
+    //assets added before enabling placeholders are not affected
+    let existing = init_data.assets.first_unchecked();
+    assert!(client.lastprice(&existing).is_none());
+
+    let assets = generate_assets(&env, 1, 100);
+    let asset = assets.first_unchecked();
+    client.add_assets(&assets);
+    assert_eq!(
+        client.lastprice(&asset),
+        Some(PriceData {
+            price: normalize_price(1),
+            timestamp: 0,
+        })
+    );
+
+    //updates without the asset price keep the placeholder
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(2)),
+        &300_000,
+    );
+    assert_eq!(client.lastprice(&asset).unwrap().timestamp, 0);
+
+    let all_assets = client.assets();
+    client.set_price(
+        &generate_updates(&env, &all_assets, normalize_price(3)),
+        &600_000,
+    );
+    assert_eq!(
+        client.lastprice(&asset),
+        Some(PriceData {
+            price: normalize_price(3),
+            timestamp: 600,
+        })
+    );
+
+    //once priced, missing records are reported as usual
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_800,
+        ..ledger_info
+    });
+    assert!(client.lastprice(&asset).is_none());
+}
+
+#[test]
+fn next_update_eta_test() {
+    let (env, client, init_data) = init_contract();
+
+    //no data yet
+    assert_eq!(client.next_update_eta(), None);
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 610,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &600_000,
+    );
+    //next round is expected at 900 seconds
+    assert_eq!(client.next_update_eta(), Some(290));
+
+    //overdue update, but the oracle is still fresh
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_000,
+        ..ledger_info
+    });
+    assert_eq!(client.next_update_eta(), Some(0));
+
+    //stale oracle
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_200,
+        ..ledger_info
+    });
+    assert_eq!(client.next_update_eta(), None);
+}
+
+#[test]
+fn has_prices_test() {
+    let (env, client, init_data) = init_contract();
+
+    //prices are missing for the first asset in the second period
+    let mut partial = Vec::new(&env);
+    partial.push_back(0);
+    for _ in 1..init_data.assets.len() {
+        partial.push_back(normalize_price(1));
+    }
+    let full = generate_updates(&env, &init_data.assets, normalize_price(1));
+    let gap = PriceUpdate {
+        mask: generate_update_record_mask(&env, &partial),
+        prices: Vec::from_slice(&env, &[normalize_price(1); 9]),
+    };
+    client.set_price(&full, &300_000);
+    client.set_price(&gap, &600_000);
+    client.set_price(&full, &900_000);
+
+    let periods = Vec::from_array(&env, [0, 1, 2, 3, 255]);
+    env.as_contract(&client.address, || {
+        for asset_index in [0u32, 1] {
+            let bulk = prices::has_prices(&env, asset_index, periods.clone());
+            assert_eq!(bulk.len(), periods.len());
+            for (periods_ago, available) in periods.iter().zip(bulk.iter()) {
+                assert_eq!(available, prices::has_price(&env, asset_index, periods_ago));
+            }
+        }
+        assert_eq!(
+            prices::has_prices(&env, 0, Vec::from_array(&env, [0, 1, 2, 300])),
+            Vec::from_array(&env, [true, false, true, false])
+        );
+    });
+}
+
+#[test]
+fn strided_cache_eviction_test() {
+    let (env, client, init_data) = init_contract();
+
+    assert_eq!(
+        client.cache_eviction_policy(),
+        CacheEvictionPolicy::Contiguous
+    );
+    assert_eq!(
+        client.try_set_cache_eviction_policy(&CacheEvictionPolicy::Strided(1)),
+        Err(Ok(Error::InvalidAmount.into()))
+    );
+    client.set_cache_size(&3);
+    client.set_cache_eviction_policy(&CacheEvictionPolicy::Strided(2));
+
+    for timestamp in (1..=6).map(|round| round * RESOLUTION as u64) {
+        let ledger_info = env.ledger().get();
+        env.ledger().set(LedgerInfo {
+            timestamp: timestamp / 1000,
+            ..ledger_info
+        });
+        client.set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(1)),
+            &timestamp,
+        );
+    }
+    //the most recent record and records spaced by two rounds are kept
+    assert_eq!(
+        client.cache_records(),
+        Vec::from_array(&env, [1_800, 1_200, 600])
+    );
+
+    //default policy keeps the newest contiguous block
+    client.set_cache_eviction_policy(&CacheEvictionPolicy::Contiguous);
+    for timestamp in [2_100_000, 2_400_000] {
+        let ledger_info = env.ledger().get();
+        env.ledger().set(LedgerInfo {
+            timestamp: timestamp / 1000,
+            ..ledger_info
+        });
+        client.set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(1)),
+            &timestamp,
+        );
+    }
+    assert_eq!(
+        client.cache_records(),
+        Vec::from_array(&env, [2_400, 2_100, 1_800])
+    );
+}
+
+#[test]
+fn mask_popcount_test() {
+    let (env, client, init_data) = init_contract();
+
+    let price = normalize_price(1);
+    for prices in [
+        Vec::new(&env),
+        Vec::from_array(&env, [price]),
+        Vec::from_array(&env, [0, price, 0, price, price]),
+        Vec::from_array(&env, [price; 10]),
+        Vec::from_array(&env, [price; 256]),
+    ] {
+        let mask = generate_update_record_mask(&env, &prices);
+        let expected = prices.iter().filter(|price| *price > 0).count() as u32;
+        assert_eq!(client.mask_popcount(&mask), expected);
+    }
+    //matches the number of prices in generated updates
+    let update = generate_updates(&env, &init_data.assets, price);
+    assert_eq!(client.mask_popcount(&update.mask), update.prices.len());
+}
+
+#[test]
+fn price_checked_future_timestamp_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 600,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &600_000,
+    );
+    let asset = init_data.assets.first_unchecked();
+    assert_eq!(
+        client.price_checked(&asset, &600),
+        Some(PriceData {
+            price: normalize_price(1),
+            timestamp: 600,
+        })
+    );
+    //missing record
+    assert_eq!(client.price_checked(&asset, &300), None);
+    //future-dated read is reported explicitly, while the regular read returns None
+    assert!(client.price(&asset, &900).is_none());
+    assert_eq!(
+        client.try_price_checked(&asset, &900),
+        Err(Ok(Error::InvalidTimestamp))
+    );
+    assert_eq!(
+        client.try_price_checked(&Asset::Stellar(Address::generate(&env)), &600),
+        Err(Ok(Error::AssetMissing))
+    );
+}
+
+#[test]
+fn base_address_symbol_test() {
+    let (env, client, init_data) = init_contract();
+
+    let Asset::Stellar(base) = init_data.base_asset else {
+        panic!("stellar base expected");
+    };
+    assert_eq!(client.base_address(), Some(base));
+    assert_eq!(client.base_symbol(), None);
+
+    let other_client = PulseOracleContractClient::new(&env, &env.register(PulseOracleContract, ()));
+    let mut config = prepare_contract_config(&env);
+    config.base_asset = Asset::Other(Symbol::new(&env, "USD"));
+    other_client.config(&config);
+    assert_eq!(other_client.base_address(), None);
+    assert_eq!(other_client.base_symbol(), Some(Symbol::new(&env, "USD")));
+}
+
+#[test]
+fn prices_range_test() {
+    let (env, client, init_data) = init_contract();
+
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_800,
+        ..ledger_info
+    });
+    for price in 1..=6 {
+        client.set_price(
+            &generate_updates(&env, &init_data.assets, normalize_price(price)),
+            &(price as u64 * 300_000),
+        );
+    }
+    client.set_max_records(&3);
+    assert_eq!(client.max_records(), 3);
+
+    let asset = init_data.assets.first_unchecked();
+    let record = |price: i128| PriceData {
+        price: normalize_price(price),
+        timestamp: price as u64 * 300,
+    };
+    //the range contains 5 records, only the most recent ones are returned
+    let recent = Vec::from_array(&env, [record(5), record(4), record(3)]);
+    assert_eq!(
+        client.prices_range(&asset, &300, &1_500),
+        Some(recent.clone())
+    );
+    assert_eq!(
+        client.prices_range_paged(&asset, &300, &1_500, &0),
+        Some((recent, true))
+    );
+    assert_eq!(
+        client.prices_range_paged(&asset, &300, &1_500, &3),
+        Some((Vec::from_array(&env, [record(2), record(1)]), false))
+    );
+    //range fitting the cap is not truncated
+    assert_eq!(
+        client.prices_range_paged(&asset, &1_200, &1_800, &0),
+        Some((
+            Vec::from_array(&env, [record(6), record(5), record(4)]),
+            false
+        ))
+    );
+    //the cap also applies to the last N records
+    assert_eq!(client.prices(&asset, &5).unwrap().len(), 3);
+    assert_eq!(
+        client.prices_range(&Asset::Stellar(Address::generate(&env)), &300, &1_500),
+        None
+    );
+}
+
+#[test]
+fn prices_range_without_retention_test() {
+    let (env, client, init_data) = init_contract();
+
+    client.set_history_retention_period(&0);
+    let ledger_info = env.ledger().get();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1_000_000_200,
+        ..ledger_info
+    });
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(1)),
+        &1_000_000_200_000,
+    );
+
+    //the scan over the whole timeline is limited to the history mask range
+    env.cost_estimate().budget().reset_default();
+    let asset = init_data.assets.first_unchecked();
+    assert_eq!(
+        client.prices_range(&asset, &0, &2_000_000_000),
+        Some(Vec::from_array(
+            &env,
+            [PriceData {
+                price: normalize_price(1),
+                timestamp: 1_000_000_200,
+            }]
+        ))
+    );
+}
+
+#[test]
+fn raw_asset_price_test() {
+    let (env, client, init_data) = init_contract();
+
+    client.set_price(
+        &generate_updates(&env, &init_data.assets, normalize_price(3)),
+        &600_000,
+    );
+    let asset = init_data.assets.first_unchecked();
+    let price = client.price(&asset, &600).unwrap();
+    //raw price matches the normalized one, but the timestamp is taken in milliseconds as stored
+    assert_eq!(client.raw_asset_price(&asset, &600_000), Some(price.price));
+    assert_eq!(client.raw_asset_price(&asset, &600), None);
+    assert_eq!(client.raw_asset_price(&asset, &300_000), None);
+}
@@ -38,13 +38,13 @@ pub(super) fn init_contract<'a>() -> (Env, PulseOracleContractClient<'a>, Config
     (env, client, init_data)
 }
 
-fn prepare_contract_config(env: &Env) -> ConfigData {
-    let admin = Address::generate(&env);
+pub(super) fn prepare_contract_config(env: &Env) -> ConfigData {
+    let admin = Address::generate(env);
     ConfigData {
         admin: admin.clone(),
         history_retention_period: (100 * RESOLUTION).into(),
-        assets: generate_assets(&env, 10, 0),
-        base_asset: Asset::Stellar(Address::generate(&env)),
+        assets: generate_assets(env, 10, 0),
+        base_asset: Asset::Stellar(Address::generate(env)),
         decimals: 14,
         resolution: RESOLUTION,
         cache_size: 0,
@@ -75,7 +75,7 @@ pub(super) fn generate_update_record_mask(e: &Env, updates: &Vec<i128>) -> Bytes
 }
 
 pub(super) fn generate_updates(env: &Env, assets: &Vec<Asset>, price: i128) -> PriceUpdate {
-    let mut updates = Vec::new(&env);
+    let mut updates = Vec::new(env);
     for _ in assets.iter() {
         updates.push_back(price);
     }
@@ -92,28 +92,29 @@ fn get_random_bool() -> bool {
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .subsec_nanos();
-    let random_bool = (nanos % 200) == 0;
-    random_bool
+    nanos.is_multiple_of(200)
 }
 
 pub(super) fn generate_random_updates(env: &Env, assets: &Vec<Asset>, price: i128) -> PriceUpdate {
-    let mut updates = Vec::new(&env);
+    let mut updates = Vec::new(env);
+    let mut prices = Vec::new(env);
     for _ in assets.iter() {
         let price = if get_random_bool() { 0 } else { price };
         updates.push_back(price);
+        //update record contains prices only for positions set in the mask
+        if price > 0 {
+            prices.push_back(price);
+        }
     }
     let mask = generate_update_record_mask(env, &updates);
-    PriceUpdate {
-        prices: updates,
-        mask,
-    }
+    PriceUpdate { prices, mask }
 }
 
 pub(super) fn generate_assets(e: &Env, count: usize, start_index: u32) -> Vec<Asset> {
-    let mut assets = Vec::new(&e);
+    let mut assets = Vec::new(e);
     for i in 0..count {
         if i % 2 == 0 {
-            assets.push_back(Asset::Stellar(Address::generate(&e)));
+            assets.push_back(Asset::Stellar(Address::generate(e)));
         } else {
             assets.push_back(Asset::Other(Symbol::new(
                 e,